@@ -13,8 +13,8 @@ use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_metrics::{metrics::Gauge, Metrics};
 use reth_primitives_traits::{
-    BlockBody as _, IndexedTx, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
-    SignedTransaction,
+    BlockBody as _, GotExpected, IndexedTx, NodePrimitives, RecoveredBlock, SealedBlock,
+    SealedHeader, SignedTransaction,
 };
 use reth_storage_api::StateProviderBox;
 use reth_trie::{updates::TrieUpdates, HashedPostState};
@@ -24,6 +24,9 @@ use tokio::sync::{broadcast, watch};
 /// Size of the broadcast channel used to notify canonical state events.
 const CANON_STATE_NOTIFICATION_CHANNEL_SIZE: usize = 256;
 
+/// Size of the broadcast channel used to notify pending block changes.
+const PENDING_BLOCK_NOTIFICATION_CHANNEL_SIZE: usize = 64;
+
 /// Metrics for the in-memory state.
 #[derive(Metrics)]
 #[metrics(scope = "blockchain_tree.in_mem_state")]
@@ -141,6 +144,8 @@ pub(crate) struct CanonicalInMemoryStateInner<N: NodePrimitives> {
     pub(crate) in_memory_state: InMemoryState<N>,
     /// A broadcast stream that emits events when the canonical chain is updated.
     pub(crate) canon_state_notification_sender: CanonStateNotificationSender<N>,
+    /// A broadcast stream that emits the pending block whenever it changes.
+    pub(crate) pending_block_sender: broadcast::Sender<SealedBlock<N::Block>>,
 }
 
 impl<N: NodePrimitives> CanonicalInMemoryStateInner<N> {
@@ -188,12 +193,14 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         let chain_info_tracker = ChainInfoTracker::new(header, finalized, safe);
         let (canon_state_notification_sender, _) =
             broadcast::channel(CANON_STATE_NOTIFICATION_CHANNEL_SIZE);
+        let (pending_block_sender, _) = broadcast::channel(PENDING_BLOCK_NOTIFICATION_CHANNEL_SIZE);
 
         Self {
             inner: Arc::new(CanonicalInMemoryStateInner {
                 chain_info_tracker,
                 in_memory_state,
                 canon_state_notification_sender,
+                pending_block_sender,
             }),
         }
     }
@@ -214,10 +221,12 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         let in_memory_state = InMemoryState::default();
         let (canon_state_notification_sender, _) =
             broadcast::channel(CANON_STATE_NOTIFICATION_CHANNEL_SIZE);
+        let (pending_block_sender, _) = broadcast::channel(PENDING_BLOCK_NOTIFICATION_CHANNEL_SIZE);
         let inner = CanonicalInMemoryStateInner {
             chain_info_tracker,
             in_memory_state,
             canon_state_notification_sender,
+            pending_block_sender,
         };
 
         Self { inner: Arc::new(inner) }
@@ -241,8 +250,15 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
 
     /// Updates the pending block with the given block.
     ///
+    /// If this changes the pending block, the block is sent to all
+    /// [pending block subscribers](Self::subscribe_pending_block).
+    ///
     /// Note: This assumes that the parent block of the pending block is canonical.
     pub fn set_pending_block(&self, pending: ExecutedBlock<N>) {
+        let hash = pending.recovered_block().hash();
+        let changed = self.pending_block_num_hash().map(|pending| pending.hash) != Some(hash);
+        let block = changed.then(|| pending.recovered_block().sealed_block().clone());
+
         // fetch the state of the pending block's parent block
         let parent = self.state_by_hash(pending.recovered_block().parent_hash());
         let pending = BlockState::with_parent(pending, parent);
@@ -250,6 +266,10 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             p.replace(pending);
         });
         self.inner.in_memory_state.update_metrics();
+
+        if let Some(block) = block {
+            self.inner.pending_block_sender.send(block).ok();
+        }
     }
 
     /// Append new blocks to the in memory state.
@@ -493,6 +513,14 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.canon_state_notification_sender.subscribe()
     }
 
+    /// Subscribe to pending block changes.
+    ///
+    /// A block is only sent if it replaces a different pending block, setting the same pending
+    /// block again is not notified.
+    pub fn subscribe_pending_block(&self) -> broadcast::Receiver<SealedBlock<N::Block>> {
+        self.inner.pending_block_sender.subscribe()
+    }
+
     /// Subscribe to new safe block events.
     pub fn subscribe_safe_block(&self) -> watch::Receiver<Option<SealedHeader<N::BlockHeader>>> {
         self.inner.chain_info_tracker.subscribe_safe_block()
@@ -780,6 +808,20 @@ impl<N: NodePrimitives> ExecutedBlock<N> {
     }
 }
 
+/// Errors of a malformed [`NewCanonicalChain`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+pub enum NewCanonicalChainError {
+    /// The new chain contains no blocks, so there is no new tip.
+    #[display("new canonical chain contains no blocks")]
+    EmptyNewChain,
+    /// The blocks of the new chain are not contiguous.
+    #[display("blocks of the new canonical chain are not contiguous: block number {_0}")]
+    NewChainNotContiguous(#[error(not(source))] GotExpected<BlockNumber>),
+    /// The blocks of the reorged chain are not contiguous.
+    #[display("blocks of the reorged chain are not contiguous: block number {_0}")]
+    OldChainNotContiguous(#[error(not(source))] GotExpected<BlockNumber>),
+}
+
 /// Non-empty chain of blocks.
 #[derive(Debug)]
 pub enum NewCanonicalChain<N: NodePrimitives = EthPrimitives> {
@@ -814,48 +856,51 @@ impl<N: NodePrimitives<SignedTx: SignedTransaction>> NewCanonicalChain<N> {
         }
     }
 
-    /// Converts the new chain into a notification that will be emitted to listeners
-    pub fn to_chain_notification(&self) -> CanonStateNotification<N> {
+    /// Converts the new chain into a notification that will be emitted to listeners.
+    ///
+    /// Returns an error if the blocks of either chain are not contiguous.
+    pub fn to_chain_notification(
+        &self,
+    ) -> Result<CanonStateNotification<N>, NewCanonicalChainError> {
         match self {
             Self::Commit { new } => {
-                let new = Arc::new(new.iter().fold(Chain::default(), |mut chain, exec| {
-                    chain.append_block(
-                        exec.recovered_block().clone(),
-                        exec.execution_outcome().clone(),
-                    );
-                    chain
-                }));
-                CanonStateNotification::Commit { new }
+                let new = Arc::new(
+                    Self::to_chain(new).map_err(NewCanonicalChainError::NewChainNotContiguous)?,
+                );
+                Ok(CanonStateNotification::Commit { new })
             }
             Self::Reorg { new, old } => {
-                let new = Arc::new(new.iter().fold(Chain::default(), |mut chain, exec| {
-                    chain.append_block(
-                        exec.recovered_block().clone(),
-                        exec.execution_outcome().clone(),
-                    );
-                    chain
-                }));
-                let old = Arc::new(old.iter().fold(Chain::default(), |mut chain, exec| {
-                    chain.append_block(
-                        exec.recovered_block().clone(),
-                        exec.execution_outcome().clone(),
-                    );
-                    chain
-                }));
-                CanonStateNotification::Reorg { new, old }
+                let new = Arc::new(
+                    Self::to_chain(new).map_err(NewCanonicalChainError::NewChainNotContiguous)?,
+                );
+                let old = Arc::new(
+                    Self::to_chain(old).map_err(NewCanonicalChainError::OldChainNotContiguous)?,
+                );
+                Ok(CanonStateNotification::Reorg { new, old })
             }
         }
     }
 
+    /// Collects the given blocks into a [`Chain`].
+    fn to_chain(blocks: &[ExecutedBlock<N>]) -> Result<Chain<N>, GotExpected<BlockNumber>> {
+        blocks.iter().try_fold(Chain::default(), |mut chain, exec| {
+            chain.try_append_block(
+                exec.recovered_block().clone(),
+                exec.execution_outcome().clone(),
+            )?;
+            Ok(chain)
+        })
+    }
+
     /// Returns the new tip of the chain.
     ///
-    /// Returns the new tip for [`Self::Reorg`] and [`Self::Commit`] variants which commit at least
-    /// 1 new block.
-    pub fn tip(&self) -> &SealedBlock<N::Block> {
+    /// Returns an error if the new chain is empty.
+    pub fn tip(&self) -> Result<&SealedBlock<N::Block>, NewCanonicalChainError> {
         match self {
-            Self::Commit { new } | Self::Reorg { new, .. } => {
-                new.last().expect("non empty blocks").recovered_block()
-            }
+            Self::Commit { new } | Self::Reorg { new, .. } => new
+                .last()
+                .map(ExecutedBlock::sealed_block)
+                .ok_or(NewCanonicalChainError::EmptyNewChain),
         }
     }
 }
@@ -1226,6 +1271,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_subscribe_pending_block() {
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+        let mut test_block_builder: TestBlockBuilder = TestBlockBuilder::default();
+        let block1 = test_block_builder.get_executed_block_with_number(0, B256::random());
+        let block2 =
+            test_block_builder.get_executed_block_with_number(1, block1.recovered_block().hash());
+        let fork2 =
+            test_block_builder.get_executed_block_with_number(1, block1.recovered_block().hash());
+        state.update_chain(NewCanonicalChain::Commit { new: vec![block1] });
+
+        let mut pending = state.subscribe_pending_block();
+
+        state.set_pending_block(block2.clone());
+        assert_eq!(pending.try_recv().unwrap(), block2.recovered_block().sealed_block().clone());
+
+        // setting the same pending block again is not notified
+        state.set_pending_block(block2);
+        assert!(pending.try_recv().is_err());
+
+        state.set_pending_block(fork2.clone());
+        assert_eq!(pending.try_recv().unwrap(), fork2.recovered_block().sealed_block().clone());
+        assert!(pending.try_recv().is_err());
+    }
+
     #[test]
     fn test_canonical_in_memory_state_state_provider() {
         let mut test_block_builder: TestBlockBuilder = TestBlockBuilder::default();
@@ -1425,7 +1495,7 @@ mod tests {
         let chain_commit = NewCanonicalChain::Commit { new: vec![block0.clone(), block1.clone()] };
 
         assert_eq!(
-            chain_commit.to_chain_notification(),
+            chain_commit.to_chain_notification().unwrap(),
             CanonStateNotification::Commit {
                 new: Arc::new(Chain::new(
                     vec![block0.recovered_block().clone(), block1.recovered_block().clone()],
@@ -1442,7 +1512,7 @@ mod tests {
         };
 
         assert_eq!(
-            chain_reorg.to_chain_notification(),
+            chain_reorg.to_chain_notification().unwrap(),
             CanonStateNotification::Reorg {
                 old: Arc::new(Chain::new(
                     vec![block1.recovered_block().clone(), block2.recovered_block().clone()],
@@ -1457,4 +1527,30 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_malformed_chain_update() {
+        let mut test_block_builder: TestBlockBuilder = TestBlockBuilder::default();
+        let block0 = test_block_builder.get_executed_block_with_number(0, B256::random());
+        let block1 =
+            test_block_builder.get_executed_block_with_number(1, block0.recovered_block.hash());
+        let block2 =
+            test_block_builder.get_executed_block_with_number(2, block1.recovered_block.hash());
+
+        let empty = NewCanonicalChain::<EthPrimitives>::Commit { new: vec![] };
+        assert_eq!(empty.tip().unwrap_err(), NewCanonicalChainError::EmptyNewChain);
+
+        let gap = NewCanonicalChain::Commit { new: vec![block0.clone(), block2.clone()] };
+        assert_eq!(gap.tip().unwrap(), block2.sealed_block());
+        assert_eq!(
+            gap.to_chain_notification().unwrap_err(),
+            NewCanonicalChainError::NewChainNotContiguous(GotExpected { got: 2, expected: 1 })
+        );
+
+        let reorg = NewCanonicalChain::Reorg { new: vec![block1], old: vec![block0, block2] };
+        assert_eq!(
+            reorg.to_chain_notification().unwrap_err(),
+            NewCanonicalChainError::OldChainNotContiguous(GotExpected { got: 2, expected: 1 })
+        );
+    }
 }
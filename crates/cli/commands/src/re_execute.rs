@@ -10,7 +10,10 @@ use eyre::WrapErr;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_consensus::FullConsensus;
-use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_evm::{
+    execute::{BasicBlockExecutor, ExecutionStrategy, Executor},
+    ConfigureEvm,
+};
 use reth_primitives_traits::{format_gas_throughput, BlockBody, GotExpected};
 use reth_provider::{
     BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory, ReceiptProvider,
@@ -44,6 +47,12 @@ pub struct Command<C: ChainSpecParser> {
     /// Number of tasks to run in parallel
     #[arg(long, default_value = "10")]
     num_tasks: u64,
+
+    /// Number of threads each task speculatively executes the transactions of a block on.
+    ///
+    /// Transactions are executed sequentially if not set.
+    #[arg(long)]
+    tx_workers: Option<usize>,
 }
 
 impl<C: ChainSpecParser> Command<C> {
@@ -83,6 +92,10 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             }
         };
 
+        let strategy = self.tx_workers.map_or(ExecutionStrategy::Sequential, |workers| {
+            ExecutionStrategy::Parallel { workers }
+        });
+
         let (stats_tx, mut stats_rx) = mpsc::unbounded_channel();
 
         let mut tasks = JoinSet::new();
@@ -98,12 +111,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             let db_at = db_at.clone();
             let stats_tx = stats_tx.clone();
             tasks.spawn_blocking(move || {
-                let mut executor = evm_config.batch_executor(db_at(start_block - 1));
+                let mut executor = BasicBlockExecutor::new(&evm_config, db_at(start_block - 1));
                 for block in start_block..end_block {
                     let block = provider_factory
                         .recovered_block(block.into(), TransactionVariant::NoHash)?
                         .unwrap();
-                    let result = executor.execute_one(&block)?;
+                    let result = executor.execute_one_with_strategy(&block, strategy)?;
 
                     if let Err(err) = consensus
                         .validate_block_post_execution(&block, &result)
@@ -156,7 +169,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
 
                     // Reset DB once in a while to avoid OOM
                     if executor.size_hint() > 1_000_000 {
-                        executor = evm_config.batch_executor(db_at(block.number()));
+                        executor = BasicBlockExecutor::new(&evm_config, db_at(block.number()));
                     }
                 }
 
@@ -242,6 +242,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                             .with_max_buffered_blocks_size_bytes(
                                 config.stages.bodies.downloader_max_buffered_blocks_size_bytes,
                             )
+                            .with_max_buffered_responses(
+                                config.stages.bodies.downloader_max_buffered_responses,
+                            )
                             .with_concurrent_requests_range(
                                 config.stages.bodies.downloader_min_concurrent_requests..=
                                     config.stages.bodies.downloader_max_concurrent_requests,
@@ -253,6 +256,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                 StageEnum::Senders => (
                     Box::new(SenderRecoveryStage::new(SenderRecoveryConfig {
                         commit_threshold: batch_size,
+                        max_threads: config.stages.sender_recovery.max_threads,
                     })),
                     None,
                 ),
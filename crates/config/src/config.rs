@@ -220,6 +220,13 @@ pub struct BodiesConfig {
     ///
     /// Default: 2GB
     pub downloader_max_buffered_blocks_size_bytes: usize,
+    /// The maximum number of responses to buffer internally.
+    ///
+    /// Responses that arrive while the buffer is full are dropped and requested again later,
+    /// unless they contain the next block to return.
+    ///
+    /// Default: `1_000`
+    pub downloader_max_buffered_responses: usize,
     /// The minimum number of requests to send concurrently.
     ///
     /// Default: 5
@@ -237,6 +244,7 @@ impl Default for BodiesConfig {
             downloader_request_limit: 200,
             downloader_stream_batch_size: 1_000,
             downloader_max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
+            downloader_max_buffered_responses: 1_000,
             downloader_min_concurrent_requests: 5,
             downloader_max_concurrent_requests: 100,
         }
@@ -250,11 +258,16 @@ impl Default for BodiesConfig {
 pub struct SenderRecoveryConfig {
     /// The maximum number of transactions to process before committing progress to the database.
     pub commit_threshold: u64,
+    /// The maximum number of threads used to recover senders.
+    ///
+    /// If not set, senders are recovered on the global rayon pool, which uses all available
+    /// cores.
+    pub max_threads: Option<usize>,
 }
 
 impl Default for SenderRecoveryConfig {
     fn default() -> Self {
-        Self { commit_threshold: 5_000_000 }
+        Self { commit_threshold: 5_000_000, max_threads: None }
     }
 }
 
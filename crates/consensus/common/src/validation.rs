@@ -345,6 +345,42 @@ pub fn validate_against_parent_timestamp<H: BlockHeader>(
     Ok(())
 }
 
+/// Validates the gas limit against the parent gas limit.
+///
+/// The gas limit must differ from the parent gas limit by less than the parent gas limit divided
+/// by the [`GAS_LIMIT_BOUND_DIVISOR`], and must not be below the [`MINIMUM_GAS_LIMIT`].
+///
+/// This does not account for the elasticity multiplier applied to the parent gas limit at the
+/// London fork block, see [`validate_against_parent_gas_limit`].
+#[inline]
+pub const fn validate_gas_limit(
+    parent_gas_limit: u64,
+    gas_limit: u64,
+) -> Result<(), ConsensusError> {
+    // Check for an increase in gas limit beyond the allowed threshold.
+    if gas_limit > parent_gas_limit {
+        if gas_limit - parent_gas_limit >= parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR {
+            return Err(ConsensusError::GasLimitInvalidIncrease {
+                parent_gas_limit,
+                child_gas_limit: gas_limit,
+            })
+        }
+    }
+    // Check for a decrease in gas limit beyond the allowed threshold.
+    else if parent_gas_limit - gas_limit >= parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR {
+        return Err(ConsensusError::GasLimitInvalidDecrease {
+            parent_gas_limit,
+            child_gas_limit: gas_limit,
+        })
+    }
+    // Check if the self gas limit is below the minimum required limit.
+    else if gas_limit < MINIMUM_GAS_LIMIT {
+        return Err(ConsensusError::GasLimitInvalidMinimum { child_gas_limit: gas_limit })
+    }
+
+    Ok(())
+}
+
 /// Validates gas limit against parent gas limit.
 ///
 /// The maximum allowable difference between self and parent gas limits is determined by the
@@ -369,28 +405,7 @@ pub fn validate_against_parent_gas_limit<
         parent.gas_limit()
     };
 
-    // Check for an increase in gas limit beyond the allowed threshold.
-    if header.gas_limit() > parent_gas_limit {
-        if header.gas_limit() - parent_gas_limit >= parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR {
-            return Err(ConsensusError::GasLimitInvalidIncrease {
-                parent_gas_limit,
-                child_gas_limit: header.gas_limit(),
-            })
-        }
-    }
-    // Check for a decrease in gas limit beyond the allowed threshold.
-    else if parent_gas_limit - header.gas_limit() >= parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR {
-        return Err(ConsensusError::GasLimitInvalidDecrease {
-            parent_gas_limit,
-            child_gas_limit: header.gas_limit(),
-        })
-    }
-    // Check if the self gas limit is below the minimum required limit.
-    else if header.gas_limit() < MINIMUM_GAS_LIMIT {
-        return Err(ConsensusError::GasLimitInvalidMinimum { child_gas_limit: header.gas_limit() })
-    }
-
-    Ok(())
+    validate_gas_limit(parent_gas_limit, header.gas_limit())
 }
 
 /// Validates that the EIP-4844 header fields are correct with respect to the parent block. This
@@ -503,4 +518,98 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn eip1559_base_fee_against_parent() {
+        let chain_spec = ChainSpecBuilder::mainnet().london_activated().build();
+        let parent = |gas_used| Header {
+            number: 1,
+            gas_used,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        };
+        let child = |base_fee| Header {
+            number: 2,
+            timestamp: 12,
+            base_fee_per_gas: Some(base_fee),
+            ..Default::default()
+        };
+
+        // (parent gas used, expected base fee)
+        let vectors = [
+            // exactly at the gas target, the base fee is unchanged
+            (15_000_000, 1_000_000_000),
+            // full block, the base fee increases by 12.5%
+            (30_000_000, 1_125_000_000),
+            // empty block, the base fee decreases by 12.5%
+            (0, 875_000_000),
+            (20_000_000, 1_041_666_666),
+            (10_000_000, 958_333_334),
+        ];
+        for (gas_used, expected) in vectors {
+            assert_eq!(
+                validate_against_parent_eip1559_base_fee(
+                    &child(expected),
+                    &parent(gas_used),
+                    &chain_spec
+                ),
+                Ok(())
+            );
+        }
+
+        assert_eq!(
+            validate_against_parent_eip1559_base_fee(
+                &child(1_000_000_001),
+                &parent(15_000_000),
+                &chain_spec
+            ),
+            Err(ConsensusError::BaseFeeDiff(GotExpected {
+                got: 1_000_000_001,
+                expected: 1_000_000_000
+            }))
+        );
+
+        // the London fork block uses the initial base fee regardless of the parent
+        let fork_block = Header {
+            base_fee_per_gas: Some(alloy_eips::eip1559::INITIAL_BASE_FEE),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_against_parent_eip1559_base_fee(&fork_block, &parent(0), &chain_spec),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn gas_limit_bounds() {
+        let parent_gas_limit = 30_000_000;
+        let max_delta = parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR;
+
+        assert_eq!(validate_gas_limit(parent_gas_limit, parent_gas_limit), Ok(()));
+        assert_eq!(validate_gas_limit(parent_gas_limit, parent_gas_limit + max_delta - 1), Ok(()));
+        assert_eq!(
+            validate_gas_limit(parent_gas_limit, parent_gas_limit + max_delta),
+            Err(ConsensusError::GasLimitInvalidIncrease {
+                parent_gas_limit,
+                child_gas_limit: parent_gas_limit + max_delta
+            })
+        );
+        assert_eq!(validate_gas_limit(parent_gas_limit, parent_gas_limit - max_delta + 1), Ok(()));
+        assert_eq!(
+            validate_gas_limit(parent_gas_limit, parent_gas_limit - max_delta),
+            Err(ConsensusError::GasLimitInvalidDecrease {
+                parent_gas_limit,
+                child_gas_limit: parent_gas_limit - max_delta
+            })
+        );
+
+        // the gas limit can't drop below the minimum, even within the allowed delta
+        let parent_gas_limit = MINIMUM_GAS_LIMIT;
+        assert_eq!(validate_gas_limit(parent_gas_limit, MINIMUM_GAS_LIMIT), Ok(()));
+        assert_eq!(
+            validate_gas_limit(parent_gas_limit, MINIMUM_GAS_LIMIT - 1),
+            Err(ConsensusError::GasLimitInvalidMinimum { child_gas_limit: MINIMUM_GAS_LIMIT - 1 })
+        );
+    }
 }
@@ -73,6 +73,10 @@ pub trait Consensus<B: Block>: HeaderValidator<B::Header> {
 }
 
 /// `HeaderValidator` is a protocol that validates headers and their relationships.
+///
+/// Implementations are responsible for fork-aware rules: the validated header carries its block
+/// number and timestamp, so a single implementation is expected to dispatch on the active
+/// hardforks internally instead of being replaced at a fork boundary.
 #[auto_impl::auto_impl(&, Arc)]
 pub trait HeaderValidator<H = Header>: Debug + Send + Sync {
     /// Validate if header is correct and follows consensus specification.
@@ -42,6 +42,9 @@ pub const DEFAULT_PREWARM_MAX_CONCURRENCY: usize = 16;
 
 const DEFAULT_BLOCK_BUFFER_LIMIT: u32 = 256;
 const DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH: u32 = 256;
+const DEFAULT_MAX_SIDECHAIN_BLOCKS: u32 = 256;
+const DEFAULT_MAX_REORG_RECORDS: usize = 128;
+const DEFAULT_MAX_BLOCKS_PER_HEIGHT: usize = 8;
 const DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE: usize = 4;
 const DEFAULT_CROSS_BLOCK_CACHE_SIZE: u64 = 4 * 1024 * 1024 * 1024;
 
@@ -78,6 +81,13 @@ pub struct TreeConfig {
     block_buffer_limit: u32,
     /// Number of invalid headers to keep in cache.
     max_invalid_header_cache_length: u32,
+    /// Maximum number of executed blocks kept in memory that neither belong to the canonical
+    /// chain nor extend the canonical head.
+    max_sidechain_blocks: u32,
+    /// Maximum number of recent reorgs to keep a record of.
+    max_reorg_records: usize,
+    /// Maximum number of executed blocks kept in memory at the same height.
+    max_blocks_per_height: usize,
     /// Maximum number of blocks to execute sequentially in a batch.
     ///
     /// This is used as a cutoff to prevent long-running sequential block execution when we receive
@@ -140,6 +150,9 @@ impl Default for TreeConfig {
             memory_block_buffer_target: DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
             block_buffer_limit: DEFAULT_BLOCK_BUFFER_LIMIT,
             max_invalid_header_cache_length: DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH,
+            max_sidechain_blocks: DEFAULT_MAX_SIDECHAIN_BLOCKS,
+            max_reorg_records: DEFAULT_MAX_REORG_RECORDS,
+            max_blocks_per_height: DEFAULT_MAX_BLOCKS_PER_HEIGHT,
             max_execute_block_batch_size: DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE,
             legacy_state_root: false,
             always_compare_trie_updates: false,
@@ -194,6 +207,9 @@ impl TreeConfig {
             memory_block_buffer_target,
             block_buffer_limit,
             max_invalid_header_cache_length,
+            max_sidechain_blocks: DEFAULT_MAX_SIDECHAIN_BLOCKS,
+            max_reorg_records: DEFAULT_MAX_REORG_RECORDS,
+            max_blocks_per_height: DEFAULT_MAX_BLOCKS_PER_HEIGHT,
             max_execute_block_batch_size,
             legacy_state_root,
             always_compare_trie_updates,
@@ -235,6 +251,21 @@ impl TreeConfig {
         self.max_invalid_header_cache_length
     }
 
+    /// Return the maximum number of sidechain blocks.
+    pub const fn max_sidechain_blocks(&self) -> u32 {
+        self.max_sidechain_blocks
+    }
+
+    /// Return the maximum number of recent reorgs to keep a record of.
+    pub const fn max_reorg_records(&self) -> usize {
+        self.max_reorg_records
+    }
+
+    /// Return the maximum number of executed blocks kept in memory at the same height.
+    pub const fn max_blocks_per_height(&self) -> usize {
+        self.max_blocks_per_height
+    }
+
     /// Return the maximum execute block batch size.
     pub const fn max_execute_block_batch_size(&self) -> usize {
         self.max_execute_block_batch_size
@@ -348,6 +379,24 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for maximum number of sidechain blocks.
+    pub const fn with_max_sidechain_blocks(mut self, max_sidechain_blocks: u32) -> Self {
+        self.max_sidechain_blocks = max_sidechain_blocks;
+        self
+    }
+
+    /// Setter for maximum number of recent reorgs to keep a record of.
+    pub const fn with_max_reorg_records(mut self, max_reorg_records: usize) -> Self {
+        self.max_reorg_records = max_reorg_records;
+        self
+    }
+
+    /// Setter for maximum number of executed blocks kept in memory at the same height.
+    pub const fn with_max_blocks_per_height(mut self, max_blocks_per_height: usize) -> Self {
+        self.max_blocks_per_height = max_blocks_per_height;
+        self
+    }
+
     /// Setter for maximum execute block batch size.
     pub const fn with_max_execute_block_batch_size(
         mut self,
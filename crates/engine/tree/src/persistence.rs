@@ -12,7 +12,7 @@ use reth_provider::{
 use reth_prune::{PrunerError, PrunerOutput, PrunerWithFactory};
 use reth_stages_api::{MetricEvent, MetricEventsSender};
 use std::{
-    sync::mpsc::{Receiver, SendError, Sender},
+    sync::mpsc::{self, Receiver, SendError, Sender},
     time::Instant,
 };
 use thiserror::Error;
@@ -116,6 +116,14 @@ where
                     provider.save_safe_block_number(safe_block)?;
                     provider.commit()?;
                 }
+                PersistenceAction::PauseMutations(paused, resume) => {
+                    debug!(target: "engine::persistence", "Pausing database writes");
+                    // we ignore the error because the caller may have given up waiting
+                    let _ = paused.send(());
+                    // the guard never sends, this returns once it has been dropped
+                    let _ = resume.recv();
+                    debug!(target: "engine::persistence", "Resuming database writes");
+                }
             }
         }
         Ok(())
@@ -193,6 +201,11 @@ pub enum PersistenceAction<N: NodePrimitives = EthPrimitives> {
 
     /// Update the persisted safe block on disk
     SaveSafeBlock(u64),
+
+    /// Stops processing actions until the [`Sender`] of the given receiver is dropped.
+    ///
+    /// The first sender is notified once the service has paused.
+    PauseMutations(Sender<()>, Receiver<()>),
 }
 
 /// A handle to the persistence service
@@ -291,6 +304,34 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
     ) -> Result<(), SendError<PersistenceAction<T>>> {
         self.send_action(PersistenceAction::RemoveBlocksAbove(block_num, tx))
     }
+
+    /// Pauses all database writes of the persistence service until the returned [`MutationGuard`]
+    /// is dropped, giving database maintenance, e.g. a compaction, a window without concurrent
+    /// writes.
+    ///
+    /// This blocks until the write currently in progress, if any, has completed. Actions sent
+    /// while the guard is held are buffered in the channel and processed in order once it is
+    /// released. The tree doesn't wait for persistence, so it keeps inserting and making blocks
+    /// canonical in the meantime, holding the blocks that are not persisted yet in memory. For
+    /// this reason the guard should only be held for as long as the maintenance takes.
+    pub fn pause_mutations(&self) -> Result<MutationGuard, SendError<PersistenceAction<T>>> {
+        let (paused_tx, paused_rx) = mpsc::channel();
+        let (resume_tx, resume_rx) = mpsc::channel();
+        self.send_action(PersistenceAction::PauseMutations(paused_tx, resume_rx))?;
+        // an error means the service exited, so there are no writes to wait for
+        let _ = paused_rx.recv();
+        Ok(MutationGuard { _resume: resume_tx })
+    }
+}
+
+/// Guard returned by [`PersistenceHandle::pause_mutations`].
+///
+/// The persistence service resumes writing to the database once this is dropped.
+#[derive(Debug)]
+#[must_use = "database writes resume as soon as the guard is dropped"]
+pub struct MutationGuard {
+    /// Dropping the sender wakes up the paused persistence service.
+    _resume: Sender<()>,
 }
 
 #[cfg(test)]
@@ -388,4 +429,31 @@ mod tests {
             assert_eq!(last_hash, actual_hash);
         }
     }
+
+    #[tokio::test]
+    async fn test_pause_mutations() {
+        reth_tracing::init_test_tracing();
+        let persistence_handle = default_persistence_handle();
+
+        let guard = persistence_handle.pause_mutations().unwrap();
+
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks = test_block_builder.get_executed_blocks(0..2).collect::<Vec<_>>();
+        let last_hash = blocks.last().unwrap().recovered_block().hash();
+        let (tx, mut rx) = oneshot::channel();
+        persistence_handle.save_blocks(blocks, tx).unwrap();
+
+        // the blocks are not written while the guard is held
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert_eq!(rx.try_recv(), Err(oneshot::error::TryRecvError::Empty));
+
+        drop(guard);
+        let BlockNumHash { hash: actual_hash, number: _ } =
+            tokio::time::timeout(std::time::Duration::from_secs(10), rx)
+                .await
+                .expect("test timed out")
+                .expect("channel closed unexpectedly")
+                .expect("no hash returned");
+        assert_eq!(last_hash, actual_hash);
+    }
 }
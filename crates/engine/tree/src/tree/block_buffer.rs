@@ -1,5 +1,6 @@
 use crate::tree::metrics::BlockBufferMetrics;
 use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
 use alloy_primitives::{BlockHash, BlockNumber};
 use reth_primitives_traits::{Block, RecoveredBlock};
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
@@ -62,6 +63,24 @@ impl<B: Block> BlockBuffer<B> {
         Some(current_block)
     }
 
+    /// Returns the parents of the buffered chains that are not in the buffer themselves, ordered by
+    /// block number ascending.
+    ///
+    /// These are the blocks that need to be known before the buffered chains can be connected.
+    pub fn missing_parents(&self) -> Vec<BlockNumHash> {
+        let mut parents: Vec<_> = self
+            .parent_to_child
+            .iter()
+            .filter(|(parent, _)| !self.blocks.contains_key(*parent))
+            .filter_map(|(parent, children)| {
+                let child = self.blocks.get(children.iter().next()?)?;
+                Some(BlockNumHash::new(child.number().saturating_sub(1), *parent))
+            })
+            .collect();
+        parents.sort_unstable_by_key(|parent| (parent.number, parent.hash));
+        parents
+    }
+
     /// Insert a correct block inside the buffer.
     pub fn insert_block(&mut self, block: RecoveredBlock<B>) {
         let hash = block.hash();
@@ -310,6 +329,28 @@ mod tests {
         assert_buffer_lengths(&buffer, 0);
     }
 
+    #[test]
+    fn missing_parents() {
+        let mut rng = generators::rng();
+
+        let main_parent = BlockNumHash::new(9, rng.random());
+        let block1 = create_block(&mut rng, 10, main_parent.hash);
+        let block2 = create_block(&mut rng, 11, block1.hash());
+        let block3 = create_block(&mut rng, 11, block1.hash());
+        let other_parent = BlockNumHash::new(4, rng.random());
+        let block4 = create_block(&mut rng, 5, other_parent.hash);
+
+        let mut buffer = BlockBuffer::new(5);
+        assert!(buffer.missing_parents().is_empty());
+
+        buffer.insert_block(block1);
+        buffer.insert_block(block2);
+        buffer.insert_block(block3);
+        buffer.insert_block(block4);
+
+        assert_eq!(buffer.missing_parents(), vec![other_parent, main_parent]);
+    }
+
     #[test]
     fn take_block_with_children() {
         let mut rng = generators::rng();
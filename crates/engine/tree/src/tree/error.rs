@@ -1,11 +1,12 @@
 //! Internal errors for the tree module.
 
 use alloy_consensus::BlockHeader;
+use alloy_eips::eip1898::BlockWithParent;
 use reth_consensus::ConsensusError;
 use reth_errors::{BlockExecutionError, BlockValidationError, ProviderError};
 use reth_evm::execute::InternalBlockExecutionError;
 use reth_payload_primitives::NewPayloadError;
-use reth_primitives_traits::{Block, BlockBody, SealedBlock};
+use reth_primitives_traits::{Block, BlockBody, GotExpected, SealedBlock};
 use tokio::sync::oneshot::error::TryRecvError;
 
 /// This is an error that can come from advancing persistence. Either this can be a
@@ -118,6 +119,16 @@ pub enum InsertBlockErrorKind {
     /// Provider error.
     #[error(transparent)]
     Provider(#[from] ProviderError),
+    /// The tree already holds the maximum number of blocks at the block's height.
+    ///
+    /// This says nothing about the validity of the block.
+    #[error("too many blocks at height {number}, limit is {limit}")]
+    HeightLimitExceeded {
+        /// The number of the rejected block.
+        number: u64,
+        /// The maximum number of blocks at the same height.
+        limit: usize,
+    },
     /// Other errors.
     #[error(transparent)]
     Other(#[from] Box<dyn core::error::Error + Send + Sync + 'static>),
@@ -149,6 +160,7 @@ impl InsertBlockErrorKind {
                 }
             }
             Self::Provider(err) => Err(InsertBlockFatalError::Provider(err)),
+            Self::HeightLimitExceeded { .. } => Err(InternalBlockExecutionError::msg(self).into()),
             Self::Other(err) => Err(InternalBlockExecutionError::Other(err).into()),
         }
     }
@@ -186,3 +198,60 @@ pub enum InsertPayloadError<B: Block> {
     #[error(transparent)]
     Payload(#[from] NewPayloadError),
 }
+
+/// Error for a block that can't be attached to the tree because its parent is neither held by the
+/// tree nor a known block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("block #{} ({}) does not connect to a known block, parent {} is missing", .0.block.number, .0.block.hash, .0.parent)]
+pub struct DisconnectedBlockError(pub BlockWithParent);
+
+/// Errors returned by the query and maintenance methods of the tree.
+///
+/// Block insertion and the engine API handlers keep their own error types, because they have to
+/// hand back the offending block or turn the error into a payload status.
+#[derive(Debug, thiserror::Error)]
+pub enum TreeError {
+    /// Block execution failed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// Provider error.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The canonical chain can't be reverted below the last persisted block.
+    #[error("cannot revert to block {target}, last persisted block is {persisted}")]
+    ReorgTooDeep {
+        /// The requested target block number.
+        target: u64,
+        /// The number of the last persisted block.
+        persisted: u64,
+    },
+    /// The canonical chain can't be reverted below the finalized block.
+    #[error("cannot revert to block {target}, finalized block is {finalized}")]
+    FinalizedRegression {
+        /// The requested target block number.
+        target: u64,
+        /// The number of the finalized block.
+        finalized: u64,
+    },
+    /// A block of the canonical chain between the head and the target is missing.
+    #[error("canonical block {0} is missing")]
+    MissingBlock(u64),
+    /// The blocks unwound from the canonical chain are not contiguous.
+    #[error("unwound blocks are not contiguous: block number {0}")]
+    UnwoundChainNotContiguous(GotExpected<u64>),
+    /// The operation can't be performed while blocks are being persisted.
+    #[error("blocks are being persisted")]
+    PersistenceInProgress,
+}
+
+impl From<InsertBlockFatalError> for TreeError {
+    fn from(err: InsertBlockFatalError) -> Self {
+        match err {
+            InsertBlockFatalError::Provider(err) => Self::Provider(err),
+            InsertBlockFatalError::BlockExecutionError(err) => Self::Execution(err.into()),
+        }
+    }
+}
+
+/// A result type for the methods of the tree that return a [`TreeError`].
+pub type TreeResult<T> = Result<T, TreeError>;
@@ -78,6 +78,23 @@ impl InvalidHeaderCache {
             self.metrics.count.set(self.headers.len() as f64);
         }
     }
+
+    /// Removes all entries whose invalid ancestor is at or below the given finalized block number.
+    ///
+    /// Blocks building on such an ancestor can never become canonical anymore, so they no longer
+    /// need to be tracked.
+    pub fn remove_finalized(&mut self, finalized_number: u64) {
+        let finalized = self
+            .headers
+            .iter()
+            .filter(|(_, entry)| entry.header.block.number <= finalized_number)
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+        for hash in finalized {
+            self.headers.remove(&hash);
+        }
+        self.metrics.count.set(self.headers.len() as f64);
+    }
 }
 
 struct HeaderEntry {
@@ -122,4 +139,20 @@ mod tests {
 
         assert!(cache.get(&header.hash()).is_none());
     }
+
+    #[test]
+    fn test_remove_finalized() {
+        let mut cache = InvalidHeaderCache::new(10);
+        let finalized = SealedHeader::seal_slow(Header { number: 1, ..Default::default() });
+        let pending = SealedHeader::seal_slow(Header { number: 2, ..Default::default() });
+        let descendant = B256::random();
+        cache.insert(finalized.block_with_parent());
+        cache.insert_with_invalid_ancestor(descendant, finalized.block_with_parent());
+        cache.insert(pending.block_with_parent());
+
+        cache.remove_finalized(1);
+        assert!(cache.get(&finalized.hash()).is_none());
+        assert!(cache.get(&descendant).is_none());
+        assert!(cache.get(&pending.hash()).is_some());
+    }
 }
@@ -126,6 +126,14 @@ pub(crate) struct TreeMetrics {
     pub safe_block_height: Gauge,
     /// The current finalized block height (this is required by optimism)
     pub finalized_block_height: Gauge,
+    /// The number of sidechain blocks evicted to stay within the sidechain block limit
+    pub evicted_sidechain_blocks: Counter,
+    /// The number of blocks rejected because the tree already held the maximum number of blocks
+    /// at their height
+    pub height_limit_rejected_blocks: Counter,
+    /// The number of blocks rejected without validation because they, or one of their ancestors,
+    /// were recently found to be invalid
+    pub known_invalid_rejected_blocks: Counter,
 }
 
 /// Metrics for the `EngineApi`.
@@ -136,6 +144,9 @@ pub(crate) struct EngineMetrics {
     pub(crate) executed_blocks: Gauge,
     /// How many already executed blocks were directly inserted into the tree.
     pub(crate) inserted_already_executed_blocks: Counter,
+    /// How many already executed blocks were skipped because they did not connect to a known
+    /// block.
+    pub(crate) disconnected_executed_blocks: Counter,
     /// The number of times the pipeline was run.
     pub(crate) pipeline_runs: Counter,
     /// The total count of forkchoice updated messages received.
@@ -8,44 +8,56 @@ use crate::{
 use alloy_consensus::BlockHeader;
 use alloy_eips::{eip1898::BlockWithParent, merge::EPOCH_SLOTS, BlockNumHash, NumHash};
 use alloy_evm::block::StateChangeSource;
-use alloy_primitives::B256;
+use alloy_primitives::{map::HashSet, Address, BlockNumber, TxHash, B256};
 use alloy_rpc_types_engine::{
     ForkchoiceState, PayloadStatus, PayloadStatusEnum, PayloadValidationError,
 };
-use error::{InsertBlockError, InsertBlockFatalError};
+use error::{
+    DisconnectedBlockError, InsertBlockError, InsertBlockErrorKind, InsertBlockFatalError,
+    TreeError, TreeResult,
+};
 use reth_chain_state::{
     CanonicalInMemoryState, ExecutedBlock, MemoryOverlayStateProvider, NewCanonicalChain,
+    NewCanonicalChainError,
 };
 use reth_consensus::{Consensus, FullConsensus};
 use reth_engine_primitives::{
     BeaconEngineMessage, BeaconOnNewPayloadError, ConsensusEngineEvent, ExecutionPayload,
     ForkchoiceStateTracker, OnForkChoiceUpdated,
 };
-use reth_errors::{ConsensusError, ProviderResult};
-use reth_evm::{ConfigureEvm, OnStateHook};
+use reth_errors::{BlockExecutionError, ConsensusError, ProviderResult};
+use reth_evm::{
+    execute::{BlockBuilder, Executor},
+    ConfigureEvm, OnStateHook,
+};
+use reth_execution_types::Chain;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, NewPayloadError, PayloadBuilderAttributes, PayloadTypes,
 };
-use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
+use reth_primitives_traits::{
+    GotExpected, NodePrimitives, Recovered, RecoveredBlock, SealedBlock, SealedHeader,
+};
 use reth_provider::{
-    providers::ConsistentDbView, BlockReader, DatabaseProviderFactory, HashedPostStateProvider,
-    ProviderError, StateProviderBox, StateProviderFactory, StateReader, TransactionVariant,
-    TrieReader,
+    providers::ConsistentDbView, BlockReader, ChangeSetReader, DatabaseProviderFactory,
+    HashedPostStateProvider, ProviderError, StateProviderBox, StateProviderFactory, StateReader,
+    StateRootProvider, TransactionVariant, TransactionsProvider, TrieReader,
 };
-use reth_revm::database::StateProviderDatabase;
+use reth_revm::{database::StateProviderDatabase, db::State};
 use reth_stages_api::ControlFlow;
 use revm::state::EvmState;
-use state::TreeState;
+use state::{ForkEdge, Reorg, ReorgRecord, TreeSnapshot, TreeState, TxStatus};
 use std::{
     fmt::Debug,
+    ops::RangeInclusive,
     sync::{
         mpsc::{Receiver, RecvError, RecvTimeoutError, Sender},
         Arc,
     },
-    time::Instant,
+    time::{Instant, SystemTime},
 };
 use tokio::sync::{
+    broadcast,
     mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     oneshot::{self, error::TryRecvError},
 };
@@ -273,6 +285,8 @@ where
     config: TreeConfig,
     /// Metrics for the engine api.
     metrics: EngineApiMetrics,
+    /// Records of the most recent reorgs, oldest first.
+    recent_reorgs: Vec<ReorgRecord>,
     /// The engine API variant of this handler
     engine_kind: EngineApiKind,
     /// The EVM configuration.
@@ -299,6 +313,7 @@ where
             .field("payload_builder", &self.payload_builder)
             .field("config", &self.config)
             .field("metrics", &self.metrics)
+            .field("recent_reorgs", &self.recent_reorgs)
             .field("engine_kind", &self.engine_kind)
             .field("evm_config", &self.evm_config)
             .finish()
@@ -314,6 +329,7 @@ where
         + StateReader<Receipt = N::Receipt>
         + HashedPostStateProvider
         + TrieReader
+        + ChangeSetReader
         + Clone
         + 'static,
     <P as DatabaseProviderFactory>::Provider:
@@ -354,6 +370,7 @@ where
             payload_builder,
             config,
             metrics: Default::default(),
+            recent_reorgs: Vec::new(),
             incoming_tx,
             engine_kind,
             evm_config,
@@ -1052,7 +1069,7 @@ where
     /// processing is complete. Returns `None` if the head is not canonical and processing
     /// should continue.
     fn handle_canonical_head(
-        &self,
+        &mut self,
         state: ForkchoiceState,
         attrs: &Option<T::PayloadAttributes>, // Changed to reference
         version: EngineApiMessageVersion,
@@ -1175,8 +1192,8 @@ where
 
         // Ensure we can apply a new chain update for the head block
         if let Some(chain_update) = self.on_new_head(state.head_block_hash)? {
-            let tip = chain_update.tip().clone_sealed_header();
-            self.on_canonical_chain_update(chain_update);
+            let tip = chain_update.tip().map_err(ProviderError::other)?.clone_sealed_header();
+            self.on_canonical_chain_update(chain_update).map_err(ProviderError::other)?;
 
             // Update the safe and finalized blocks and ensure their values are valid
             if let Err(outcome) = self.ensure_consistent_forkchoice_state(state) {
@@ -1368,6 +1385,16 @@ where
                             return Ok(())
                         }
 
+                        // the block was not executed by the tree, so make sure it attaches to a
+                        // known block before tracking it
+                        if let Err(err) =
+                            self.ensure_connected(block.recovered_block().block_with_parent())?
+                        {
+                            warn!(target: "engine::tree", %err, "Skipping disconnected executed block");
+                            self.metrics.engine.disconnected_executed_blocks.increment(1);
+                            return Ok(())
+                        }
+
                         debug!(target: "engine::tree", block=?block_num_hash, "inserting already executed block");
                         let now = Instant::now();
 
@@ -1576,15 +1603,20 @@ where
         };
 
         // try to close the gap by executing buffered blocks that are child blocks of the new head
-        self.try_connect_buffered_blocks(self.state.tree_state.current_canonical_head)
+        // or of any other block that is known now
+        self.try_connect_all_buffered_blocks()?;
+        Ok(())
     }
 
     /// Attempts to make the given target canonical.
     ///
     /// This will update the tracked canonical in memory state and do the necessary housekeeping.
+    ///
+    /// Returns an error if the chain update leading to the target is malformed, see
+    /// [`NewCanonicalChainError`].
     fn make_canonical(&mut self, target: B256) -> ProviderResult<()> {
         if let Some(chain_update) = self.on_new_head(target)? {
-            self.on_canonical_chain_update(chain_update);
+            self.on_canonical_chain_update(chain_update).map_err(ProviderError::other)?;
         }
 
         Ok(())
@@ -1780,6 +1812,28 @@ where
         }
     }
 
+    /// Return sealed block from in-memory state or database by hash.
+    fn sealed_block_by_hash(&self, hash: B256) -> ProviderResult<Option<SealedBlock<N::Block>>> {
+        // check memory first
+        if let Some(block) = self.state.tree_state.executed_block_by_hash(hash) {
+            return Ok(Some(block.recovered_block().sealed_block().clone()))
+        }
+
+        Ok(self.provider.block_by_hash(hash)?.map(|block| SealedBlock::new_unchecked(block, hash)))
+    }
+
+    /// Checks that the parent of the given block is either held by the in-memory tree or a block
+    /// known to the database.
+    fn ensure_connected(
+        &self,
+        block: BlockWithParent,
+    ) -> ProviderResult<Result<(), DisconnectedBlockError>> {
+        if self.sealed_header_by_hash(block.parent)?.is_some() {
+            return Ok(Ok(()))
+        }
+        Ok(Err(DisconnectedBlockError(block)))
+    }
+
     /// Return the parent hash of the lowest buffered ancestor for the requested block, if there
     /// are any buffered ancestors. If there are no buffered ancestors, and the block itself does
     /// not exist in the buffer, this returns the hash that is passed in.
@@ -1830,6 +1884,15 @@ where
         Ok(None)
     }
 
+    /// Returns the hash of the latest valid ancestor of the block with the given hash.
+    ///
+    /// This walks up the invalid headers cache until it reaches a block that is known to the tree
+    /// or the database. Returns the hash itself if the block is known, and `None` if the block
+    /// is unknown, in which case the engine API response should contain a null latest valid hash.
+    pub fn latest_valid_ancestor(&mut self, hash: B256) -> TreeResult<Option<B256>> {
+        Ok(self.latest_valid_hash_for_invalid_payload(hash)?)
+    }
+
     /// Prepares the invalid payload response for the given hash, checking the
     /// database for the parent hash and populating the payload status with the latest valid hash
     /// according to the engine api spec.
@@ -1859,6 +1922,17 @@ where
         false
     }
 
+    /// Returns true if the block with the given hash and parent extends the canonical head or the
+    /// latest forkchoice head, or is the latest forkchoice head itself.
+    fn extends_head(&self, block_hash: B256, parent_hash: B256) -> bool {
+        if parent_hash == self.state.tree_state.canonical_block_hash() {
+            return true
+        }
+        self.state.forkchoice_state_tracker.latest_state().is_some_and(|state| {
+            state.head_block_hash == parent_hash || state.head_block_hash == block_hash
+        })
+    }
+
     /// Checks if the given `check` hash points to an invalid header, inserting the given `head`
     /// block into the invalid header cache if the `check` hash has a known invalid ancestor.
     ///
@@ -1881,6 +1955,8 @@ where
         head: SealedBlock<N::Block>,
         invalid: BlockWithParent,
     ) -> ProviderResult<PayloadStatus> {
+        self.metrics.tree.known_invalid_rejected_blocks.increment(1);
+
         // populate the latest valid hash field
         let status = self.prepare_invalid_response(invalid.parent)?;
 
@@ -1897,14 +1973,19 @@ where
     /// hash and checks if any ancestor is marked as invalid in the tree state.
     ///
     /// The check works by:
-    /// 1. Finding the lowest buffered ancestor for the given block hash
-    /// 2. If the ancestor is the same as the block hash itself, using the parent hash instead
-    /// 3. Checking if this ancestor is in the `invalid_headers` map
+    /// 1. Checking if the block itself was already found to be invalid, e.g. if it's re-delivered
+    /// 2. Finding the lowest buffered ancestor for the given block hash
+    /// 3. If the ancestor is the same as the block hash itself, using the parent hash instead
+    /// 4. Checking if this ancestor is in the `invalid_headers` map
     ///
     /// Returns the invalid ancestor block info if found, or None if no invalid ancestor exists.
     fn find_invalid_ancestor(&mut self, payload: &T::ExecutionData) -> Option<BlockWithParent> {
         let parent_hash = payload.parent_hash();
         let block_hash = payload.block_hash();
+        if let Some(invalid) = self.state.invalid_headers.get(&block_hash) {
+            return Some(invalid)
+        }
+
         let mut lowest_buffered_ancestor = self.lowest_buffered_ancestor_or(block_hash);
         if lowest_buffered_ancestor == block_hash {
             lowest_buffered_ancestor = parent_hash;
@@ -1963,10 +2044,14 @@ where
 
     /// Validate if block is correct and satisfies all the consensus rules that concern the header
     /// and block body itself.
+    ///
+    /// Header validation is skipped if the header of this block already passed validation.
     fn validate_block(&self, block: &RecoveredBlock<N::Block>) -> Result<(), ConsensusError> {
-        if let Err(e) = self.consensus.validate_header(block.sealed_header()) {
-            error!(target: "engine::tree", ?block, "Failed to validate header {}: {e}", block.hash());
-            return Err(e)
+        if !self.state.tree_state.is_header_validated(block.hash()) {
+            if let Err(e) = self.consensus.validate_header(block.sealed_header()) {
+                error!(target: "engine::tree", ?block, "Failed to validate header {}: {e}", block.hash());
+                return Err(e)
+            }
         }
 
         if let Err(e) = self.consensus.validate_block_pre_execution(block.sealed_block()) {
@@ -1978,20 +2063,23 @@ where
     }
 
     /// Attempts to connect any buffered blocks that are connected to the given parent hash.
+    ///
+    /// Returns the outcome of every buffered block that was inserted successfully.
     #[instrument(level = "debug", target = "engine::tree", skip(self))]
     fn try_connect_buffered_blocks(
         &mut self,
         parent: BlockNumHash,
-    ) -> Result<(), InsertBlockFatalError> {
+    ) -> Result<Vec<(BlockNumHash, InsertPayloadOk)>, InsertBlockFatalError> {
         let blocks = self.state.buffer.remove_block_with_children(&parent.hash);
 
         if blocks.is_empty() {
             // nothing to append
-            return Ok(())
+            return Ok(Vec::new())
         }
 
         let now = Instant::now();
         let block_count = blocks.len();
+        let mut outcomes = Vec::with_capacity(block_count);
         for child in blocks {
             let child_num_hash = child.num_hash();
             match self.insert_block(child) {
@@ -2002,6 +2090,7 @@ where
                     {
                         self.make_canonical(child_num_hash.hash)?;
                     }
+                    outcomes.push((child_num_hash, res));
                 }
                 Err(err) => {
                     if let InsertPayloadError::Block(err) = err {
@@ -2016,7 +2105,7 @@ where
         }
 
         debug!(target: "engine::tree", elapsed = ?now.elapsed(), %block_count, "connected buffered blocks");
-        Ok(())
+        Ok(outcomes)
     }
 
     /// Pre-validates the block and inserts it into the buffer.
@@ -2027,6 +2116,7 @@ where
         if let Err(err) = self.validate_block(&block) {
             return Err(InsertBlockError::consensus_error(err, block.into_sealed_block()))
         }
+        self.state.tree_state.insert_validated_header(block.num_hash());
         self.state.buffer.insert_block(block);
         Ok(())
     }
@@ -2181,15 +2271,19 @@ where
     /// Invoked when we the canonical chain has been updated.
     ///
     /// This is invoked on a valid forkchoice update, or if we can make the target block canonical.
-    fn on_canonical_chain_update(&mut self, chain_update: NewCanonicalChain<N>) {
+    fn on_canonical_chain_update(
+        &mut self,
+        chain_update: NewCanonicalChain<N>,
+    ) -> Result<(), NewCanonicalChainError> {
         trace!(target: "engine::tree", new_blocks = %chain_update.new_block_count(), reorged_blocks =  %chain_update.reorged_block_count(), "applying new chain update");
         let start = Instant::now();
 
-        // update the tracked canonical head
-        self.state.tree_state.set_canonical_head(chain_update.tip().num_hash());
+        // check the update before touching any state, so a malformed update leaves the tree as is
+        let tip = chain_update.tip()?.clone_sealed_header();
+        let notification = chain_update.to_chain_notification()?;
 
-        let tip = chain_update.tip().clone_sealed_header();
-        let notification = chain_update.to_chain_notification();
+        // update the tracked canonical head
+        self.state.tree_state.set_canonical_head(tip.num_hash());
 
         // reinsert any missing reorged blocks
         if let NewCanonicalChain::Reorg { new, old } = &chain_update {
@@ -2198,6 +2292,19 @@ where
             trace!(target: "engine::tree", ?new_first, ?old_first, "Reorg detected, new and old first blocks");
 
             self.update_reorg_metrics(old.len());
+            if let (Some(old_tip), Some(new_tip)) = (old.last(), new.last()) {
+                self.record_reorg(ReorgRecord {
+                    depth: old.len() as u64,
+                    timestamp: SystemTime::now(),
+                    old_tip: old_tip.recovered_block().num_hash(),
+                    new_tip: new_tip.recovered_block().num_hash(),
+                });
+            }
+            // blocks that are no longer canonical must not skip validation if they are
+            // re-delivered
+            for block in old {
+                self.state.tree_state.remove_validated_header(block.recovered_block().hash());
+            }
             self.reinsert_reorged_blocks(new.clone());
             self.reinsert_reorged_blocks(old.clone());
         }
@@ -2217,6 +2324,8 @@ where
             Box::new(tip),
             start.elapsed(),
         ));
+
+        Ok(())
     }
 
     /// This updates metrics based on the given reorg length.
@@ -2225,6 +2334,65 @@ where
         self.metrics.tree.latest_reorg_depth.set(old_chain_length as f64);
     }
 
+    /// Records the reorg, dropping the oldest record if the configured limit is reached.
+    fn record_reorg(&mut self, record: ReorgRecord) {
+        if self.config.max_reorg_records() == 0 {
+            return
+        }
+        if self.recent_reorgs.len() >= self.config.max_reorg_records() {
+            self.recent_reorgs.remove(0);
+        }
+        self.recent_reorgs.push(record);
+    }
+
+    /// Marks the block with the given hash and all of its descendants as invalid.
+    ///
+    /// The blocks are removed from the tree and the buffer, and recorded in the invalid headers
+    /// cache so that re-delivery of any of them is rejected immediately. They are also dropped
+    /// from the validated headers, so their headers are not trusted anymore. Canonical blocks are
+    /// never invalidated.
+    ///
+    /// Returns the parent hash of the invalidated block, which is the latest valid ancestor, and
+    /// the hashes of all invalidated blocks. Returns `None` if the block is not a non-canonical
+    /// block in the tree.
+    pub fn mark_invalid(&mut self, hash: B256) -> Option<(B256, Vec<B256>)> {
+        if self.state.tree_state.is_canonical(hash) {
+            return None
+        }
+
+        let removed = self.state.tree_state.remove_with_descendants(hash);
+        let parent_hash = removed.first()?.recovered_block().parent_hash();
+
+        let mut invalidated = Vec::with_capacity(removed.len());
+        for executed in &removed {
+            let block = executed.recovered_block();
+            self.state.invalid_headers.insert(block.block_with_parent());
+            invalidated.push(block.hash());
+            for buffered in self.state.buffer.remove_block_with_children(&block.hash()) {
+                self.state.invalid_headers.insert(buffered.block_with_parent());
+                invalidated.push(buffered.hash());
+            }
+        }
+        for hash in &invalidated {
+            self.state.tree_state.remove_validated_header(*hash);
+        }
+
+        Some((parent_hash, invalidated))
+    }
+
+    /// Subscribes to changes of the pending block, i.e. the block on top of the canonical head.
+    ///
+    /// Only blocks that replace a different pending block are sent, inserting sidechain blocks
+    /// does not notify subscribers.
+    pub fn subscribe_pending(&self) -> broadcast::Receiver<SealedBlock<N::Block>> {
+        self.canonical_in_memory_state.subscribe_pending_block()
+    }
+
+    /// Returns the records of the most recent reorgs of the canonical chain, oldest first.
+    pub fn recent_reorgs(&self) -> &[ReorgRecord] {
+        &self.recent_reorgs
+    }
+
     /// This reinserts any blocks in the new chain that do not already exist in the tree
     fn reinsert_reorged_blocks(&mut self, new_chain: Vec<ExecutedBlock<N>>) {
         for block in new_chain {
@@ -2377,6 +2545,84 @@ where
         )
     }
 
+    /// Validates a block as if it was inserted into the tree, without mutating the tree.
+    ///
+    /// This runs the consensus checks, executes the block on top of its parent state and compares
+    /// the resulting state root, but the executed block is discarded instead of being inserted
+    /// into the tree, set as the pending block or buffered. Invalid blocks are not recorded as
+    /// invalid headers and no events are emitted.
+    ///
+    /// If the parent state is not available, the block can't be executed and
+    /// [`BlockStatus::Disconnected`] is returned.
+    pub fn validate_block_speculative(
+        &self,
+        block: RecoveredBlock<N::Block>,
+    ) -> Result<BlockStatus, InsertBlockError<N::Block>> {
+        match self.sealed_header_by_hash(block.hash()) {
+            Err(err) => return Err(InsertBlockError::new(block.into_sealed_block(), err.into())),
+            Ok(Some(_)) => return Ok(BlockStatus::Valid),
+            Ok(None) => {}
+        }
+
+        let limit = self.config.max_blocks_per_height();
+        if !self.extends_head(block.hash(), block.parent_hash()) &&
+            self.state.tree_state.block_count_at_number(block.number()) >= limit
+        {
+            let number = block.number();
+            return Err(InsertBlockError::new(
+                block.into_sealed_block(),
+                InsertBlockErrorKind::HeightLimitExceeded { number, limit },
+            ))
+        }
+
+        let provider_builder = match self.state_provider_builder(block.parent_hash()) {
+            Err(err) => return Err(InsertBlockError::new(block.into_sealed_block(), err.into())),
+            Ok(None) => {
+                return Ok(BlockStatus::Disconnected {
+                    head: self.state.tree_state.current_canonical_head,
+                    missing_ancestor: self.missing_ancestor_for(&block),
+                })
+            }
+            Ok(Some(builder)) => builder,
+        };
+
+        match self.execute_block_speculative(&block, provider_builder) {
+            Ok(()) => Ok(BlockStatus::Valid),
+            Err(kind) => Err(InsertBlockError::new(block.into_sealed_block(), kind)),
+        }
+    }
+
+    /// Validates and executes the block on top of the state of the given builder, see
+    /// [`Self::validate_block_speculative`].
+    fn execute_block_speculative(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        provider_builder: StateProviderBuilder<N, P>,
+    ) -> Result<(), InsertBlockErrorKind> {
+        let parent = self
+            .sealed_header_by_hash(block.parent_hash())?
+            .ok_or(ProviderError::HeaderNotFound(block.parent_hash().into()))?;
+
+        self.validate_block(block)?;
+        self.consensus.validate_header_against_parent(block.sealed_header(), &parent)?;
+
+        let state_provider = provider_builder.build()?;
+        let output =
+            self.evm_config.executor(StateProviderDatabase::new(&state_provider)).execute(block)?;
+        self.consensus.validate_block_post_execution(block, &output)?;
+
+        let state_root =
+            state_provider.state_root(state_provider.hashed_post_state(&output.state))?;
+        if state_root != block.state_root() {
+            return Err(ConsensusError::BodyStateRootDiff(
+                GotExpected { got: state_root, expected: block.state_root() }.into(),
+            )
+            .into())
+        }
+
+        Ok(())
+    }
+
     /// Inserts a block or payload into the blockchain tree with full execution.
     ///
     /// This is a generic function that handles both blocks and payloads by accepting
@@ -2408,7 +2654,82 @@ where
         let block_num_hash = block_id.block;
         debug!(target: "engine::tree", block=?block_num_hash, parent = ?block_id.parent, "Inserting new block into tree");
 
-        match self.sealed_header_by_hash(block_num_hash.hash) {
+        let start = Instant::now();
+
+        let (executed, is_fork) =
+            match self.execute_block_or_payload(block_id, input, execute, convert_to_block)? {
+                ExecuteBlockOutcome::AlreadySeen => {
+                    return Ok(InsertPayloadOk::AlreadySeen(BlockStatus::Valid))
+                }
+                ExecuteBlockOutcome::Disconnected(block) => {
+                    // we don't have the state required to execute this block, buffering it and
+                    // find the missing parent block
+                    let missing_ancestor = self.missing_ancestor_for(&block);
+
+                    self.state.buffer.insert_block(block);
+
+                    return Ok(InsertPayloadOk::Inserted(BlockStatus::Disconnected {
+                        head: self.state.tree_state.current_canonical_head,
+                        missing_ancestor,
+                    }))
+                }
+                ExecuteBlockOutcome::Executed { executed, is_fork } => (executed, is_fork),
+            };
+
+        // if the parent is the canonical head, we can insert the block as the pending block
+        if self.state.tree_state.canonical_block_hash() == executed.recovered_block().parent_hash()
+        {
+            debug!(target: "engine::tree", pending=?block_num_hash, "updating pending block");
+            self.canonical_in_memory_state.set_pending_block(executed.clone());
+        }
+
+        self.state.tree_state.insert_executed(executed.clone());
+
+        // evict the least valuable sidechains, keeping the one the new block is part of
+        let evicted = self
+            .state
+            .tree_state
+            .evict_sidechains(self.config.max_sidechain_blocks() as usize, block_num_hash.hash);
+        if evicted > 0 {
+            debug!(target: "engine::tree", evicted, "Evicted sidechain blocks");
+            self.metrics.tree.evicted_sidechain_blocks.increment(evicted as u64);
+        }
+        self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
+
+        // emit insert event
+        let elapsed = start.elapsed();
+        let engine_event = if is_fork {
+            ConsensusEngineEvent::ForkBlockAdded(executed, elapsed)
+        } else {
+            ConsensusEngineEvent::CanonicalBlockAdded(executed, elapsed)
+        };
+        self.emit_event(EngineApiEvent::BeaconConsensus(engine_event));
+
+        self.metrics
+            .engine
+            .block_insert_total_duration
+            .record(block_insert_start.elapsed().as_secs_f64());
+        debug!(target: "engine::tree", block=?block_num_hash, "Finished inserting block");
+        Ok(InsertPayloadOk::Inserted(BlockStatus::Valid))
+    }
+
+    /// Runs all checks and the execution of a block or payload without inserting it into the
+    /// tree.
+    ///
+    /// This is shared by [`Self::insert_block_or_payload`] and
+    /// [`Self::validate_block_speculative`], so that both paths perform the exact same
+    /// validation.
+    fn execute_block_or_payload<Input, Err>(
+        &mut self,
+        block_id: BlockWithParent,
+        input: Input,
+        execute: impl FnOnce(&mut V, Input, TreeCtx<'_, N>) -> Result<ExecutedBlock<N>, Err>,
+        convert_to_block: impl FnOnce(&mut Self, Input) -> Result<RecoveredBlock<N::Block>, Err>,
+    ) -> Result<ExecuteBlockOutcome<N>, Err>
+    where
+        Err: From<InsertBlockError<N::Block>>,
+    {
+        match self.sealed_header_by_hash(block_id.block.hash) {
             Err(err) => {
                 let block = convert_to_block(self, input)?;
                 return Err(InsertBlockError::new(block.into_sealed_block(), err.into()).into());
@@ -2417,11 +2738,26 @@ where
                 // We now assume that we already have this block in the tree. However, we need to
                 // run the conversion to ensure that the block hash is valid.
                 convert_to_block(self, input)?;
-                return Ok(InsertPayloadOk::AlreadySeen(BlockStatus::Valid))
+                return Ok(ExecuteBlockOutcome::AlreadySeen)
             }
             _ => {}
         };
 
+        // Bound the number of competing blocks at a single height. Blocks extending the canonical
+        // or forkchoice head are exempt, so sidechain blocks can't crowd out the canonical chain.
+        let limit = self.config.max_blocks_per_height();
+        if !self.extends_head(block_id.block.hash, block_id.parent) &&
+            self.state.tree_state.block_count_at_number(block_id.block.number) >= limit
+        {
+            self.metrics.tree.height_limit_rejected_blocks.increment(1);
+            let block = convert_to_block(self, input)?;
+            return Err(InsertBlockError::new(
+                block.into_sealed_block(),
+                InsertBlockErrorKind::HeightLimitExceeded { number: block_id.block.number, limit },
+            )
+            .into())
+        }
+
         // Ensure that the parent state is available.
         match self.state_provider_builder(block_id.parent) {
             Err(err) => {
@@ -2430,22 +2766,7 @@ where
             }
             Ok(None) => {
                 let block = convert_to_block(self, input)?;
-
-                // we don't have the state required to execute this block, buffering it and find the
-                // missing parent block
-                let missing_ancestor = self
-                    .state
-                    .buffer
-                    .lowest_ancestor(&block.parent_hash())
-                    .map(|block| block.parent_num_hash())
-                    .unwrap_or_else(|| block.parent_num_hash());
-
-                self.state.buffer.insert_block(block);
-
-                return Ok(InsertPayloadOk::Inserted(BlockStatus::Disconnected {
-                    head: self.state.tree_state.current_canonical_head,
-                    missing_ancestor,
-                }))
+                return Ok(ExecuteBlockOutcome::Disconnected(block))
             }
             Ok(Some(_)) => {}
         }
@@ -2462,35 +2783,19 @@ where
         let ctx =
             TreeCtx::new(&mut self.state, &self.persistence_state, &self.canonical_in_memory_state);
 
-        let start = Instant::now();
-
         let executed = execute(&mut self.payload_validator, input, ctx)?;
 
-        // if the parent is the canonical head, we can insert the block as the pending block
-        if self.state.tree_state.canonical_block_hash() == executed.recovered_block().parent_hash()
-        {
-            debug!(target: "engine::tree", pending=?block_num_hash, "updating pending block");
-            self.canonical_in_memory_state.set_pending_block(executed.clone());
-        }
-
-        self.state.tree_state.insert_executed(executed.clone());
-        self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
-
-        // emit insert event
-        let elapsed = start.elapsed();
-        let engine_event = if is_fork {
-            ConsensusEngineEvent::ForkBlockAdded(executed, elapsed)
-        } else {
-            ConsensusEngineEvent::CanonicalBlockAdded(executed, elapsed)
-        };
-        self.emit_event(EngineApiEvent::BeaconConsensus(engine_event));
+        Ok(ExecuteBlockOutcome::Executed { executed, is_fork })
+    }
 
-        self.metrics
-            .engine
-            .block_insert_total_duration
-            .record(block_insert_start.elapsed().as_secs_f64());
-        debug!(target: "engine::tree", block=?block_num_hash, "Finished inserting block");
-        Ok(InsertPayloadOk::Inserted(BlockStatus::Valid))
+    /// Returns the lowest missing ancestor of a disconnected block, taking the buffered blocks
+    /// into account.
+    fn missing_ancestor_for(&self, block: &RecoveredBlock<N::Block>) -> BlockNumHash {
+        self.state
+            .buffer
+            .lowest_ancestor(&block.parent_hash())
+            .map(|block| block.parent_num_hash())
+            .unwrap_or_else(|| block.parent_num_hash())
     }
 
     /// Handles an error that occurred while inserting a block.
@@ -2504,6 +2809,19 @@ where
     ) -> Result<PayloadStatus, InsertBlockFatalError> {
         let (block, error) = error.split();
 
+        // a block rejected because of the per-height limit is not known to be invalid, so it must
+        // not end up in the invalid headers cache.
+        if let InsertBlockErrorKind::HeightLimitExceeded { .. } = error {
+            warn!(
+                target: "engine::tree",
+                hash=%block.hash(),
+                number=block.number(),
+                %error,
+                "Rejected block",
+            );
+            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
+        }
+
         // if invalid block, we check the validation error. Otherwise return the fatal
         // error.
         let validation_err = error.ensure_validation_error()?;
@@ -2559,7 +2877,7 @@ where
     pub fn find_canonical_header(
         &self,
         hash: B256,
-    ) -> Result<Option<SealedHeader<N::BlockHeader>>, ProviderError> {
+    ) -> TreeResult<Option<SealedHeader<N::BlockHeader>>> {
         let mut canonical = self.canonical_in_memory_state.header_by_hash(hash);
 
         if canonical.is_none() {
@@ -2569,9 +2887,456 @@ where
         Ok(canonical)
     }
 
+    /// Cross-checks the in-memory canonical chain against the canonical hashes stored in the
+    /// database and returns the numbers of all blocks that disagree, sorted ascending.
+    ///
+    /// Blocks that are not yet persisted are skipped. This does not modify any state.
+    pub fn validate_canonical_index(&self) -> TreeResult<Vec<BlockNumber>> {
+        Ok(self.divergent_canonical_blocks()?.into_iter().map(|block| block.number).collect())
+    }
+
+    /// Returns the blocks of the in-memory canonical chain whose hash disagrees with the canonical
+    /// hash stored in the database, sorted ascending.
+    fn divergent_canonical_blocks(&self) -> TreeResult<Vec<BlockNumHash>> {
+        let mut divergent = Vec::new();
+        for block in self.canonical_in_memory_state.canonical_chain() {
+            let num_hash = block.block_ref().recovered_block().num_hash();
+            if self.provider.block_hash(num_hash.number)?.is_some_and(|hash| hash != num_hash.hash)
+            {
+                divergent.push(num_hash);
+            }
+        }
+        divergent.reverse();
+        Ok(divergent)
+    }
+
+    /// Rebuilds the in-memory canonical chain from the database if it disagrees with the
+    /// canonical hashes stored there, see [`Self::validate_canonical_index`].
+    ///
+    /// The database is treated as authoritative: the divergent blocks and their descendants are
+    /// removed from the tree, the in-memory canonical chain is cleared and the canonical head is
+    /// reset to the best block of the database. All database reads happen before any state is
+    /// modified, so an error leaves the tree untouched.
+    ///
+    /// Returns [`TreeError::PersistenceInProgress`] while blocks are being persisted, because the
+    /// last persisted block is reset as well.
+    ///
+    /// Returns the numbers of the blocks that were found divergent.
+    pub fn rebuild_canonical_index(&mut self) -> TreeResult<Vec<BlockNumber>> {
+        if self.persistence_state.in_progress() {
+            return Err(TreeError::PersistenceInProgress)
+        }
+
+        let divergent = self.divergent_canonical_blocks()?;
+        if divergent.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let best_number = self.provider.best_block_number()?;
+        let head = self
+            .provider
+            .sealed_header(best_number)?
+            .ok_or(ProviderError::HeaderNotFound(best_number.into()))?;
+
+        warn!(target: "engine::tree", ?divergent, head = ?head.num_hash(), "Rebuilding canonical chain from database");
+        for block in &divergent {
+            self.state.tree_state.remove_with_descendants(block.hash);
+        }
+        self.canonical_in_memory_state.clear_state();
+        self.state.tree_state.set_canonical_head(head.num_hash());
+        self.persistence_state.last_persisted_block = head.num_hash();
+        self.canonical_in_memory_state.set_canonical_head(head);
+
+        Ok(divergent.into_iter().map(|block| block.number).collect())
+    }
+
+    /// Computes the blocks that have to be reverted and committed to move from the chain ending in
+    /// `old_tip` to the chain ending in `new_tip`.
+    ///
+    /// Blocks are looked up in the in-memory tree first and in the database second. If both tips
+    /// are the same block, both block lists are empty.
+    ///
+    /// Returns `None` if a block of either chain can't be found before reaching a common
+    /// ancestor.
+    pub fn compute_reorg(
+        &self,
+        old_tip: B256,
+        new_tip: B256,
+    ) -> TreeResult<Option<Reorg<N::Block>>> {
+        let Some(mut old) = self.sealed_block_by_hash(old_tip)? else { return Ok(None) };
+        let Some(mut new) = self.sealed_block_by_hash(new_tip)? else { return Ok(None) };
+
+        let mut reverted = Vec::new();
+        let mut committed = Vec::new();
+
+        // Walk back the higher chain, or the old one if both are at the same height, until both
+        // chains meet
+        while old.hash() != new.hash() {
+            let (chain, block) = if old.number() >= new.number() {
+                (&mut reverted, &mut old)
+            } else {
+                (&mut committed, &mut new)
+            };
+            let Some(parent) = self.sealed_block_by_hash(block.parent_hash())? else {
+                return Ok(None)
+            };
+            chain.push(std::mem::replace(block, parent));
+        }
+        committed.reverse();
+
+        Ok(Some(Reorg { common_ancestor: old.num_hash(), reverted, committed }))
+    }
+
+    /// Returns all blocks of the in-memory tree that more than one chain forks off, ordered
+    /// deterministically.
+    pub fn fork_graph(&self) -> Vec<ForkEdge> {
+        self.state.tree_state.fork_graph()
+    }
+
+    /// Returns the blocks that would be removed from the in-memory tree if the given block were
+    /// finalized, without removing them.
+    pub fn finalize_preview(&self, finalized: BlockNumHash) -> Vec<BlockNumHash> {
+        self.state.tree_state.finalized_sidechain_blocks(finalized)
+    }
+
+    /// Attempts to connect all buffered blocks whose missing parent is now known, e.g. after a
+    /// batch of blocks was inserted at once.
+    ///
+    /// Buffered chains are attempted in order of their parent's block number, each at most once
+    /// per call. Blocks whose parent is still unknown remain buffered. Returns the outcome of
+    /// every buffered block that was inserted successfully.
+    pub fn connect_buffered_blocks(&mut self) -> TreeResult<Vec<(BlockNumHash, InsertPayloadOk)>> {
+        Ok(self.try_connect_all_buffered_blocks()?)
+    }
+
+    /// See [`Self::connect_buffered_blocks`].
+    fn try_connect_all_buffered_blocks(
+        &mut self,
+    ) -> Result<Vec<(BlockNumHash, InsertPayloadOk)>, InsertBlockFatalError> {
+        let mut outcomes = Vec::new();
+        for parent in self.state.buffer.missing_parents() {
+            if self.sealed_header_by_hash(parent.hash)?.is_some() {
+                outcomes.extend(self.try_connect_buffered_blocks(parent)?);
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Returns how many blocks the longest branch of the tree that does not contain the given block
+    /// has on top of their common ancestor, as a heuristic for how likely the block is to be
+    /// reorged out. `0` means there is no competing branch.
+    ///
+    /// Total difficulty is not tracked after the merge, so competing work is measured in blocks.
+    ///
+    /// Returns `None` if the block is neither in the tree nor part of the persisted canonical
+    /// chain.
+    pub fn competing_weight(&self, hash: B256) -> TreeResult<Option<u64>> {
+        if let Some(executed) = self.state.tree_state.executed_block_by_hash(hash) {
+            let block = executed.recovered_block().num_hash();
+            return Ok(self.state.tree_state.competing_weight(block, false))
+        }
+        let Some(number) = self.provider.block_number(hash)? else { return Ok(None) };
+        Ok(self.state.tree_state.competing_weight(BlockNumHash::new(number, hash), true))
+    }
+
+    /// Returns the tips of all chains in the in-memory tree, i.e. all blocks without children.
+    ///
+    /// Total difficulty is not tracked after the merge, so the tips are ordered by number
+    /// descending and then by hash, which puts the highest candidate first.
+    pub fn tips(&self) -> Vec<BlockNumHash> {
+        self.state.tree_state.tips()
+    }
+
+    /// Returns whether a block at the given number is served from the in-memory tree rather than
+    /// the database.
+    pub fn serves_number(&self, number: BlockNumber) -> bool {
+        self.state.tree_state.contains_number(number)
+    }
+
+    /// Returns the hashes of all blocks in the in-memory tree at the given number, canonical or
+    /// not, sorted by hash.
+    ///
+    /// Returns an empty vec if the tree has no block at that number.
+    pub fn blocks_at_number(&self, number: BlockNumber) -> Vec<B256> {
+        self.state.tree_state.hashes_at_number(number)
+    }
+
+    /// Returns the hashes of all transactions in the longest chain of blocks in the in-memory tree
+    /// extending the canonical head.
+    ///
+    /// Returns an empty set if no block extends the canonical head.
+    pub fn pending_transaction_hashes(&self) -> HashSet<TxHash> {
+        self.state.tree_state.pending_transaction_hashes()
+    }
+
+    /// Returns the hash and beneficiary of every block in the in-memory tree at the given number
+    /// if there is more than one, sorted by hash.
+    ///
+    /// Returns an empty vec if the tree has at most one block at that number.
+    pub fn equivocations_at(&self, number: BlockNumber) -> Vec<(B256, Address)> {
+        self.state.tree_state.equivocations_at(number)
+    }
+
+    /// Returns the hashes from the canonical block the given block of the in-memory tree builds on
+    /// up to the block itself, starting with the canonical block.
+    ///
+    /// Returns `None` if the block is not in the in-memory tree.
+    pub fn fork_path(&self, hash: B256) -> Option<Vec<B256>> {
+        self.state.tree_state.fork_path(hash)
+    }
+
+    /// Returns the hashes of all blocks in the in-memory tree that directly build on the given
+    /// block, sorted by hash.
+    ///
+    /// Returns an empty vec if no block in the tree has the given block as parent.
+    pub fn children_of(&self, hash: B256) -> Vec<B256> {
+        self.state.tree_state.children_of(hash)
+    }
+
+    /// Returns whether the block with the given hash is part of the canonical chain.
+    ///
+    /// Blocks of the in-memory tree are resolved without touching the database, so this is cheap
+    /// for recent blocks. Only blocks below the in-memory window, i.e. blocks that were already
+    /// persisted, are looked up in the database by comparing the canonical hash at their number.
+    ///
+    /// Returns `false` for unknown hashes.
+    pub fn is_canonical(&self, hash: B256) -> TreeResult<bool> {
+        if self.state.tree_state.is_canonical(hash) {
+            return Ok(true)
+        }
+        if self.state.tree_state.executed_block_by_hash(hash).is_some() {
+            // sidechain block
+            return Ok(false)
+        }
+
+        let Some(number) = self.provider.block_number(hash)? else { return Ok(false) };
+        Ok(self.provider.block_hash(number)? == Some(hash))
+    }
+
+    /// Returns the range of blocks above the canonical head that a peer with the given head can
+    /// provide.
+    ///
+    /// Returns `None` if the canonical head is at or above the peer's head, so there is nothing to
+    /// request.
+    pub fn missing_range(&self, peer_head: BlockNumber) -> Option<RangeInclusive<BlockNumber>> {
+        let best_number = self.state.tree_state.current_canonical_head.number;
+        (peer_head > best_number).then(|| best_number + 1..=peer_head)
+    }
+
+    /// Returns an iterator over the canonical blocks from the given block number up to the
+    /// canonical head, oldest first.
+    ///
+    /// Blocks are loaded lazily one at a time, from the in-memory state if present and from the
+    /// database otherwise, so the consumer can stop early without loading the whole range. The
+    /// canonical head is fixed when the iterator is created. A block that cannot be read is
+    /// yielded as an error.
+    pub fn canonical_blocks_iter(
+        &self,
+        from: BlockNumber,
+    ) -> impl Iterator<Item = TreeResult<SealedBlock<N::Block>>> + '_ {
+        let tip = self.state.tree_state.current_canonical_head.number;
+        (from..=tip).map(move |number| {
+            let hash = match self.canonical_in_memory_state.hash_by_number(number) {
+                Some(hash) => hash,
+                None => self
+                    .provider
+                    .block_hash(number)?
+                    .ok_or(ProviderError::HeaderNotFound(number.into()))?,
+            };
+            let block = self
+                .sealed_block_by_hash(hash)?
+                .ok_or(ProviderError::HeaderNotFound(hash.into()))?;
+            Ok(block)
+        })
+    }
+
+    /// Returns the addresses of all accounts changed by the canonical blocks after the block with
+    /// the given hash, up to the canonical head.
+    ///
+    /// If the block is no longer canonical, e.g. after a reorg, the accounts changed by the blocks
+    /// from the common ancestor up to and including the given block are included as well, because
+    /// their changes were reverted. Changes of blocks held in memory are taken from their execution
+    /// outcome, while older blocks are read from the database changesets.
+    ///
+    /// Returns an error if the block is unknown.
+    pub fn touched_accounts_since(&self, base: B256) -> TreeResult<HashSet<Address>> {
+        let mut touched = HashSet::default();
+
+        // resolve the common ancestor of the block and the canonical chain
+        let mut ancestor = base;
+        while !self.is_canonical(ancestor)? {
+            let block = self
+                .state
+                .tree_state
+                .executed_block_by_hash(ancestor)
+                .ok_or(ProviderError::BlockHashNotFound(ancestor))?;
+            touched.extend(block.execution_outcome().bundle.state().keys().copied());
+            ancestor = block.recovered_block().parent_hash();
+        }
+
+        let ancestor = self
+            .sealed_header_by_hash(ancestor)?
+            .ok_or(ProviderError::BlockHashNotFound(ancestor))?;
+        let tip = self.state.tree_state.current_canonical_head.number;
+        let range = ancestor.number() + 1..tip + 1;
+
+        // the blocks held in memory are the most recent ones, everything below was persisted
+        let mut persisted_end = range.end;
+        for number in range.clone().rev() {
+            let Some(block) = self.canonical_in_memory_state.state_by_number(number) else { break };
+            touched.extend(block.block_ref().execution_outcome().bundle.state().keys().copied());
+            persisted_end = number;
+        }
+        touched.extend(
+            self.provider
+                .account_changesets_range(range.start..persisted_end)?
+                .into_iter()
+                .map(|(_, account)| account.address),
+        );
+
+        Ok(touched)
+    }
+
+    /// Unwinds the canonical chain to the block with the given number.
+    ///
+    /// This is meant for operator driven recovery and is not used for reorgs. The unwound blocks
+    /// stay in the in-memory tree as a sidechain and are returned as a [`Chain`], oldest first.
+    /// If the target is at or above the canonical head, nothing is unwound and the returned chain
+    /// is empty.
+    ///
+    /// Only blocks that are not yet persisted can be unwound, and the target must not be below the
+    /// finalized block.
+    pub fn unwind_to(&mut self, target: BlockNumber) -> TreeResult<Chain<N>> {
+        if let Some(finalized) = self.canonical_in_memory_state.get_finalized_num_hash() &&
+            target < finalized.number
+        {
+            return Err(TreeError::FinalizedRegression { target, finalized: finalized.number })
+        }
+
+        let persisted = self.persistence_state.last_persisted_block.number;
+        if target < persisted {
+            return Err(TreeError::ReorgTooDeep { target, persisted })
+        }
+
+        let head = self.state.tree_state.current_canonical_head;
+        if target >= head.number {
+            return Ok(Chain::default())
+        }
+
+        // collect the canonical blocks above the target, newest first
+        let mut reverted = Vec::new();
+        let mut current = head;
+        while current.number > target {
+            let block = self
+                .state
+                .tree_state
+                .executed_block_by_hash(current.hash)
+                .ok_or(TreeError::MissingBlock(current.number))?
+                .clone();
+            current = BlockNumHash::new(current.number - 1, block.recovered_block().parent_hash());
+            reverted.push(block);
+        }
+
+        // check the unwound blocks before touching any state
+        let chain = reverted.iter().rev().try_fold(Chain::default(), |mut chain, block| {
+            chain
+                .try_append_block(
+                    block.recovered_block().clone(),
+                    block.execution_outcome().clone(),
+                )
+                .map_err(TreeError::UnwoundChainNotContiguous)?;
+            Ok::<_, TreeError>(chain)
+        })?;
+
+        let header = self
+            .sealed_header_by_hash(current.hash)?
+            .ok_or(TreeError::MissingBlock(current.number))?;
+        self.update_latest_block_to_canonical_ancestor(&header)?;
+        self.metrics.tree.canonical_chain_height.set(target as f64);
+
+        warn!(target: "engine::tree", from = head.number, to = target, "Unwound canonical chain");
+
+        Ok(chain)
+    }
+
+    /// Builds a block on top of the canonical head out of the given transactions.
+    ///
+    /// The transactions are executed in order on top of the state of the canonical head. Reverted
+    /// transactions are included, while transactions that are invalid in that state, e.g. because
+    /// of a wrong nonce or because they don't fit into the remaining block gas, are skipped and
+    /// returned alongside the block. The environment of the block, like its beneficiary and
+    /// timestamp, is taken from the given attributes.
+    ///
+    /// The block is not inserted into the tree.
+    pub fn build_block(
+        &self,
+        transactions: Vec<Recovered<N::SignedTx>>,
+        attributes: C::NextBlockEnvCtx,
+    ) -> TreeResult<(SealedBlock<N::Block>, Vec<Recovered<N::SignedTx>>)> {
+        let head = self.state.tree_state.current_canonical_head;
+        let parent = self
+            .sealed_header_by_hash(head.hash)?
+            .ok_or(ProviderError::HeaderNotFound(head.hash.into()))?;
+        let state_provider = self
+            .state_provider_builder(head.hash)?
+            .ok_or(ProviderError::StateForHashNotFound(head.hash))?
+            .build()?;
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(&state_provider))
+            .with_bundle_update()
+            .build();
+
+        let mut builder = self
+            .evm_config
+            .builder_for_next_block(&mut db, &parent, attributes)
+            .map_err(BlockExecutionError::other)?;
+        builder.apply_pre_execution_changes()?;
+
+        let skipped = builder.execute_transactions_skip_invalid(transactions)?;
+
+        let outcome = builder.finish(&state_provider)?;
+        debug!(target: "engine::tree", block = ?outcome.block.num_hash(), skipped = skipped.len(), "Built block");
+
+        Ok((outcome.block.into_sealed_block(), skipped))
+    }
+
+    /// Returns a snapshot of the in-memory tree for debugging.
+    ///
+    /// This only copies block numbers and hashes, so it is cheap enough to call on demand.
+    pub fn debug_snapshot(&self) -> TreeSnapshot {
+        let tree_state = &self.state.tree_state;
+        TreeSnapshot {
+            canonical_head: *tree_state.canonical_head(),
+            canonical_window: tree_state.canonical_window(),
+            branches: tree_state.branches(),
+            buffered_blocks: self.state.buffer.blocks.len(),
+        }
+    }
+
+    /// Returns the inclusion status of the transaction with the given hash.
+    ///
+    /// A canonical inclusion in the executed blocks of the tree or in the database, where every
+    /// transaction is canonical, takes precedence over an inclusion in a pending or sidechain
+    /// block of the tree.
+    pub fn transaction_status(&self, tx_hash: B256) -> TreeResult<TxStatus> {
+        let in_memory = self.state.tree_state.transaction_status(tx_hash);
+        if let Some(status @ TxStatus::Canonical { .. }) = in_memory {
+            return Ok(status)
+        }
+
+        if let Some((_, meta)) = self.provider.transaction_by_hash_with_meta(tx_hash)? {
+            return Ok(TxStatus::Canonical {
+                block: BlockNumHash::new(meta.block_number, meta.block_hash),
+                index: meta.index,
+            })
+        }
+
+        Ok(in_memory.unwrap_or(TxStatus::Unknown))
+    }
+
     /// Updates the tracked finalized block if we have it.
     fn update_finalized_block(
-        &self,
+        &mut self,
         finalized_block_hash: B256,
     ) -> Result<(), OnForkChoiceUpdated> {
         if finalized_block_hash.is_zero() {
@@ -2594,6 +3359,8 @@ where
                     self.canonical_in_memory_state.set_finalized(finalized.clone());
                     // Update finalized block height metric
                     self.metrics.tree.finalized_block_height.set(finalized.number() as f64);
+                    // blocks building on a finalized invalid block can never become canonical
+                    self.state.invalid_headers.remove_finalized(finalized.number());
                 }
             }
             Err(err) => {
@@ -2643,7 +3410,7 @@ where
     /// This also updates the safe and finalized blocks in the [`CanonicalInMemoryState`], if they
     /// are consistent with the head block.
     fn ensure_consistent_forkchoice_state(
-        &self,
+        &mut self,
         state: ForkchoiceState,
     ) -> Result<(), OnForkChoiceUpdated> {
         // Ensure that the finalized block, if not zero, is known and in the canonical chain
@@ -2804,6 +3571,22 @@ pub enum InsertPayloadOk {
     Inserted(BlockStatus),
 }
 
+/// Outcome of the checks and execution performed before a block is inserted into the tree.
+#[derive(Debug)]
+enum ExecuteBlockOutcome<N: NodePrimitives> {
+    /// The block is already known to the tree.
+    AlreadySeen,
+    /// The parent state is not available, so the block could not be executed.
+    Disconnected(RecoveredBlock<N::Block>),
+    /// The block was executed successfully.
+    Executed {
+        /// The executed block.
+        executed: ExecutedBlock<N>,
+        /// Whether the block is on a fork.
+        is_fork: bool,
+    },
+}
+
 /// Whether or not the blocks are currently persisting and the input block is a descendant.
 #[derive(Debug, Clone, Copy)]
 pub enum PersistingKind {
@@ -4,16 +4,137 @@ use crate::engine::EngineApiKind;
 use alloy_eips::{eip1898::BlockWithParent, BlockNumHash};
 use alloy_primitives::{
     map::{HashMap, HashSet},
-    BlockNumber, B256,
+    Address, BlockNumber, TxHash, B256,
 };
 use reth_chain_state::{EthPrimitives, ExecutedBlock};
-use reth_primitives_traits::{AlloyBlockHeader, NodePrimitives, SealedHeader};
+use reth_primitives_traits::{
+    AlloyBlockHeader, Block, BlockBody, NodePrimitives, SealedBlock, SealedHeader,
+};
+use schnellru::{ByLength, LruMap};
+use serde::Serialize;
 use std::{
     collections::{btree_map, hash_map, BTreeMap, VecDeque},
     ops::Bound,
+    time::SystemTime,
 };
 use tracing::debug;
 
+/// The maximum number of validated headers remembered by the [`TreeState`].
+const MAX_VALIDATED_HEADERS: u32 = 1024;
+
+/// Inclusion status of a transaction across the tree and the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxStatus {
+    /// The transaction is included in a canonical block.
+    Canonical {
+        /// The block that includes the transaction.
+        block: BlockNumHash,
+        /// The index of the transaction in the block.
+        index: u64,
+    },
+    /// The transaction is included in a non-canonical block that extends the canonical head.
+    Pending {
+        /// The block that includes the transaction.
+        block: BlockNumHash,
+    },
+    /// The transaction is included in a non-canonical block that forks off below the canonical
+    /// head.
+    Sidechain {
+        /// The block that includes the transaction.
+        block: BlockNumHash,
+    },
+    /// The transaction is not known.
+    Unknown,
+}
+
+/// A reorg of the canonical chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReorgRecord {
+    /// The number of blocks of the old chain that were reorged out.
+    pub depth: u64,
+    /// The time the reorg happened.
+    pub timestamp: SystemTime,
+    /// The tip of the canonical chain before the reorg.
+    pub old_tip: BlockNumHash,
+    /// The tip of the canonical chain after the reorg.
+    pub new_tip: BlockNumHash,
+}
+
+/// The blocks that differ between two chains, see
+/// [`EngineApiTreeHandler::compute_reorg`](crate::tree::EngineApiTreeHandler::compute_reorg).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reorg<B: Block> {
+    /// The highest block both chains have in common.
+    pub common_ancestor: BlockNumHash,
+    /// The blocks of the old chain above the common ancestor, from highest to lowest.
+    pub reverted: Vec<SealedBlock<B>>,
+    /// The blocks of the new chain above the common ancestor, from lowest to highest.
+    pub committed: Vec<SealedBlock<B>>,
+}
+
+/// A serializable snapshot of the in-memory tree, without any block bodies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeSnapshot {
+    /// The canonical head.
+    pub canonical_head: BlockNumHash,
+    /// The lowest and highest number of the canonical blocks held in memory, if any.
+    pub canonical_window: Option<(BlockNumber, BlockNumber)>,
+    /// The branches of the tree, ordered by their first block.
+    pub branches: Vec<BranchSnapshot>,
+    /// The number of blocks in the buffer that are not connected to the tree yet.
+    pub buffered_blocks: usize,
+}
+
+/// A run of blocks in the tree without forks, as part of a [`TreeSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchSnapshot {
+    /// The parent hash of the first block of the branch.
+    pub fork_point: B256,
+    /// The first block of the branch.
+    pub first: BlockNumHash,
+    /// The last block of the branch.
+    pub tip: BlockNumHash,
+    /// The number of blocks in the branch.
+    pub block_count: usize,
+    /// Whether the tip of the branch is part of the canonical chain.
+    pub canonical: bool,
+}
+
+/// A block in the tree that has more than one child, i.e. the point where chains fork.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkEdge {
+    /// The hash of the block the chains fork off.
+    pub parent: B256,
+    /// The children of the parent block, ordered by hash.
+    pub children: Vec<ForkChild>,
+}
+
+/// A child of a [`ForkEdge`], the first block of a chain that forks off the parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForkChild {
+    /// The hash of the child block.
+    pub hash: B256,
+    /// The highest block descending from the child, or the child itself.
+    pub tip: BlockNumHash,
+    /// Whether the child is part of the canonical chain.
+    pub canonical: bool,
+}
+
+/// Block numbers of the most recently validated headers, by block hash.
+///
+/// Holds at most [`MAX_VALIDATED_HEADERS`] headers, the least recently validated ones are evicted
+/// first.
+#[derive(Debug)]
+pub(crate) struct ValidatedHeaders(LruMap<B256, BlockNumber>);
+
+impl Default for ValidatedHeaders {
+    fn default() -> Self {
+        Self(LruMap::new(ByLength::new(MAX_VALIDATED_HEADERS)))
+    }
+}
+
 /// Keeps track of the state of the tree.
 ///
 /// ## Invariants
@@ -36,6 +157,12 @@ pub struct TreeState<N: NodePrimitives = EthPrimitives> {
     pub(crate) parent_to_child: HashMap<B256, HashSet<B256>>,
     /// Currently tracked canonical head of the chain.
     pub(crate) current_canonical_head: BlockNumHash,
+    /// Block numbers of blocks whose headers already passed consensus validation, by block
+    /// hash.
+    ///
+    /// Keyed by hash so that a block at the same height on another fork is not considered
+    /// validated.
+    pub(crate) validated_headers: ValidatedHeaders,
     /// The engine API variant of this handler
     pub(crate) engine_kind: EngineApiKind,
 }
@@ -48,6 +175,7 @@ impl<N: NodePrimitives> TreeState<N> {
             blocks_by_number: BTreeMap::new(),
             current_canonical_head,
             parent_to_child: HashMap::default(),
+            validated_headers: ValidatedHeaders::default(),
             engine_kind,
         }
     }
@@ -62,6 +190,69 @@ impl<N: NodePrimitives> TreeState<N> {
         self.blocks_by_hash.len()
     }
 
+    /// Returns the number of executed blocks, canonical or not, held at the given number.
+    pub(crate) fn block_count_at_number(&self, number: BlockNumber) -> usize {
+        self.blocks_by_number.get(&number).map_or(0, Vec::len)
+    }
+
+    /// Returns whether any executed block, canonical or not, is held at the given number.
+    pub(crate) fn contains_number(&self, number: BlockNumber) -> bool {
+        self.blocks_by_number.contains_key(&number)
+    }
+
+    /// Returns the hashes of all executed blocks at the given number, sorted by hash.
+    ///
+    /// More than one hash means that the tree forks at this height.
+    pub(crate) fn hashes_at_number(&self, number: BlockNumber) -> Vec<B256> {
+        let mut hashes = self
+            .blocks_by_number
+            .get(&number)
+            .into_iter()
+            .flatten()
+            .map(|executed| executed.recovered_block().hash())
+            .collect::<Vec<_>>();
+        hashes.sort_unstable();
+        hashes
+    }
+
+    /// Returns the hash and beneficiary of every executed block at the given number if the tree
+    /// holds more than one block at that number, sorted by hash.
+    ///
+    /// Callers can group the blocks by beneficiary to detect a proposer building competing blocks.
+    pub(crate) fn equivocations_at(&self, number: BlockNumber) -> Vec<(B256, Address)> {
+        let Some(blocks) = self.blocks_by_number.get(&number).filter(|blocks| blocks.len() > 1)
+        else {
+            return Vec::new()
+        };
+
+        let mut equivocations = blocks
+            .iter()
+            .map(|executed| {
+                let block = executed.recovered_block();
+                (block.hash(), block.beneficiary())
+            })
+            .collect::<Vec<_>>();
+        equivocations.sort_unstable();
+        equivocations
+    }
+
+    /// Returns the hashes of all executed blocks whose parent is the given block, sorted by hash.
+    ///
+    /// This covers both forks and the linear successor of the block, and the block itself does
+    /// not need to be in the tree, e.g. if it's the last persisted block.
+    pub(crate) fn children_of(&self, hash: B256) -> Vec<B256> {
+        let mut children = self
+            .parent_to_child
+            .get(&hash)
+            .into_iter()
+            .flatten()
+            .filter(|child| self.blocks_by_hash.contains_key(*child))
+            .copied()
+            .collect::<Vec<_>>();
+        children.sort_unstable();
+        children
+    }
+
     /// Returns the [`ExecutedBlock`] by hash.
     pub(crate) fn executed_block_by_hash(&self, hash: B256) -> Option<&ExecutedBlock<N>> {
         self.blocks_by_hash.get(&hash)
@@ -119,6 +310,7 @@ impl<N: NodePrimitives> TreeState<N> {
     /// The removed block and the block hashes of its children.
     fn remove_by_hash(&mut self, hash: B256) -> Option<(ExecutedBlock<N>, HashSet<B256>)> {
         let executed = self.blocks_by_hash.remove(&hash)?;
+        self.validated_headers.0.remove(&hash);
 
         // Remove this block from collection of children of its parent block.
         let parent_entry = self.parent_to_child.entry(executed.recovered_block().parent_hash());
@@ -151,6 +343,23 @@ impl<N: NodePrimitives> TreeState<N> {
         Some((executed, children))
     }
 
+    /// Returns whether the header of the block with the given hash already passed consensus
+    /// validation.
+    pub(crate) fn is_header_validated(&self, hash: B256) -> bool {
+        self.validated_headers.0.peek(&hash).is_some()
+    }
+
+    /// Records that the header of the given block passed consensus validation.
+    pub(crate) fn insert_validated_header(&mut self, block: BlockNumHash) {
+        self.validated_headers.0.insert(block.hash, block.number);
+    }
+
+    /// Forgets that the header of the block with the given hash passed consensus validation, so
+    /// it is validated again if the block is re-delivered.
+    pub(crate) fn remove_validated_header(&mut self, hash: B256) {
+        self.validated_headers.0.remove(&hash);
+    }
+
     /// Returns whether or not the hash is part of the canonical chain.
     pub(crate) fn is_canonical(&self, hash: B256) -> bool {
         let mut current_block = self.current_canonical_head.hash;
@@ -168,6 +377,96 @@ impl<N: NodePrimitives> TreeState<N> {
         false
     }
 
+    /// Returns the hashes from the canonical block the given block builds on up to the block
+    /// itself, starting with the canonical block.
+    ///
+    /// Blocks outside the tree are persisted and therefore canonical, so the path ends at the
+    /// latest persisted block at the latest. If the block is canonical, the path only contains the
+    /// block itself.
+    ///
+    /// Returns `None` if the block is not in the tree.
+    pub(crate) fn fork_path(&self, hash: B256) -> Option<Vec<B256>> {
+        self.blocks_by_hash.get(&hash)?;
+
+        let mut path = vec![hash];
+        let mut current = hash;
+        while !self.is_canonical(current) {
+            let Some(executed) = self.blocks_by_hash.get(&current) else { break };
+            current = executed.recovered_block().parent_hash();
+            path.push(current);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns the inclusion status of the transaction if it is included in any executed block.
+    ///
+    /// If the transaction is included in multiple forks, a canonical inclusion takes precedence
+    /// over a pending one, which takes precedence over a sidechain one.
+    pub(crate) fn transaction_status(&self, tx_hash: TxHash) -> Option<TxStatus> {
+        // walk the canonical chain first, so a canonical inclusion doesn't require visiting forks
+        let mut current = self.current_canonical_head.hash;
+        while let Some(executed) = self.blocks_by_hash.get(&current) {
+            let block = executed.recovered_block();
+            if let Some(indexed) = block.find_indexed(tx_hash) {
+                return Some(TxStatus::Canonical {
+                    block: block.num_hash(),
+                    index: indexed.index() as u64,
+                });
+            }
+            current = block.parent_hash();
+        }
+
+        let mut status = None;
+        for executed in self.blocks_by_hash.values() {
+            let block = executed.recovered_block();
+            if block.find_indexed(tx_hash).is_none() {
+                continue
+            }
+
+            if self.is_descendant(self.current_canonical_head, block.block_with_parent()) {
+                return Some(TxStatus::Pending { block: block.num_hash() })
+            }
+            status.get_or_insert(TxStatus::Sidechain { block: block.num_hash() });
+        }
+        status
+    }
+
+    /// Returns the hashes of all transactions in the longest chain of executed blocks extending
+    /// the canonical head.
+    ///
+    /// If multiple chains are equally long, the one with the lowest tip hash is used. Only the
+    /// descendants of the canonical head are visited. Returns an empty set if no block extends
+    /// the canonical head.
+    pub(crate) fn pending_transaction_hashes(&self) -> HashSet<TxHash> {
+        let head = self.current_canonical_head.hash;
+
+        // find the highest descendant of the canonical head
+        let mut tip: Option<BlockNumHash> = None;
+        let mut stack = self.children_of(head);
+        while let Some(hash) = stack.pop() {
+            let Some(executed) = self.blocks_by_hash.get(&hash) else { continue };
+            let num_hash = executed.recovered_block().num_hash();
+            if tip.is_none_or(|tip| {
+                num_hash.number > tip.number ||
+                    (num_hash.number == tip.number && num_hash.hash < tip.hash)
+            }) {
+                tip = Some(num_hash);
+            }
+            stack.extend(self.children_of(hash));
+        }
+
+        let mut hashes = HashSet::default();
+        let mut current = tip.map(|tip| tip.hash);
+        while let Some(hash) = current.filter(|hash| *hash != head) {
+            let Some(executed) = self.blocks_by_hash.get(&hash) else { break };
+            let block = executed.recovered_block();
+            hashes.extend(block.body().transaction_hashes_iter().copied());
+            current = Some(block.parent_hash());
+        }
+        hashes
+    }
+
     /// Removes canonical blocks below the upper bound, only if the last persisted hash is
     /// part of the canonical chain.
     pub(crate) fn remove_canonical_until(
@@ -197,56 +496,345 @@ impl<N: NodePrimitives> TreeState<N> {
         debug!(target: "engine::tree", ?upper_bound, ?last_persisted_hash, "Removed canonical blocks from the tree");
     }
 
-    /// Removes all blocks that are below the finalized block, as well as removing non-canonical
-    /// sidechains that fork from below the finalized block.
-    pub(crate) fn prune_finalized_sidechains(&mut self, finalized_num_hash: BlockNumHash) {
-        let BlockNumHash { number: finalized_num, hash: finalized_hash } = finalized_num_hash;
+    /// Returns all fork points of the tree.
+    ///
+    /// Edges are ordered by the block number of their children and then by parent hash, so the
+    /// output is stable for the same tree.
+    pub(crate) fn fork_graph(&self) -> Vec<ForkEdge> {
+        let mut edges = self
+            .parent_to_child
+            .iter()
+            .filter(|(_, children)| children.len() > 1)
+            .map(|(parent, children)| {
+                let mut children = children
+                    .iter()
+                    .map(|hash| ForkChild {
+                        hash: *hash,
+                        tip: self.branch_tip(*hash),
+                        canonical: self.is_canonical(*hash),
+                    })
+                    .collect::<Vec<_>>();
+                children.sort_unstable_by_key(|child| child.hash);
+                ForkEdge { parent: *parent, children }
+            })
+            .collect::<Vec<_>>();
+        edges.sort_unstable_by_key(|edge| {
+            let number = edge
+                .children
+                .first()
+                .and_then(|child| self.blocks_by_hash.get(&child.hash))
+                .map(|executed| executed.recovered_block().number());
+            (number, edge.parent)
+        });
+        edges
+    }
 
-        // We remove disconnected sidechains in three steps:
-        // * first, remove everything with a block number __below__ the finalized block.
-        // * next, we populate a vec with parents __at__ the finalized block.
-        // * finally, we iterate through the vec, removing children until the vec is empty
-        // (BFS).
+    /// Returns the lowest and highest number of the canonical blocks held in memory.
+    pub(crate) fn canonical_window(&self) -> Option<(BlockNumber, BlockNumber)> {
+        let head = self.blocks_by_hash.get(&self.current_canonical_head.hash)?;
+        let mut lowest = head.recovered_block().number();
+        let mut current_block = head.recovered_block().parent_hash();
+        while let Some(executed) = self.blocks_by_hash.get(&current_block) {
+            lowest = executed.recovered_block().number();
+            current_block = executed.recovered_block().parent_hash();
+        }
+        Some((lowest, self.current_canonical_head.number))
+    }
 
-        // We _exclude_ the finalized block because we will be dealing with the blocks __at__
-        // the finalized block later.
-        let blocks_to_remove = self
-            .blocks_by_number
-            .range((Bound::Unbounded, Bound::Excluded(finalized_num)))
-            .flat_map(|(_, blocks)| blocks.iter().map(|b| b.recovered_block().hash()))
+    /// Returns all runs of blocks in the tree without forks, ordered by their first block.
+    ///
+    /// A branch starts at a block whose parent is not in the tree or has more than one child,
+    /// and ends at a block without exactly one child.
+    pub(crate) fn branches(&self) -> Vec<BranchSnapshot> {
+        let single_child = |hash: &B256| {
+            self.parent_to_child
+                .get(hash)
+                .filter(|children| children.len() == 1)
+                .and_then(|children| children.iter().next())
+                .and_then(|child| self.blocks_by_hash.get(child))
+        };
+
+        let mut branches = self
+            .blocks_by_hash
+            .values()
+            .map(|executed| executed.recovered_block())
+            .filter(|block| {
+                !self.blocks_by_hash.contains_key(&block.parent_hash()) ||
+                    single_child(&block.parent_hash()).is_none()
+            })
+            .map(|block| {
+                let mut tip = block.num_hash();
+                let mut block_count = 1;
+                while let Some(child) = single_child(&tip.hash) {
+                    tip = child.recovered_block().num_hash();
+                    block_count += 1;
+                }
+                BranchSnapshot {
+                    fork_point: block.parent_hash(),
+                    first: block.num_hash(),
+                    tip,
+                    block_count,
+                    canonical: self.is_canonical(tip.hash),
+                }
+            })
+            .collect::<Vec<_>>();
+        branches.sort_unstable_by_key(|branch| (branch.first.number, branch.first.hash));
+        branches
+    }
+
+    /// Returns all blocks in the tree without children, ordered by number descending and then by
+    /// hash.
+    pub(crate) fn tips(&self) -> Vec<BlockNumHash> {
+        let mut tips = self
+            .blocks_by_hash
+            .values()
+            .map(|executed| executed.recovered_block())
+            .filter(|block| {
+                self.parent_to_child.get(&block.hash()).is_none_or(|children| {
+                    !children.iter().any(|child| self.blocks_by_hash.contains_key(child))
+                })
+            })
+            .map(|block| block.num_hash())
             .collect::<Vec<_>>();
-        for hash in blocks_to_remove {
-            if let Some((removed, _)) = self.remove_by_hash(hash) {
-                debug!(target: "engine::tree", num_hash=?removed.recovered_block().num_hash(), "Removed finalized sidechain block");
+        tips.sort_unstable_by(|a, b| b.number.cmp(&a.number).then_with(|| a.hash.cmp(&b.hash)));
+        tips
+    }
+
+    /// Returns the length, in blocks, of the longest branch in the tree that does not contain the
+    /// given block, counted from its common ancestor with the block.
+    ///
+    /// The block is either held by the tree or, if `persisted` is set, a block of the persisted
+    /// canonical chain. Branches that leave the tree are assumed to descend from the persisted
+    /// canonical chain.
+    ///
+    /// Returns `None` if the block is not persisted and not in the tree.
+    pub(crate) fn competing_weight(&self, block: BlockNumHash, persisted: bool) -> Option<u64> {
+        // ancestors of the block in the tree, including the block itself and the persisted
+        // ancestor the tree portion is anchored to
+        let mut ancestors = HashMap::default();
+        ancestors.insert(block.hash, block.number);
+        let mut anchor = block.number;
+        if !persisted {
+            let mut current = self.blocks_by_hash.get(&block.hash)?.recovered_block();
+            while let Some(parent) = self.blocks_by_hash.get(&current.parent_hash()) {
+                current = parent.recovered_block();
+                ancestors.insert(current.hash(), current.number());
             }
+            anchor = current.number().saturating_sub(1);
+            ancestors.insert(current.parent_hash(), anchor);
         }
 
-        // The only block that should remain at the `finalized` number now, is the finalized
-        // block, if it exists.
-        //
-        // For all other blocks, we  first put their children into this vec.
-        // Then, we will iterate over them, removing them, adding their children, etc,
-        // until the vec is empty.
-        let mut blocks_to_remove = self.blocks_by_number.remove(&finalized_num).unwrap_or_default();
+        let weight = self
+            .tips()
+            .into_iter()
+            .filter_map(|tip| {
+                let mut current = tip;
+                loop {
+                    if let Some(&number) = ancestors.get(&current.hash) {
+                        return (current.hash != block.hash).then(|| tip.number - number)
+                    }
+                    let Some(executed) = self.blocks_by_hash.get(&current.hash) else {
+                        // left the tree at a persisted block
+                        if persisted && current.number >= anchor {
+                            return None
+                        }
+                        return Some(tip.number - current.number.min(anchor))
+                    };
+                    let block = executed.recovered_block();
+                    current =
+                        BlockNumHash::new(block.number().saturating_sub(1), block.parent_hash());
+                }
+            })
+            .max()
+            .unwrap_or_default();
+        Some(weight)
+    }
+
+    /// Returns the highest block descending from the given block, or the block itself.
+    ///
+    /// Ties are broken by the lowest hash.
+    fn branch_tip(&self, hash: B256) -> BlockNumHash {
+        let mut tip = self
+            .blocks_by_hash
+            .get(&hash)
+            .map(|executed| executed.recovered_block().num_hash())
+            .unwrap_or(BlockNumHash { number: 0, hash });
+        let mut queue = VecDeque::from([hash]);
+        while let Some(block) = queue.pop_front() {
+            if let Some(executed) = self.blocks_by_hash.get(&block) {
+                let num_hash = executed.recovered_block().num_hash();
+                if num_hash.number > tip.number ||
+                    (num_hash.number == tip.number && num_hash.hash < tip.hash)
+                {
+                    tip = num_hash;
+                }
+            }
+            if let Some(children) = self.parent_to_child.get(&block) {
+                queue.extend(children.iter().copied());
+            }
+        }
+        tip
+    }
 
-        // re-insert the finalized hash if we removed it
-        if let Some(position) =
-            blocks_to_remove.iter().position(|b| b.recovered_block().hash() == finalized_hash)
+    /// Evicts sidechains until at most `max_sidechain_blocks` executed blocks remain that are
+    /// neither canonical nor extend the canonical head.
+    ///
+    /// Sidechains are evicted shortest first, and among sidechains of equal length the one with
+    /// the lowest tip is evicted first. The canonical chain, blocks extending the canonical head
+    /// and the sidechain containing the `keep` block are never evicted.
+    ///
+    /// The sidechains are only collected if the tree holds more than `max_sidechain_blocks`
+    /// non-canonical blocks, otherwise this only walks the canonical blocks in memory.
+    ///
+    /// Returns the number of evicted blocks.
+    pub(crate) fn evict_sidechains(&mut self, max_sidechain_blocks: usize, keep: B256) -> usize {
+        // Collect the canonical chain, including the parent of the lowest canonical block in
+        // memory, because sidechains can also fork off the last persisted block.
+        let mut canonical = HashSet::default();
+        let mut current_block = self.current_canonical_head.hash;
+        canonical.insert(current_block);
+        while let Some(executed) = self.blocks_by_hash.get(&current_block) {
+            current_block = executed.recovered_block().parent_hash();
+            canonical.insert(current_block);
+        }
+        let canonical_in_memory = canonical.len() - 1;
+        if self.blocks_by_hash.len().saturating_sub(canonical_in_memory) <= max_sidechain_blocks {
+            return 0
+        }
+
+        // The root of the sidechain containing the `keep` block, i.e. its first non-canonical
+        // ancestor.
+        let mut keep_root = keep;
+        while let Some(parent) = self
+            .blocks_by_hash
+            .get(&keep_root)
+            .map(|executed| executed.recovered_block().parent_hash())
+            .filter(|parent| !canonical.contains(parent))
         {
-            let finalized_block = blocks_to_remove.swap_remove(position);
-            self.blocks_by_number.insert(finalized_num, vec![finalized_block]);
+            keep_root = parent;
         }
 
-        let mut blocks_to_remove = blocks_to_remove
-            .into_iter()
-            .map(|e| e.recovered_block().hash())
-            .collect::<VecDeque<_>>();
+        // Every non-canonical child of a canonical block other than the head is the root of a
+        // sidechain.
+        let mut sidechains = Vec::new();
+        for hash in canonical.iter().filter(|hash| **hash != self.current_canonical_head.hash) {
+            let Some(children) = self.parent_to_child.get(hash) else { continue };
+            for root in children.iter().filter(|child| !canonical.contains(*child)) {
+                let mut blocks = Vec::new();
+                let mut tip = 0;
+                let mut queue = VecDeque::from([*root]);
+                while let Some(block) = queue.pop_front() {
+                    if let Some(executed) = self.blocks_by_hash.get(&block) {
+                        tip = tip.max(executed.recovered_block().number());
+                    }
+                    if let Some(children) = self.parent_to_child.get(&block) {
+                        queue.extend(children.iter().copied());
+                    }
+                    blocks.push(block);
+                }
+                sidechains.push((*root, blocks, tip));
+            }
+        }
+
+        let mut sidechain_blocks =
+            sidechains.iter().map(|(_, blocks, _)| blocks.len()).sum::<usize>();
+        sidechains.retain(|(root, _, _)| *root != keep_root);
+        sidechains.sort_unstable_by_key(|(_, blocks, tip)| (blocks.len(), *tip));
+
+        let mut evicted = 0;
+        for (_, blocks, _) in sidechains {
+            if sidechain_blocks <= max_sidechain_blocks {
+                break
+            }
+            sidechain_blocks -= blocks.len();
+
+            for block in blocks {
+                if let Some((removed, _)) = self.remove_by_hash(block) {
+                    debug!(target: "engine::tree", num_hash=?removed.recovered_block().num_hash(), "Evicted sidechain block");
+                    evicted += 1;
+                }
+            }
+        }
+        evicted
+    }
+
+    /// Removes the block with the given hash and all of its descendants from the tree.
+    ///
+    /// Returns the removed blocks, with every block preceding its descendants.
+    pub(crate) fn remove_with_descendants(&mut self, hash: B256) -> Vec<ExecutedBlock<N>> {
+        let mut removed = Vec::new();
+        let mut blocks_to_remove = VecDeque::from([hash]);
         while let Some(block) = blocks_to_remove.pop_front() {
-            if let Some((removed, children)) = self.remove_by_hash(block) {
-                debug!(target: "engine::tree", num_hash=?removed.recovered_block().num_hash(), "Removed finalized sidechain child block");
+            if let Some((executed, children)) = self.remove_by_hash(block) {
+                debug!(target: "engine::tree", num_hash=?executed.recovered_block().num_hash(), "Removed invalidated block");
+                removed.push(executed);
                 blocks_to_remove.extend(children);
             }
         }
+        removed
+    }
+
+    /// Returns the blocks that [`Self::prune_finalized_sidechains`] removes for the given finalized
+    /// block, without removing them.
+    ///
+    /// These are all blocks below the finalized block, and all blocks at the finalized height
+    /// other than the finalized block, together with their descendants.
+    pub(crate) fn finalized_sidechain_blocks(
+        &self,
+        finalized_num_hash: BlockNumHash,
+    ) -> Vec<BlockNumHash> {
+        let BlockNumHash { number: finalized_num, hash: finalized_hash } = finalized_num_hash;
+
+        // We _exclude_ the finalized block because we will be dealing with the blocks __at__
+        // the finalized block later.
+        let mut blocks = self
+            .blocks_by_number
+            .range((Bound::Unbounded, Bound::Excluded(finalized_num)))
+            .flat_map(|(_, blocks)| blocks.iter().map(|b| b.recovered_block().num_hash()))
+            .collect::<Vec<_>>();
+
+        // For all blocks at the finalized height other than the finalized block, we first put
+        // them into this queue. Then, we iterate over them, adding their children, etc, until
+        // the queue is empty (BFS).
+        let mut queue = self
+            .blocks_by_number
+            .get(&finalized_num)
+            .into_iter()
+            .flatten()
+            .map(|b| b.recovered_block().hash())
+            .filter(|hash| *hash != finalized_hash)
+            .collect::<VecDeque<_>>();
+        while let Some(hash) = queue.pop_front() {
+            if let Some(executed) = self.blocks_by_hash.get(&hash) {
+                blocks.push(executed.recovered_block().num_hash());
+            }
+            if let Some(children) = self.parent_to_child.get(&hash) {
+                queue.extend(children.iter().copied());
+            }
+        }
+        blocks
+    }
+
+    /// Removes all blocks that are below the finalized block, as well as removing non-canonical
+    /// sidechains that fork from below the finalized block.
+    pub(crate) fn prune_finalized_sidechains(&mut self, finalized_num_hash: BlockNumHash) {
+        // Headers at or below the finalized block will not be validated again.
+        let finalized_headers = self
+            .validated_headers
+            .0
+            .iter()
+            .filter(|(_, number)| **number <= finalized_num_hash.number)
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+        for hash in finalized_headers {
+            self.validated_headers.0.remove(&hash);
+        }
+
+        for block in self.finalized_sidechain_blocks(finalized_num_hash) {
+            if self.remove_by_hash(block.hash).is_some() {
+                debug!(target: "engine::tree", num_hash=?block, "Removed finalized sidechain block");
+            }
+        }
     }
 
     /// Remove all blocks up to __and including__ the given block number.
@@ -379,6 +967,421 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_tree_state_transaction_status() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..4).collect();
+        let fork = test_block_builder
+            .get_executed_block_with_number(2, blocks[0].recovered_block().hash());
+
+        let mut tree_state =
+            TreeState::new(blocks[0].recovered_block().num_hash(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork]) {
+            tree_state.insert_executed(block.clone());
+        }
+        tree_state.set_canonical_head(blocks[1].recovered_block().num_hash());
+
+        // the canonical chain is 1 -> 2, block 3 extends the head and the fork branches off 1
+        let expected = |block: &ExecutedBlock, index: u64| {
+            let num_hash = block.recovered_block().num_hash();
+            if num_hash == blocks[2].recovered_block().num_hash() {
+                TxStatus::Pending { block: num_hash }
+            } else if num_hash == fork.recovered_block().num_hash() {
+                TxStatus::Sidechain { block: num_hash }
+            } else {
+                TxStatus::Canonical { block: num_hash, index }
+            }
+        };
+        for block in blocks.iter().chain([&fork]) {
+            for (index, tx) in block.recovered_block().body().transactions_iter().enumerate() {
+                assert_eq!(
+                    tree_state.transaction_status(*tx.tx_hash()),
+                    Some(expected(block, index as u64))
+                );
+            }
+        }
+
+        assert_eq!(tree_state.transaction_status(B256::random()), None);
+    }
+
+    #[test]
+    fn test_tree_state_fork_graph() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+        let fork_a1 = test_block_builder
+            .get_executed_block_with_number(2, blocks[0].recovered_block().hash());
+        let fork_a2 =
+            test_block_builder.get_executed_block_with_number(3, fork_a1.recovered_block().hash());
+        let fork_b1 = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork_a1, &fork_a2, &fork_b1]) {
+            tree_state.insert_executed(block.clone());
+        }
+        tree_state.set_canonical_head(blocks[3].recovered_block().num_hash());
+
+        let child = |block: &ExecutedBlock, tip: &ExecutedBlock, canonical: bool| ForkChild {
+            hash: block.recovered_block().hash(),
+            tip: tip.recovered_block().num_hash(),
+            canonical,
+        };
+        let edge = |parent: &ExecutedBlock, mut children: Vec<ForkChild>| {
+            children.sort_unstable_by_key(|child| child.hash);
+            ForkEdge { parent: parent.recovered_block().hash(), children }
+        };
+
+        assert_eq!(
+            tree_state.fork_graph(),
+            vec![
+                edge(
+                    &blocks[0],
+                    vec![child(&blocks[1], &blocks[3], true), child(&fork_a1, &fork_a2, false)]
+                ),
+                edge(
+                    &blocks[1],
+                    vec![child(&blocks[2], &blocks[3], true), child(&fork_b1, &fork_b1, false)]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tree_state_contains_number() {
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in TestBlockBuilder::eth().get_executed_blocks(1..4) {
+            tree_state.insert_executed(block);
+        }
+
+        assert!(!tree_state.contains_number(0));
+        assert!(tree_state.contains_number(1));
+        assert!(tree_state.contains_number(3));
+        assert!(!tree_state.contains_number(4));
+    }
+
+    #[test]
+    fn test_tree_state_finalized_sidechain_blocks() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+        let fork = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+        let fork_child =
+            test_block_builder.get_executed_block_with_number(4, fork.recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork, &fork_child]) {
+            tree_state.insert_executed(block.clone());
+        }
+        tree_state.set_canonical_head(blocks[3].recovered_block().num_hash());
+
+        let finalized = blocks[2].recovered_block().num_hash();
+        let mut preview = tree_state.finalized_sidechain_blocks(finalized);
+        preview.sort_unstable_by_key(|block| block.number);
+        assert_eq!(
+            preview,
+            [&blocks[0], &blocks[1], &fork, &fork_child]
+                .map(|block| block.recovered_block().num_hash())
+        );
+        // the preview does not modify the tree
+        assert_eq!(tree_state.block_count(), 6);
+
+        tree_state.prune_finalized_sidechains(finalized);
+        assert_eq!(tree_state.block_count(), 2);
+        for block in preview {
+            assert!(!tree_state.blocks_by_hash.contains_key(&block.hash));
+        }
+    }
+
+    #[test]
+    fn test_tree_state_hashes_at_number() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..4).collect();
+        let fork = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork]) {
+            tree_state.insert_executed(block.clone());
+        }
+
+        assert_eq!(tree_state.hashes_at_number(1), vec![blocks[0].recovered_block().hash()]);
+        let mut expected = vec![blocks[2].recovered_block().hash(), fork.recovered_block().hash()];
+        expected.sort_unstable();
+        assert_eq!(tree_state.hashes_at_number(3), expected);
+        assert!(tree_state.hashes_at_number(4).is_empty());
+    }
+
+    #[test]
+    fn test_tree_state_branches() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+        let fork = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        assert_eq!(tree_state.canonical_window(), None);
+        for block in blocks.iter().chain([&fork]) {
+            tree_state.insert_executed(block.clone());
+        }
+        tree_state.set_canonical_head(blocks[3].recovered_block().num_hash());
+
+        let branch =
+            |first: &ExecutedBlock, tip: &ExecutedBlock, block_count, canonical| BranchSnapshot {
+                fork_point: first.recovered_block().parent_hash(),
+                first: first.recovered_block().num_hash(),
+                tip: tip.recovered_block().num_hash(),
+                block_count,
+                canonical,
+            };
+        let mut expected = vec![
+            branch(&blocks[0], &blocks[1], 2, true),
+            branch(&blocks[2], &blocks[3], 2, true),
+            branch(&fork, &fork, 1, false),
+        ];
+        expected.sort_unstable_by_key(|branch| (branch.first.number, branch.first.hash));
+
+        assert_eq!(tree_state.branches(), expected);
+        assert_eq!(tree_state.canonical_window(), Some((1, 4)));
+    }
+
+    #[test]
+    fn test_tree_state_tips() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+        let fork_a = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+        let fork_b = test_block_builder
+            .get_executed_block_with_number(2, blocks[0].recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        assert!(tree_state.tips().is_empty());
+        for block in blocks.iter().chain([&fork_a, &fork_b]) {
+            tree_state.insert_executed(block.clone());
+        }
+
+        assert_eq!(
+            tree_state.tips(),
+            vec![
+                blocks[3].recovered_block().num_hash(),
+                fork_a.recovered_block().num_hash(),
+                fork_b.recovered_block().num_hash(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tree_state_fork_path() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..4).collect();
+        let fork1 = test_block_builder
+            .get_executed_block_with_number(2, blocks[0].recovered_block().hash());
+        let fork2 =
+            test_block_builder.get_executed_block_with_number(3, fork1.recovered_block().hash());
+        let persisted_fork = test_block_builder
+            .get_executed_block_with_number(1, blocks[0].recovered_block().parent_hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork1, &fork2, &persisted_fork]) {
+            tree_state.insert_executed(block.clone());
+        }
+        tree_state.set_canonical_head(blocks[2].recovered_block().num_hash());
+        let hash = |block: &ExecutedBlock| block.recovered_block().hash();
+
+        assert_eq!(
+            tree_state.fork_path(hash(&fork2)),
+            Some(vec![hash(&blocks[0]), hash(&fork1), hash(&fork2)])
+        );
+        assert_eq!(
+            tree_state.fork_path(hash(&persisted_fork)),
+            Some(vec![blocks[0].recovered_block().parent_hash(), hash(&persisted_fork)])
+        );
+        assert_eq!(tree_state.fork_path(hash(&blocks[1])), Some(vec![hash(&blocks[1])]));
+        assert_eq!(tree_state.fork_path(B256::random()), None);
+    }
+
+    #[test]
+    fn test_tree_state_pending_transaction_hashes() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+        let fork = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+
+        let mut tree_state =
+            TreeState::new(blocks[0].recovered_block().num_hash(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork]) {
+            tree_state.insert_executed(block.clone());
+        }
+        tree_state.set_canonical_head(blocks[1].recovered_block().num_hash());
+
+        // blocks 3 and 4 form the longest chain extending the head, the fork is shorter
+        let tx_hashes = |blocks: &[ExecutedBlock]| {
+            blocks
+                .iter()
+                .flat_map(|block| {
+                    block
+                        .recovered_block()
+                        .body()
+                        .transaction_hashes_iter()
+                        .copied()
+                        .collect::<Vec<_>>()
+                })
+                .collect::<HashSet<_>>()
+        };
+        assert_eq!(tree_state.pending_transaction_hashes(), tx_hashes(&blocks[2..]));
+
+        tree_state.set_canonical_head(blocks[3].recovered_block().num_hash());
+        assert!(tree_state.pending_transaction_hashes().is_empty());
+    }
+
+    #[test]
+    fn test_tree_state_equivocations_at() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..3).collect();
+        let fork = test_block_builder
+            .get_executed_block_with_number(2, blocks[0].recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork]) {
+            tree_state.insert_executed(block.clone());
+        }
+
+        let mut expected = [&blocks[1], &fork]
+            .map(|block| {
+                let block = block.recovered_block();
+                (block.hash(), block.beneficiary())
+            })
+            .to_vec();
+        expected.sort_unstable();
+        assert_eq!(tree_state.equivocations_at(2), expected);
+        assert!(tree_state.equivocations_at(1).is_empty());
+        assert!(tree_state.equivocations_at(3).is_empty());
+    }
+
+    #[test]
+    fn test_tree_state_children_of() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..4).collect();
+        let fork = test_block_builder
+            .get_executed_block_with_number(2, blocks[0].recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork]) {
+            tree_state.insert_executed(block.clone());
+        }
+
+        // fork point
+        let mut expected = vec![blocks[1].recovered_block().hash(), fork.recovered_block().hash()];
+        expected.sort_unstable();
+        assert_eq!(tree_state.children_of(blocks[0].recovered_block().hash()), expected);
+        // linear successor
+        assert_eq!(
+            tree_state.children_of(blocks[1].recovered_block().hash()),
+            vec![blocks[2].recovered_block().hash()]
+        );
+        // persisted parent of the lowest block
+        assert_eq!(
+            tree_state.children_of(blocks[0].recovered_block().parent_hash()),
+            vec![blocks[0].recovered_block().hash()]
+        );
+        // tips
+        assert!(tree_state.children_of(blocks[2].recovered_block().hash()).is_empty());
+        assert!(tree_state.children_of(fork.recovered_block().hash()).is_empty());
+    }
+
+    #[test]
+    fn test_tree_state_competing_weight() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..6).collect();
+        // a two block sidechain forking off block 2
+        let fork1 = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+        let fork2 =
+            test_block_builder.get_executed_block_with_number(4, fork1.recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork1, &fork2]) {
+            tree_state.insert_executed(block.clone());
+        }
+        let num_hash = |block: &ExecutedBlock| block.recovered_block().num_hash();
+
+        // blocks below the fork are part of every branch
+        assert_eq!(tree_state.competing_weight(num_hash(&blocks[1]), false), Some(0));
+        // the canonical chain after the fork competes with the two sidechain blocks
+        assert_eq!(tree_state.competing_weight(num_hash(&blocks[2]), false), Some(2));
+        // the sidechain competes with the three canonical blocks after the fork
+        assert_eq!(tree_state.competing_weight(num_hash(&fork1), false), Some(3));
+        assert_eq!(tree_state.competing_weight(num_hash(&fork2), false), Some(3));
+
+        // the persisted parent of the lowest block is contained in all branches
+        let persisted = BlockNumHash::new(0, blocks[0].recovered_block().parent_hash());
+        assert_eq!(tree_state.competing_weight(persisted, true), Some(0));
+
+        let unknown = BlockNumHash::new(3, B256::random());
+        assert_eq!(tree_state.competing_weight(unknown, false), None);
+    }
+
+    #[test]
+    fn test_tree_state_evict_sidechains() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+
+        // a two block sidechain forking off block 1 and a single block sidechain forking off
+        // block 2
+        let fork_a1 = test_block_builder
+            .get_executed_block_with_number(2, blocks[0].recovered_block().hash());
+        let fork_a2 =
+            test_block_builder.get_executed_block_with_number(3, fork_a1.recovered_block().hash());
+        let fork_b1 = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork_a1, &fork_a2, &fork_b1]) {
+            tree_state.insert_executed(block.clone());
+        }
+        // block 4 extends the canonical head
+        tree_state.set_canonical_head(blocks[2].recovered_block().num_hash());
+
+        let head = blocks[3].recovered_block().hash();
+        assert_eq!(tree_state.evict_sidechains(3, head), 0);
+        assert_eq!(tree_state.block_count(), 7);
+
+        // the sidechain of the kept block survives even though it's the shortest
+        assert_eq!(tree_state.evict_sidechains(1, fork_b1.recovered_block().hash()), 2);
+        assert!(tree_state.blocks_by_hash.contains_key(&fork_b1.recovered_block().hash()));
+        assert!(!tree_state.blocks_by_hash.contains_key(&fork_a1.recovered_block().hash()));
+        assert!(!tree_state.blocks_by_hash.contains_key(&fork_a2.recovered_block().hash()));
+
+        // the canonical chain and the block extending the head are kept
+        assert_eq!(tree_state.evict_sidechains(0, head), 1);
+        assert_eq!(tree_state.block_count(), 4);
+        for block in &blocks {
+            assert!(tree_state.blocks_by_hash.contains_key(&block.recovered_block().hash()));
+        }
+    }
+
+    #[test]
+    fn test_tree_state_evict_shortest_sidechain_first() {
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..4).collect();
+
+        let fork_a1 = test_block_builder
+            .get_executed_block_with_number(2, blocks[0].recovered_block().hash());
+        let fork_a2 =
+            test_block_builder.get_executed_block_with_number(3, fork_a1.recovered_block().hash());
+        let fork_b1 = test_block_builder
+            .get_executed_block_with_number(3, blocks[1].recovered_block().hash());
+
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        for block in blocks.iter().chain([&fork_a1, &fork_a2, &fork_b1]) {
+            tree_state.insert_executed(block.clone());
+        }
+        tree_state.set_canonical_head(blocks[2].recovered_block().num_hash());
+
+        assert_eq!(tree_state.evict_sidechains(2, fork_a2.recovered_block().hash()), 1);
+        assert!(!tree_state.blocks_by_hash.contains_key(&fork_b1.recovered_block().hash()));
+        assert!(tree_state.blocks_by_hash.contains_key(&fork_a2.recovered_block().hash()));
+    }
+
     #[tokio::test]
     async fn test_tree_state_insert_executed() {
         let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
@@ -544,6 +1547,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tree_state_prune_validated_headers() {
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(1..4).collect();
+
+        for block in &blocks {
+            tree_state.insert_executed(block.clone());
+            tree_state.insert_validated_header(block.recovered_block().num_hash());
+        }
+        assert!(!tree_state.is_header_validated(B256::random()));
+
+        tree_state.prune_finalized_sidechains(blocks[1].recovered_block().num_hash());
+
+        assert!(!tree_state.is_header_validated(blocks[0].recovered_block().hash()));
+        assert!(!tree_state.is_header_validated(blocks[1].recovered_block().hash()));
+        assert!(tree_state.is_header_validated(blocks[2].recovered_block().hash()));
+
+        // removing a block from the tree forgets its header
+        tree_state.remove_with_descendants(blocks[2].recovered_block().hash());
+        assert!(!tree_state.is_header_validated(blocks[2].recovered_block().hash()));
+    }
+
+    #[test]
+    fn test_tree_state_validated_headers_bounded() {
+        let mut tree_state =
+            TreeState::<EthPrimitives>::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        let hashes = (0..=MAX_VALIDATED_HEADERS as u64)
+            .map(|number| {
+                let hash = B256::random();
+                tree_state.insert_validated_header(BlockNumHash::new(number, hash));
+                hash
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(tree_state.validated_headers.0.len(), MAX_VALIDATED_HEADERS as usize);
+        assert!(!tree_state.is_header_validated(hashes[0]));
+        assert!(tree_state.is_header_validated(hashes[hashes.len() - 1]));
+    }
+
+    #[test]
+    fn test_tree_state_remove_with_descendants() {
+        let mut tree_state = TreeState::new(BlockNumHash::default(), EngineApiKind::Ethereum);
+        let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(1..5).collect();
+
+        for block in &blocks {
+            tree_state.insert_executed(block.clone());
+        }
+
+        let removed = tree_state.remove_with_descendants(blocks[1].recovered_block().hash());
+        assert_eq!(
+            removed.iter().map(|b| b.recovered_block().hash()).collect::<Vec<_>>(),
+            blocks[1..].iter().map(|b| b.recovered_block().hash()).collect::<Vec<_>>()
+        );
+        assert_eq!(tree_state.block_count(), 1);
+        assert!(tree_state.blocks_by_hash.contains_key(&blocks[0].recovered_block().hash()));
+        assert!(!tree_state.parent_to_child.contains_key(&blocks[0].recovered_block().hash()));
+        assert!(tree_state.remove_with_descendants(B256::random()).is_empty());
+    }
+
     #[tokio::test]
     async fn test_tree_state_remove_before_lower_finalized() {
         let start_num_hash = BlockNumHash::default();
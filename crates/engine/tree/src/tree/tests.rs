@@ -7,11 +7,11 @@ use crate::{
         TreeConfig,
     },
 };
-use alloy_consensus::Header;
+use alloy_consensus::{BlockBody, Header};
 use alloy_eips::eip1898::BlockWithParent;
 use alloy_primitives::{
     map::{HashMap, HashSet},
-    Bytes, B256,
+    Bytes, Signature, B256,
 };
 use alloy_rlp::Decodable;
 use alloy_rpc_types_engine::{
@@ -20,10 +20,11 @@ use alloy_rpc_types_engine::{
 use assert_matches::assert_matches;
 use reth_chain_state::{test_utils::TestBlockBuilder, BlockState};
 use reth_chainspec::{ChainSpec, HOLESKY, MAINNET};
+use reth_consensus::test_utils::TestConsensus;
 use reth_engine_primitives::{EngineApiValidator, ForkchoiceStatus, NoopInvalidBlockHook};
 use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::EthEngineTypes;
-use reth_ethereum_primitives::{Block, EthPrimitives};
+use reth_ethereum_primitives::{Block, EthPrimitives, Receipt, Transaction, TransactionSigned};
 use reth_evm_ethereum::MockEvmConfig;
 use reth_primitives_traits::Block as _;
 use reth_provider::{test_utils::MockEthProvider, ExecutionOutcome};
@@ -254,6 +255,7 @@ impl TestHarness {
             blocks_by_number,
             current_canonical_head: blocks.last().unwrap().recovered_block().num_hash(),
             parent_to_child,
+            validated_headers: Default::default(),
             engine_kind: EngineApiKind::Ethereum,
         };
 
@@ -652,6 +654,120 @@ fn test_disconnected_block() {
     );
 }
 
+#[test]
+fn test_validate_block_speculative_does_not_buffer() {
+    let s = include_str!("../../test-data/holesky/2.rlp");
+    let data = Bytes::from_str(s).unwrap();
+    let block = Block::decode(&mut data.as_ref()).unwrap();
+    let sealed = block.seal_slow().try_recover().unwrap();
+
+    let mut test_harness = TestHarness::new(HOLESKY.clone());
+
+    let status = test_harness.tree.validate_block_speculative(sealed.clone()).unwrap();
+    assert_eq!(
+        status,
+        BlockStatus::Disconnected {
+            head: test_harness.tree.state.tree_state.current_canonical_head,
+            missing_ancestor: sealed.parent_num_hash()
+        }
+    );
+
+    // ensure the block was neither buffered nor inserted
+    assert!(test_harness.tree.state.buffer.block(&sealed.hash()).is_none());
+    assert_eq!(test_harness.tree.state.tree_state.block_count(), 0);
+}
+
+#[test]
+fn test_validate_block_speculative_valid_block() {
+    let mut test_harness = TestHarness::new(MAINNET.clone());
+    test_harness.tree.consensus = Arc::new(TestConsensus::default());
+    let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+
+    let block =
+        test_harness.block_builder.generate_random_block(3, blocks[2].recovered_block().hash());
+    test_harness.tree.evm_config.extend([ExecutionOutcome::<Receipt>::default()]);
+    test_harness.provider.add_state_root(block.state_root);
+
+    let status = test_harness.tree.validate_block_speculative(block.clone()).unwrap();
+    assert_eq!(status, BlockStatus::Valid);
+
+    // the tree is left untouched
+    assert_eq!(test_harness.tree.state.tree_state.block_count(), 3);
+    assert!(test_harness.tree.state.tree_state.executed_block_by_hash(block.hash()).is_none());
+    assert!(test_harness.tree.state.buffer.block(&block.hash()).is_none());
+    assert_eq!(
+        test_harness.tree.state.tree_state.current_canonical_head,
+        blocks[2].recovered_block().num_hash()
+    );
+}
+
+#[test]
+fn test_validate_block_speculative_invalid_block() {
+    let mut test_harness = TestHarness::new(MAINNET.clone());
+    let consensus = Arc::new(TestConsensus::default());
+    consensus.set_fail_validation(true);
+    test_harness.tree.consensus = consensus;
+    let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+
+    let block =
+        test_harness.block_builder.generate_random_block(3, blocks[2].recovered_block().hash());
+
+    let err = test_harness.tree.validate_block_speculative(block.clone()).unwrap_err();
+    assert_matches!(err.kind(), InsertBlockErrorKind::Consensus(_));
+
+    // the block is neither inserted, buffered nor recorded as invalid
+    assert_eq!(test_harness.tree.state.tree_state.block_count(), 3);
+    assert!(test_harness.tree.state.tree_state.executed_block_by_hash(block.hash()).is_none());
+    assert!(test_harness.tree.state.buffer.block(&block.hash()).is_none());
+    assert!(test_harness.tree.state.invalid_headers.get(&block.hash()).is_none());
+}
+
+#[test]
+fn test_transaction_status_prefers_database_inclusion() {
+    let mut test_harness = TestHarness::new(MAINNET.clone());
+    let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+
+    let tx = TransactionSigned::new_unhashed(
+        Transaction::Legacy(Default::default()),
+        Signature::test_signature(),
+    );
+    let tx_hash = *tx.tx_hash();
+    let body = BlockBody { transactions: vec![tx], ..Default::default() };
+
+    // the transaction is included in a sidechain block of the tree
+    let fork = Block {
+        header: Header {
+            number: 2,
+            parent_hash: blocks[1].recovered_block().hash(),
+            ..Default::default()
+        },
+        body: body.clone(),
+    };
+    let fork = RecoveredBlock::new_unhashed(fork, vec![Address::ZERO]);
+    let fork_num_hash = fork.num_hash();
+    test_harness
+        .tree
+        .state
+        .tree_state
+        .insert_executed(ExecutedBlock { recovered_block: Arc::new(fork), ..Default::default() });
+    assert_eq!(
+        test_harness.tree.transaction_status(tx_hash).unwrap(),
+        TxStatus::Sidechain { block: fork_num_hash }
+    );
+
+    // once the same transaction is canonical in the database, that inclusion takes precedence
+    let persisted = Block { header: Header { number: 1, ..Default::default() }, body };
+    let persisted_hash = persisted.header.hash_slow();
+    test_harness.provider.add_block(persisted_hash, persisted);
+    assert_eq!(
+        test_harness.tree.transaction_status(tx_hash).unwrap(),
+        TxStatus::Canonical { block: BlockNumHash::new(1, persisted_hash), index: 0 }
+    );
+}
+
 #[tokio::test]
 async fn test_holesky_payload() {
     let s = include_str!("../../test-data/holesky/1.rlp");
@@ -1247,6 +1363,32 @@ fn test_on_new_payload_backfill_buffering() {
     );
 }
 
+#[test]
+fn test_on_new_payload_known_invalid_block() {
+    reth_tracing::init_test_tracing();
+
+    let s = include_str!("../../test-data/holesky/1.rlp");
+    let data = Bytes::from_str(s).unwrap();
+    let block = Block::decode(&mut data.as_ref()).unwrap();
+    let sealed = block.seal_slow();
+    let payload = ExecutionPayloadV1::from_block_unchecked(sealed.hash(), &sealed.clone_block());
+
+    let mut test_harness = TestHarness::new(HOLESKY.clone());
+    test_harness.tree.state.invalid_headers.insert(sealed.block_with_parent());
+
+    // a payload that was already found to be invalid is rejected again without being executed
+    let outcome = test_harness
+        .tree
+        .on_new_payload(ExecutionData {
+            payload: payload.into(),
+            sidecar: ExecutionPayloadSidecar::none(),
+        })
+        .unwrap();
+    assert!(outcome.outcome.is_invalid());
+    assert!(test_harness.tree.state.buffer.block(&sealed.hash()).is_none());
+    assert_eq!(test_harness.tree.state.tree_state.block_count(), 0);
+}
+
 /// Test that captures the Engine-API rule where malformed payloads report latestValidHash = None
 #[test]
 fn test_on_new_payload_malformed_payload() {
@@ -1696,6 +1838,7 @@ mod payload_execution_tests {
 mod forkchoice_updated_tests {
     use super::*;
     use alloy_primitives::Address;
+    use reth_db::models::AccountBeforeTx;
 
     /// Test that validates the forkchoice state pre-validation logic
     #[tokio::test]
@@ -1991,4 +2134,667 @@ mod forkchoice_updated_tests {
             .unwrap();
         assert!(result.is_some(), "OpStack should handle canonical head");
     }
+
+    #[test]
+    fn test_recent_reorgs_bounded() {
+        let mut test_harness = TestHarness::new(MAINNET.clone());
+        test_harness.tree.config = test_harness.tree.config.clone().with_max_reorg_records(2);
+
+        let records: Vec<_> = (1..=3)
+            .map(|depth| ReorgRecord {
+                depth,
+                timestamp: SystemTime::UNIX_EPOCH,
+                old_tip: BlockNumHash::new(depth, B256::with_last_byte(depth as u8)),
+                new_tip: BlockNumHash::new(depth, B256::ZERO),
+            })
+            .collect();
+        for record in &records {
+            test_harness.tree.record_reorg(*record);
+        }
+
+        assert_eq!(test_harness.tree.recent_reorgs(), &records[1..]);
+    }
+
+    #[test]
+    fn test_latest_valid_ancestor() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        let valid = blocks[2].recovered_block().hash();
+        let invalid =
+            BlockWithParent { block: BlockNumHash::new(3, B256::random()), parent: valid };
+        let invalid_child = BlockWithParent {
+            block: BlockNumHash::new(4, B256::random()),
+            parent: invalid.block.hash,
+        };
+        test_harness.tree.state.invalid_headers.insert(invalid);
+        test_harness.tree.state.invalid_headers.insert(invalid_child);
+
+        assert_eq!(test_harness.tree.latest_valid_ancestor(valid).unwrap(), Some(valid));
+        assert_eq!(
+            test_harness.tree.latest_valid_ancestor(invalid_child.block.hash).unwrap(),
+            Some(valid)
+        );
+        assert_eq!(test_harness.tree.latest_valid_ancestor(B256::random()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_compute_reorg() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..4).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        let fork_2 = test_harness
+            .block_builder
+            .get_executed_block_with_number(2, blocks[1].recovered_block().hash());
+        test_harness.tree.state.tree_state.insert_executed(fork_2.clone());
+
+        let sealed = |block: &ExecutedBlock| block.recovered_block().sealed_block().clone();
+        let tip = blocks[3].recovered_block().hash();
+
+        // no-op
+        let reorg = test_harness.tree.compute_reorg(tip, tip).unwrap().unwrap();
+        assert_eq!(reorg.common_ancestor, blocks[3].recovered_block().num_hash());
+        assert!(reorg.reverted.is_empty());
+        assert!(reorg.committed.is_empty());
+
+        // reorg from the canonical tip to the fork
+        let reorg =
+            test_harness.tree.compute_reorg(tip, fork_2.recovered_block().hash()).unwrap().unwrap();
+        assert_eq!(reorg.common_ancestor, blocks[1].recovered_block().num_hash());
+        assert_eq!(reorg.reverted, vec![sealed(&blocks[3]), sealed(&blocks[2])]);
+        assert_eq!(reorg.committed, vec![sealed(&fork_2)]);
+
+        // and back
+        let reorg =
+            test_harness.tree.compute_reorg(fork_2.recovered_block().hash(), tip).unwrap().unwrap();
+        assert_eq!(reorg.reverted, vec![sealed(&fork_2)]);
+        assert_eq!(reorg.committed, vec![sealed(&blocks[2]), sealed(&blocks[3])]);
+
+        assert!(test_harness.tree.compute_reorg(tip, B256::random()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_max_blocks_per_height() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        test_harness.tree.config = test_harness.tree.config.with_max_blocks_per_height(1);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        let fork = test_harness
+            .block_builder
+            .get_executed_block_with_number(2, blocks[1].recovered_block().hash());
+        let err = test_harness.tree.insert_block(fork.recovered_block().clone()).unwrap_err();
+        let InsertPayloadError::Block(err) = err else { panic!("unexpected error: {err:?}") };
+        assert_matches!(
+            err.kind(),
+            InsertBlockErrorKind::HeightLimitExceeded { number: 2, limit: 1 }
+        );
+
+        // the rejected block is not considered invalid
+        let status = test_harness.tree.on_insert_block_error(err).unwrap();
+        assert!(status.is_syncing());
+        assert!(test_harness
+            .tree
+            .state
+            .invalid_headers
+            .get(&fork.recovered_block().hash())
+            .is_none());
+    }
+
+    #[test]
+    fn test_max_blocks_per_height_exempts_head_extension() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        test_harness.tree.config = test_harness.tree.config.with_max_blocks_per_height(1);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        // a sidechain fills the height above the canonical head
+        let fork_2 = test_harness
+            .block_builder
+            .get_executed_block_with_number(2, blocks[1].recovered_block().hash());
+        let fork_3 = test_harness
+            .block_builder
+            .get_executed_block_with_number(3, fork_2.recovered_block().hash());
+        test_harness.tree.state.tree_state.insert_executed(fork_2.clone());
+        test_harness.tree.state.tree_state.insert_executed(fork_3);
+        assert_eq!(test_harness.tree.state.tree_state.block_count_at_number(3), 1);
+
+        // the block extending the canonical head is not rejected by the limit
+        let next = test_harness
+            .block_builder
+            .get_executed_block_with_number(3, blocks[2].recovered_block().hash());
+        if let Err(InsertPayloadError::Block(err)) =
+            test_harness.tree.insert_block(next.recovered_block().clone())
+        {
+            assert!(
+                !matches!(err.kind(), InsertBlockErrorKind::HeightLimitExceeded { .. }),
+                "unexpected error: {err:?}"
+            );
+        }
+
+        // another sidechain block at that height is
+        let fork_3b = test_harness
+            .block_builder
+            .get_executed_block_with_number(3, fork_2.recovered_block().hash());
+        let err = test_harness.tree.insert_block(fork_3b.recovered_block().clone()).unwrap_err();
+        let InsertPayloadError::Block(err) = err else { panic!("unexpected error: {err:?}") };
+        assert_matches!(err.kind(), InsertBlockErrorKind::HeightLimitExceeded { number: 3, .. });
+    }
+
+    #[test]
+    fn test_insert_known_invalid_block() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        let invalid = test_harness
+            .block_builder
+            .get_executed_block_with_number(3, blocks[2].recovered_block().hash());
+        let invalid = invalid.recovered_block().clone();
+        test_harness.tree.state.invalid_headers.insert(invalid.block_with_parent());
+
+        // a re-delivered invalid block is rejected without being executed
+        assert!(test_harness.tree.on_downloaded_block(invalid.clone()).unwrap().is_none());
+        assert!(test_harness
+            .tree
+            .state
+            .tree_state
+            .executed_block_by_hash(invalid.hash())
+            .is_none());
+
+        // and so is its child, which is recorded as invalid as well
+        let child = test_harness.block_builder.get_executed_block_with_number(4, invalid.hash());
+        let child = child.recovered_block().clone();
+        assert!(test_harness.tree.on_downloaded_block(child.clone()).unwrap().is_none());
+        assert_eq!(
+            test_harness.tree.state.invalid_headers.get(&child.hash()),
+            Some(invalid.block_with_parent())
+        );
+        assert!(test_harness.tree.state.tree_state.executed_block_by_hash(child.hash()).is_none());
+    }
+
+    #[test]
+    fn test_is_canonical() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..4).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        let fork = test_harness
+            .block_builder
+            .get_executed_block_with_number(3, blocks[2].recovered_block().hash());
+        test_harness.tree.state.tree_state.insert_executed(fork.clone());
+
+        for block in &blocks {
+            assert!(test_harness.tree.is_canonical(block.recovered_block().hash()).unwrap());
+        }
+        assert!(!test_harness.tree.is_canonical(fork.recovered_block().hash()).unwrap());
+        assert!(!test_harness.tree.is_canonical(B256::random()).unwrap());
+
+        // blocks that are no longer held in memory are resolved through the database
+        test_harness.tree.state.tree_state.remove_until(
+            blocks[2].recovered_block().num_hash(),
+            blocks[2].recovered_block().hash(),
+            None,
+        );
+        assert!(test_harness.tree.is_canonical(blocks[0].recovered_block().hash()).unwrap());
+    }
+
+    #[test]
+    fn test_touched_accounts_since() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+
+        // replaces the changes of the block with a change of the given account
+        let touching = |mut block: ExecutedBlock, address: Address| {
+            let number = block.recovered_block().number;
+            let bundle = revm::database::BundleState::builder(number..=number)
+                .state_present_account_info(address, Default::default())
+                .build();
+            block.execution_output =
+                Arc::new(ExecutionOutcome::new(bundle, vec![vec![]], number, Vec::new()));
+            block
+        };
+        // every block changes the account with the address of its number
+        let blocks: Vec<_> = test_harness
+            .block_builder
+            .get_executed_blocks(0..4)
+            .map(|block| {
+                let address = Address::with_last_byte(block.recovered_block().number as u8);
+                touching(block, address)
+            })
+            .collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        let touched = |test_harness: &TestHarness, base: B256| {
+            let mut touched = test_harness
+                .tree
+                .touched_accounts_since(base)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>();
+            touched.sort();
+            touched
+        };
+
+        assert_eq!(
+            touched(&test_harness, blocks[1].recovered_block().hash()),
+            vec![Address::with_last_byte(2), Address::with_last_byte(3)]
+        );
+        assert!(touched(&test_harness, blocks[3].recovered_block().hash()).is_empty());
+
+        // the changes of persisted blocks are read from the database changesets
+        test_harness
+            .tree
+            .canonical_in_memory_state
+            .remove_persisted_blocks(blocks[1].recovered_block().num_hash());
+        assert!(test_harness.tree.canonical_in_memory_state.state_by_number(1).is_none());
+        test_harness.provider.add_account_changeset(
+            1,
+            vec![AccountBeforeTx { address: Address::with_last_byte(1), info: None }],
+        );
+        assert_eq!(
+            touched(&test_harness, blocks[0].recovered_block().hash()),
+            (1..=3).map(Address::with_last_byte).collect::<Vec<_>>()
+        );
+
+        // a block that was reorged out also includes the changes that were reverted
+        let fork = touching(
+            test_harness
+                .block_builder
+                .get_executed_block_with_number(3, blocks[2].recovered_block().hash()),
+            Address::with_last_byte(9),
+        );
+        test_harness.tree.state.tree_state.insert_executed(fork.clone());
+        assert_eq!(
+            touched(&test_harness, fork.recovered_block().hash()),
+            vec![Address::with_last_byte(3), Address::with_last_byte(9)]
+        );
+
+        assert_matches!(
+            test_harness.tree.touched_accounts_since(B256::random()),
+            Err(TreeError::Provider(ProviderError::BlockHashNotFound(_)))
+        );
+    }
+
+    #[test]
+    fn test_missing_range() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..4).collect();
+        test_harness = test_harness.with_blocks(blocks);
+
+        assert_eq!(test_harness.tree.missing_range(10), Some(4..=10));
+        assert_eq!(test_harness.tree.missing_range(4), Some(4..=4));
+        // the head advertised by the peer can't overflow the range
+        assert_eq!(test_harness.tree.missing_range(u64::MAX), Some(4..=u64::MAX));
+        assert_eq!(test_harness.tree.missing_range(3), None);
+        assert_eq!(test_harness.tree.missing_range(1), None);
+    }
+
+    #[test]
+    fn test_canonical_blocks_iter() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..4).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        let hashes = |from| {
+            test_harness
+                .tree
+                .canonical_blocks_iter(from)
+                .map(|block| block.unwrap().hash())
+                .collect::<Vec<_>>()
+        };
+        let expected =
+            blocks.iter().map(|block| block.recovered_block().hash()).collect::<Vec<_>>();
+        assert_eq!(hashes(0), expected);
+        assert_eq!(hashes(2), expected[2..]);
+        assert!(hashes(4).is_empty());
+
+        // the iterator can be stopped early
+        let first = test_harness.tree.canonical_blocks_iter(1).next().unwrap().unwrap();
+        assert_eq!(first.hash(), expected[1]);
+    }
+
+    #[test]
+    fn test_reorged_out_block_is_validated_again() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        let reorged = blocks[2].recovered_block().clone();
+        test_harness.tree.state.tree_state.insert_validated_header(reorged.num_hash());
+        assert!(test_harness.tree.state.tree_state.is_header_validated(reorged.hash()));
+
+        // reorg the block out
+        let fork = test_harness
+            .block_builder
+            .get_executed_block_with_number(2, blocks[1].recovered_block().hash());
+        test_harness.tree.state.tree_state.insert_executed(fork.clone());
+        test_harness.tree.make_canonical(fork.recovered_block().hash()).unwrap();
+        assert!(test_harness.tree.state.tree_state.is_canonical(fork.recovered_block().hash()));
+        assert!(!test_harness.tree.state.tree_state.is_header_validated(reorged.hash()));
+
+        // once invalidated, re-delivery is rejected
+        test_harness.tree.state.tree_state.insert_validated_header(reorged.num_hash());
+        let (_, invalidated) = test_harness.tree.mark_invalid(reorged.hash()).unwrap();
+        assert_eq!(invalidated, vec![reorged.hash()]);
+        assert!(!test_harness.tree.state.tree_state.is_header_validated(reorged.hash()));
+        assert!(test_harness.tree.state.invalid_headers.get(&reorged.hash()).is_some());
+
+        // re-inserting the block through the tree rejects it instead of trusting it
+        assert!(test_harness.tree.on_downloaded_block(reorged.clone()).unwrap().is_none());
+        assert!(test_harness
+            .tree
+            .state
+            .tree_state
+            .executed_block_by_hash(reorged.hash())
+            .is_none());
+        assert!(!test_harness.tree.state.tree_state.is_header_validated(reorged.hash()));
+        assert!(test_harness.tree.state.buffer.block(&reorged.hash()).is_none());
+    }
+
+    #[test]
+    fn test_malformed_chain_update_is_rejected() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..4).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+        let head = blocks[3].recovered_block().num_hash();
+
+        assert_matches!(
+            test_harness.tree.on_canonical_chain_update(NewCanonicalChain::Commit { new: vec![] }),
+            Err(NewCanonicalChainError::EmptyNewChain)
+        );
+
+        // a gap between the new blocks
+        let block4 = test_harness
+            .block_builder
+            .get_executed_block_with_number(4, blocks[3].recovered_block().hash());
+        let block6 = test_harness
+            .block_builder
+            .get_executed_block_with_number(6, block4.recovered_block().hash());
+        assert_matches!(
+            test_harness
+                .tree
+                .on_canonical_chain_update(NewCanonicalChain::Commit { new: vec![block4, block6] }),
+            Err(NewCanonicalChainError::NewChainNotContiguous(_))
+        );
+
+        // the tree is left untouched
+        assert_eq!(test_harness.tree.state.tree_state.canonical_head(), &head);
+        assert_eq!(
+            test_harness.tree.canonical_in_memory_state.get_canonical_head().num_hash(),
+            head
+        );
+    }
+
+    #[test]
+    fn test_unwind_to() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..5).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+        test_harness
+            .tree
+            .canonical_in_memory_state
+            .set_finalized(blocks[1].recovered_block().clone_sealed_header());
+
+        assert_matches!(
+            test_harness.tree.unwind_to(0),
+            Err(TreeError::FinalizedRegression { target: 0, finalized: 1 })
+        );
+
+        // nothing to unwind
+        assert!(test_harness.tree.unwind_to(4).unwrap().is_empty());
+
+        let chain = test_harness.tree.unwind_to(2).unwrap();
+        assert_eq!(chain.range(), 3..=4);
+        assert_eq!(chain.tip().hash(), blocks[4].recovered_block().hash());
+
+        let head = blocks[2].recovered_block().num_hash();
+        assert_eq!(test_harness.tree.state.tree_state.canonical_head(), &head);
+        assert_eq!(
+            test_harness.tree.canonical_in_memory_state.get_canonical_head().num_hash(),
+            head
+        );
+
+        // the unwound blocks are kept as a sidechain
+        for block in &blocks[3..] {
+            let hash = block.recovered_block().hash();
+            assert!(test_harness.tree.state.tree_state.executed_block_by_hash(hash).is_some());
+            assert!(!test_harness.tree.state.tree_state.is_canonical(hash));
+        }
+    }
+
+    #[test]
+    fn test_unwind_to_missing_block() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..5).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+        test_harness
+            .tree
+            .state
+            .tree_state
+            .blocks_by_hash
+            .remove(&blocks[3].recovered_block().hash());
+
+        assert_matches!(test_harness.tree.unwind_to(2), Err(TreeError::MissingBlock(3)));
+        // the tree is left as is
+        assert_eq!(
+            test_harness.tree.state.tree_state.canonical_head(),
+            &blocks[4].recovered_block().num_hash()
+        );
+    }
+
+    #[test]
+    fn test_connect_buffered_blocks_keeps_orphans() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+        test_harness = test_harness.with_blocks(blocks);
+
+        let orphan = test_harness.block_builder.get_executed_block_with_number(6, B256::random());
+        let orphan = orphan.recovered_block().clone();
+        test_harness.tree.state.buffer.insert_block(orphan.clone());
+
+        // the parent is unknown, so the block stays buffered no matter how often this is called
+        for _ in 0..2 {
+            assert!(test_harness.tree.connect_buffered_blocks().unwrap().is_empty());
+            assert!(test_harness.tree.state.buffer.block(&orphan.hash()).is_some());
+        }
+    }
+
+    #[test]
+    fn test_connect_buffered_blocks_with_known_parent() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..4).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        // a block that was buffered before it and its parent arrived, e.g. through backfill
+        let buffered = blocks[3].recovered_block().clone();
+        test_harness.tree.state.buffer.insert_block(buffered.clone());
+
+        let outcomes = test_harness.tree.connect_buffered_blocks().unwrap();
+        assert_eq!(
+            outcomes,
+            vec![(buffered.num_hash(), InsertPayloadOk::AlreadySeen(BlockStatus::Valid))]
+        );
+        assert!(test_harness.tree.state.buffer.block(&buffered.hash()).is_none());
+
+        // nothing is left to connect
+        assert!(test_harness.tree.connect_buffered_blocks().unwrap().is_empty());
+
+        // a buffered child connects once its parent is inserted, the random blocks are only
+        // checked by the test consensus and their state root is computed synchronously
+        let consensus = Arc::new(TestConsensus::default());
+        test_harness.tree.consensus = consensus.clone();
+        test_harness.tree.payload_validator = BasicEngineValidator::new(
+            test_harness.provider.clone(),
+            consensus,
+            test_harness.tree.evm_config.clone(),
+            MockEngineValidator,
+            TreeConfig::default().with_state_root_fallback(true),
+            Box::new(NoopInvalidBlockHook::default()),
+        );
+        let parent = test_harness
+            .block_builder
+            .get_executed_block_with_number(4, blocks[3].recovered_block().hash());
+        let child =
+            test_harness.block_builder.generate_random_block(5, parent.recovered_block().hash());
+        test_harness.tree.state.buffer.insert_block(child.clone());
+        test_harness.tree.state.tree_state.insert_executed(parent);
+
+        test_harness.tree.evm_config.extend([ExecutionOutcome::<Receipt>::default()]);
+        test_harness.provider.add_state_root(child.state_root);
+        let outcomes = test_harness.tree.connect_buffered_blocks().unwrap();
+        assert_eq!(
+            outcomes,
+            vec![(child.num_hash(), InsertPayloadOk::Inserted(BlockStatus::Valid))]
+        );
+        assert!(test_harness.tree.state.buffer.block(&child.hash()).is_none());
+        assert!(test_harness.tree.state.tree_state.executed_block_by_hash(child.hash()).is_some());
+    }
+
+    #[test]
+    fn test_out_of_order_blocks_are_not_fragmented() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        // blocks are indexed by their parent rather than grouped into chains, so inserting a
+        // branch in reverse order leaves nothing to merge afterwards
+        let mut parent = blocks[2].recovered_block().hash();
+        let branch: Vec<_> = (3..7)
+            .map(|number| {
+                let block =
+                    test_harness.block_builder.get_executed_block_with_number(number, parent);
+                parent = block.recovered_block().hash();
+                block
+            })
+            .collect();
+        for block in branch.iter().rev() {
+            test_harness.tree.state.tree_state.insert_executed(block.clone());
+        }
+
+        let tip = branch.last().unwrap().recovered_block().num_hash();
+        assert_eq!(test_harness.tree.tips(), vec![tip]);
+        for block in &branch {
+            let hash = block.recovered_block().hash();
+            assert!(test_harness.tree.state.tree_state.executed_block_by_hash(hash).is_some());
+        }
+    }
+
+    #[test]
+    fn test_insert_disconnected_executed_block() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        let disconnected =
+            test_harness.block_builder.get_executed_block_with_number(5, B256::random());
+        test_harness
+            .tree
+            .on_engine_message(FromEngine::Request(EngineApiRequest::InsertExecutedBlock(
+                disconnected.clone(),
+            )))
+            .unwrap();
+        let hash = disconnected.recovered_block().hash();
+        assert!(test_harness.tree.state.tree_state.executed_block_by_hash(hash).is_none());
+        assert_eq!(
+            test_harness
+                .tree
+                .ensure_connected(disconnected.recovered_block().block_with_parent())
+                .unwrap(),
+            Err(DisconnectedBlockError(disconnected.recovered_block().block_with_parent()))
+        );
+
+        let connected = test_harness
+            .block_builder
+            .get_executed_block_with_number(3, blocks[2].recovered_block().hash());
+        test_harness
+            .tree
+            .on_engine_message(FromEngine::Request(EngineApiRequest::InsertExecutedBlock(
+                connected.clone(),
+            )))
+            .unwrap();
+        let hash = connected.recovered_block().hash();
+        assert!(test_harness.tree.state.tree_state.executed_block_by_hash(hash).is_some());
+    }
+
+    #[test]
+    fn test_validate_and_rebuild_canonical_index() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..3).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+
+        assert!(test_harness.tree.validate_canonical_index().unwrap().is_empty());
+        assert!(test_harness.tree.rebuild_canonical_index().unwrap().is_empty());
+        assert_eq!(
+            test_harness.tree.state.tree_state.canonical_block_hash(),
+            blocks[2].recovered_block().hash()
+        );
+
+        // replace block 1 in the database with a different block at the same height
+        let replaced = blocks[1].recovered_block();
+        let mut header = replaced.header().clone();
+        header.extra_data = Bytes::from_static(b"diverged");
+        {
+            let mut headers = test_harness.provider.headers.lock();
+            headers.remove(&replaced.hash());
+            headers.insert(B256::random(), header);
+        }
+
+        assert_eq!(test_harness.tree.validate_canonical_index().unwrap(), vec![1]);
+
+        // the rebuild is refused while blocks are being persisted
+        let (_tx, rx) = oneshot::channel();
+        test_harness.tree.persistence_state.start_save(blocks[2].recovered_block().num_hash(), rx);
+        assert_matches!(
+            test_harness.tree.rebuild_canonical_index(),
+            Err(TreeError::PersistenceInProgress)
+        );
+        test_harness
+            .tree
+            .persistence_state
+            .finish(blocks[0].recovered_block().hash(), blocks[0].recovered_block().number);
+
+        assert_eq!(test_harness.tree.rebuild_canonical_index().unwrap(), vec![1]);
+
+        // the divergent block and its descendants are removed from the tree
+        for block in &blocks[1..] {
+            assert!(test_harness
+                .tree
+                .state
+                .tree_state
+                .executed_block_by_hash(block.recovered_block().hash())
+                .is_none());
+        }
+        assert!(test_harness
+            .tree
+            .state
+            .tree_state
+            .executed_block_by_hash(blocks[0].recovered_block().hash())
+            .is_some());
+
+        assert_eq!(
+            *test_harness.tree.state.tree_state.canonical_head(),
+            blocks[2].recovered_block().num_hash()
+        );
+        assert!(test_harness.tree.validate_canonical_index().unwrap().is_empty());
+    }
 }
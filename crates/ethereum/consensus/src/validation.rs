@@ -5,7 +5,8 @@ use alloy_primitives::{Bloom, Bytes, B256};
 use reth_chainspec::EthereumHardforks;
 use reth_consensus::ConsensusError;
 use reth_primitives_traits::{
-    receipt::gas_spent_by_transactions, Block, GotExpected, Receipt, RecoveredBlock,
+    receipt::{aggregate_bloom, gas_spent_by_transactions},
+    Block, GotExpected, Receipt, RecoveredBlock,
 };
 
 /// Validate a block with regard to execution results:
@@ -80,7 +81,7 @@ fn verify_receipts<R: Receipt>(
     let receipts_root = calculate_receipt_root(&receipts_with_bloom);
 
     // Calculate header logs bloom.
-    let logs_bloom = receipts_with_bloom.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom_ref());
+    let logs_bloom = aggregate_bloom(&receipts_with_bloom);
 
     compare_receipts_root_and_logs_bloom(
         receipts_root,
@@ -118,7 +119,7 @@ fn compare_receipts_root_and_logs_bloom(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::{b256, hex};
+    use alloy_primitives::{b256, hex, Address, Log};
     use reth_ethereum_primitives::Receipt;
 
     #[test]
@@ -147,6 +148,38 @@ mod tests {
         assert!(verify_receipts(expected_receipts_root, expected_logs_bloom, &receipts).is_err());
     }
 
+    #[test]
+    fn test_verify_receipts_logs_bloom() {
+        let log = |address: u8, topic: u8| {
+            Log::new_unchecked(
+                Address::with_last_byte(address),
+                vec![B256::with_last_byte(topic)],
+                Bytes::default(),
+            )
+        };
+        let receipts: Vec<Receipt> = vec![
+            Receipt { logs: vec![log(1, 1), log(2, 2)], ..Default::default() },
+            Receipt::default(),
+            Receipt { logs: vec![log(3, 3), log(3, 4), log(4, 5)], ..Default::default() },
+        ];
+        let receipts_root = calculate_receipt_root(
+            &receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>(),
+        );
+
+        let logs_bloom = aggregate_bloom(&receipts);
+        assert_eq!(verify_receipts(receipts_root, logs_bloom, &receipts), Ok(()));
+
+        // bloom missing the logs of the last receipt
+        let partial_bloom = aggregate_bloom(&receipts[..2]);
+        assert_ne!(partial_bloom, logs_bloom);
+        assert_eq!(
+            verify_receipts(receipts_root, partial_bloom, &receipts),
+            Err(ConsensusError::BodyBloomLogDiff(
+                GotExpected { got: logs_bloom, expected: partial_bloom }.into()
+            ))
+        );
+    }
+
     #[test]
     fn test_compare_receipts_root_and_logs_bloom_success() {
         let calculated_receipts_root = B256::random();
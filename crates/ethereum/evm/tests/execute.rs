@@ -9,26 +9,35 @@ use alloy_eips::{
     eip7685::EMPTY_REQUESTS_HASH,
 };
 use alloy_evm::block::BlockValidationError;
-use alloy_primitives::{b256, fixed_bytes, keccak256, Bytes, TxKind, B256, U256};
+use alloy_primitives::{b256, fixed_bytes, keccak256, Address, Bytes, TxKind, B256, U256};
 use reth_chainspec::{ChainSpecBuilder, EthereumHardfork, ForkCondition, MAINNET};
 use reth_ethereum_primitives::{Block, BlockBody, Transaction};
 use reth_evm::{
-    execute::{BasicBlockExecutor, Executor},
-    ConfigureEvm,
+    execute::{
+        BasicBlockExecutor, BlockBuilder, BlockExecutionError, ExecutionStrategy, Executor,
+        ParallelExecutionStats, StateRootMismatch, StateRootValidation,
+    },
+    ConfigureEvm, NextBlockEnvAttributes,
 };
 use reth_evm_ethereum::EthEvmConfig;
 use reth_execution_types::BlockExecutionResult;
 use reth_primitives_traits::{
-    crypto::secp256k1::public_key_to_address, Block as _, RecoveredBlock,
+    crypto::secp256k1::public_key_to_address, Account, Block as _, RecoveredBlock, SealedHeader,
+    SignerRecoverable,
 };
+use reth_revm::{database::StateProviderDatabase, test_utils::StateProviderTest};
+use reth_storage_api::{noop::NoopProvider, HashedPostStateProvider, StateRootProvider};
 use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
 use revm::{
-    database::{CacheDB, EmptyDB, TransitionState},
+    database::{CacheDB, EmptyDB, State, TransitionState},
     primitives::address,
     state::{AccountInfo, Bytecode, EvmState},
     Database,
 };
-use std::sync::{mpsc, Arc};
+use std::{
+    collections::BTreeSet,
+    sync::{mpsc, Arc},
+};
 
 fn create_database_with_beacon_root_contract() -> CacheDB<EmptyDB> {
     let mut db = CacheDB::new(Default::default());
@@ -826,3 +835,254 @@ fn test_balance_increment_not_duplicated() {
         );
     }
 }
+
+#[test]
+fn parallel_execution_matches_sequential() {
+    let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    let mut rng = generators::rng();
+    let keys: Vec<_> = (0..4).map(|_| generators::generate_key(&mut rng)).collect();
+    let senders: Vec<_> = keys.iter().map(|key| public_key_to_address(key.public_key())).collect();
+    for sender in &senders {
+        db.insert_account_info(
+            *sender,
+            AccountInfo { balance: U256::from(ETH_TO_WEI), ..Default::default() },
+        );
+    }
+
+    let beneficiary = address!("0x2000000000000000000000000000000000000000");
+    let mut header = chain_spec.genesis_header().clone();
+    header.gas_limit = 1_000_000;
+    header.beneficiary = beneficiary;
+    // every transaction pays a priority fee to the beneficiary
+    let gas_price = header.base_fee_per_gas.unwrap() + 1_000_000_000;
+
+    let transfer = |key, nonce, to, value| {
+        sign_tx_with_key_pair(
+            key,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce,
+                gas_price: gas_price.into(),
+                gas_limit: 21_000,
+                to: TxKind::Call(to),
+                value: U256::from(value),
+                input: Bytes::new(),
+            }),
+        )
+    };
+    let transactions = vec![
+        // independent transfers
+        transfer(keys[0], 0, address!("0x1000000000000000000000000000000000000001"), 1),
+        transfer(keys[1], 0, address!("0x1000000000000000000000000000000000000002"), 2),
+        // depends on the first transfer through the sender's nonce and balance
+        transfer(keys[0], 1, address!("0x1000000000000000000000000000000000000003"), 3),
+        // reads the balance of a sender of a preceding transfer
+        transfer(keys[2], 0, senders[0], 4),
+        // reads the balance of the beneficiary, which preceding transfers paid fees to
+        transfer(keys[3], 0, beneficiary, 5),
+    ];
+    let block = Block { header, body: BlockBody { transactions, ..Default::default() } }
+        .try_into_recovered()
+        .unwrap();
+
+    let execute = |strategy| {
+        let mut executor =
+            BasicBlockExecutor::new(EthEvmConfig::new(chain_spec.clone()), db.clone());
+        let result = executor.execute_one_with_strategy(&block, strategy).unwrap();
+        let stats = executor.parallel_stats();
+        ((result, executor.into_state().take_bundle()), stats)
+    };
+
+    let (sequential, stats) = execute(ExecutionStrategy::Sequential);
+    assert_eq!(stats, ParallelExecutionStats::default());
+    assert_eq!(sequential.0.receipts.len(), 5);
+    assert!(sequential.0.receipts.iter().all(|receipt| receipt.success));
+
+    for workers in [1, 2, 4] {
+        let (parallel, stats) = execute(ExecutionStrategy::Parallel { workers });
+        assert_eq!(parallel, sequential);
+        // only the two independent transfers are committed, the fee payments to the beneficiary
+        // don't conflict
+        assert_eq!(stats, ParallelExecutionStats { committed: 2, reexecuted: 3 });
+    }
+}
+
+#[test]
+fn builder_skips_invalid_transactions() {
+    let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    let mut rng = generators::rng();
+    let keys: Vec<_> = (0..2).map(|_| generators::generate_key(&mut rng)).collect();
+    for key in &keys {
+        db.insert_account_info(
+            public_key_to_address(key.public_key()),
+            AccountInfo { balance: U256::from(ETH_TO_WEI), ..Default::default() },
+        );
+    }
+
+    let parent = SealedHeader::seal_slow(chain_spec.genesis_header().clone());
+    let gas_price = parent.base_fee_per_gas.unwrap() + 1_000_000_000;
+    let transfer = |key, nonce, gas_limit| {
+        sign_tx_with_key_pair(
+            key,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce,
+                gas_price: gas_price.into(),
+                gas_limit,
+                to: TxKind::Call(address!("0x1000000000000000000000000000000000000001")),
+                value: U256::from(1),
+                input: Bytes::new(),
+            }),
+        )
+        .try_into_recovered()
+        .unwrap()
+    };
+    let transactions = vec![
+        transfer(keys[0], 0, 21_000),
+        // nonce too high
+        transfer(keys[0], 2, 21_000),
+        // doesn't fit into the block gas limit
+        transfer(keys[1], 0, 2_000_000),
+        transfer(keys[1], 0, 21_000),
+    ];
+
+    let evm_config = EthEvmConfig::new(chain_spec);
+    let mut state = State::builder().with_database(db.clone()).with_bundle_update().build();
+    let mut builder = evm_config
+        .builder_for_next_block(
+            &mut state,
+            &parent,
+            NextBlockEnvAttributes {
+                timestamp: parent.timestamp + 12,
+                suggested_fee_recipient: address!("0x2000000000000000000000000000000000000000"),
+                prev_randao: B256::ZERO,
+                gas_limit: 1_000_000,
+                parent_beacon_block_root: None,
+                withdrawals: Some(Default::default()),
+            },
+        )
+        .unwrap();
+    builder.apply_pre_execution_changes().unwrap();
+    let skipped = builder.execute_transactions_skip_invalid(transactions.clone()).unwrap();
+    assert_eq!(skipped, vec![transactions[1].clone(), transactions[2].clone()]);
+
+    let outcome = builder.finish(NoopProvider::default()).unwrap();
+    assert_eq!(
+        outcome.block.body().transactions,
+        vec![transactions[0].clone_inner(), transactions[3].clone_inner()]
+    );
+
+    // the built block executes to the same result
+    let result = BasicBlockExecutor::new(evm_config, db).execute_one(&outcome.block).unwrap();
+    assert_eq!(result, outcome.execution_result);
+    assert_eq!(result.gas_used, outcome.block.gas_used);
+}
+
+#[test]
+fn batch_state_root_validation() {
+    let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+    let evm_config = EthEvmConfig::new(chain_spec.clone());
+
+    let key = generators::generate_key(&mut generators::rng());
+    let mut state = StateProviderTest::default();
+    state.insert_account(
+        public_key_to_address(key.public_key()),
+        Account { balance: U256::from(ETH_TO_WEI), ..Default::default() },
+        None,
+        Default::default(),
+    );
+
+    let mut blocks: Vec<_> = (0..3)
+        .map(|nonce| {
+            let mut header = chain_spec.genesis_header().clone();
+            header.number = nonce + 1;
+            header.gas_limit = 1_000_000;
+            let tx = sign_tx_with_key_pair(
+                key,
+                Transaction::Legacy(TxLegacy {
+                    chain_id: Some(chain_spec.chain.id()),
+                    nonce,
+                    gas_price: header.base_fee_per_gas.unwrap().into(),
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::with_last_byte(nonce as u8 + 1)),
+                    value: U256::from(1),
+                    input: Bytes::new(),
+                }),
+            );
+            Block { header, body: BlockBody { transactions: vec![tx], ..Default::default() } }
+        })
+        .collect();
+    // set the state roots computed over the whole state after each block
+    for number in 1..=blocks.len() {
+        let recovered: Vec<_> = blocks[..number]
+            .iter()
+            .map(|block| block.clone().try_into_recovered().unwrap())
+            .collect();
+        let outcome = evm_config
+            .batch_executor(StateProviderDatabase::new(&state))
+            .execute_batch(&recovered)
+            .unwrap();
+        let hashed_state = state.hashed_post_state(&outcome.bundle);
+        blocks[number - 1].header.state_root = state.state_root(hashed_state).unwrap();
+    }
+
+    let execute = |blocks: &[Block], validation| {
+        let blocks: Vec<_> =
+            blocks.iter().map(|block| block.clone().try_into_recovered().unwrap()).collect();
+        BasicBlockExecutor::new(&evm_config, StateProviderDatabase::new(&state))
+            .execute_batch_with_state_root_validation(&blocks, &state, &validation)
+    };
+    let mismatch = |err: BlockExecutionError| {
+        let BlockExecutionError::Internal(err) = err else { panic!("unexpected error: {err:?}") };
+        err.downcast_other::<StateRootMismatch>().unwrap().range.clone()
+    };
+
+    // matching roots are accepted in every mode, with the same outcome as without validation
+    let expected = evm_config
+        .batch_executor(StateProviderDatabase::new(&state))
+        .execute_batch(
+            &blocks
+                .iter()
+                .map(|block| block.clone().try_into_recovered().unwrap())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+    for validation in [
+        StateRootValidation::EveryBlock,
+        StateRootValidation::Checkpoints(BTreeSet::from([2])),
+        StateRootValidation::end_of_batch(),
+    ] {
+        assert_eq!(execute(&blocks, validation).unwrap(), expected);
+    }
+
+    // a wrong root in the middle of the batch is only detected if that block is validated
+    let mut invalid = blocks.clone();
+    invalid[1].header.state_root = B256::random();
+    assert_eq!(mismatch(execute(&invalid, StateRootValidation::EveryBlock).unwrap_err()), 2..=2);
+    assert_eq!(
+        mismatch(
+            execute(&invalid, StateRootValidation::Checkpoints(BTreeSet::from([2]))).unwrap_err()
+        ),
+        1..=2
+    );
+    assert!(execute(&invalid, StateRootValidation::end_of_batch()).is_ok());
+
+    // the last block is always validated, the mismatch covers the blocks since the last checkpoint
+    let mut invalid = blocks;
+    invalid[2].header.state_root = B256::random();
+    assert_eq!(mismatch(execute(&invalid, StateRootValidation::EveryBlock).unwrap_err()), 3..=3);
+    assert_eq!(
+        mismatch(
+            execute(&invalid, StateRootValidation::Checkpoints(BTreeSet::from([1]))).unwrap_err()
+        ),
+        2..=3
+    );
+    assert_eq!(
+        mismatch(execute(&invalid, StateRootValidation::end_of_batch()).unwrap_err()),
+        1..=3
+    );
+}
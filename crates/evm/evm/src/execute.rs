@@ -1,7 +1,7 @@
 //! Traits for execution.
 
 use crate::{ConfigureEvm, Database, OnStateHook, TxEnvFor};
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
 use alloy_consensus::{BlockHeader, Header};
 use alloy_eips::eip2718::WithEncoded;
 pub use alloy_evm::block::{BlockExecutor, BlockExecutorFactory};
@@ -9,18 +9,20 @@ use alloy_evm::{
     block::{CommitChanges, ExecutableTx},
     Evm, EvmEnv, EvmFactory, RecoveredTx, ToTxEnv,
 };
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{Address, BlockNumber, B256};
+use core::ops::RangeInclusive;
 pub use reth_execution_errors::{
     BlockExecutionError, BlockValidationError, InternalBlockExecutionError,
 };
 use reth_execution_types::BlockExecutionResult;
 pub use reth_execution_types::{BlockExecutionOutput, ExecutionOutcome};
 use reth_primitives_traits::{
-    Block, HeaderTy, NodePrimitives, ReceiptTy, Recovered, RecoveredBlock, SealedHeader, TxTy,
+    Block, GotExpected, HeaderTy, NodePrimitives, ReceiptTy, Recovered, RecoveredBlock,
+    SealedHeader, TxTy,
 };
 use reth_storage_api::StateProvider;
 pub use reth_storage_errors::provider::ProviderError;
-use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use reth_trie_common::{updates::TrieUpdates, HashedPostState, TrieInput};
 use revm::{
     context::result::ExecutionResult,
     database::{states::bundle_state::BundleRetention, BundleState, State},
@@ -369,6 +371,29 @@ pub trait BlockBuilder {
         self.execute_transaction_with_result_closure(tx, |_| ())
     }
 
+    /// Executes the given transactions in order and saves them in internal state, skipping the
+    /// ones that are invalid in the current state.
+    ///
+    /// A transaction is invalid if it fails with a [`BlockExecutionError::Validation`] error, e.g.
+    /// because of a wrong nonce or because it doesn't fit into the remaining block gas. Reverted
+    /// transactions are not invalid and are included.
+    ///
+    /// Returns the skipped transactions.
+    fn execute_transactions_skip_invalid(
+        &mut self,
+        transactions: impl IntoIterator<Item = Recovered<TxTy<Self::Primitives>>>,
+    ) -> Result<Vec<Recovered<TxTy<Self::Primitives>>>, BlockExecutionError> {
+        let mut skipped = Vec::new();
+        for tx in transactions {
+            match self.execute_transaction(tx.clone()) {
+                Ok(_) => {}
+                Err(BlockExecutionError::Validation(_)) => skipped.push(tx),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(skipped)
+    }
+
     /// Completes the block building process and returns the [`BlockBuilderOutcome`].
     fn finish(
         self,
@@ -551,6 +576,81 @@ where
     }
 }
 
+/// How the transactions of a block are executed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExecutionStrategy {
+    /// Execute transactions one after another.
+    #[default]
+    Sequential,
+    /// Speculatively execute transactions concurrently on the given number of worker threads
+    /// against the state before the block.
+    ///
+    /// Transactions are then committed in order. A transaction that read an account or storage
+    /// slot changed by a preceding transaction is executed again on top of the updated state, so
+    /// the result is always identical to [`Self::Sequential`]. Fee payments to the block
+    /// beneficiary don't count as reads. Blocks with many independent transactions benefit the
+    /// most.
+    Parallel {
+        /// The number of worker threads.
+        workers: usize,
+    },
+}
+
+/// Counts of how the transactions of blocks executed with [`ExecutionStrategy::Parallel`] were
+/// committed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParallelExecutionStats {
+    /// The number of transactions whose speculative result was committed.
+    pub committed: usize,
+    /// The number of transactions that were executed again because of a conflict with a
+    /// preceding transaction.
+    pub reexecuted: usize,
+}
+
+/// When the state root is validated while executing a batch of blocks.
+///
+/// See [`BasicBlockExecutor::execute_batch_with_state_root_validation`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StateRootValidation {
+    /// Validate the state root after every block.
+    #[default]
+    EveryBlock,
+    /// Validate the state root only after the given blocks and after the last block of the batch.
+    ///
+    /// Computing the state root is expensive, so this speeds up executing trusted blocks, e.g.
+    /// during initial sync with already verified headers. In exchange, a mismatch can only be
+    /// attributed to the range of blocks executed since the previous checkpoint.
+    Checkpoints(BTreeSet<BlockNumber>),
+}
+
+impl StateRootValidation {
+    /// Validates the state root only after the last block of the batch.
+    pub const fn end_of_batch() -> Self {
+        Self::Checkpoints(BTreeSet::new())
+    }
+
+    /// Returns whether the state root is validated after the block with the given number.
+    pub fn is_checkpoint(&self, number: BlockNumber) -> bool {
+        match self {
+            Self::EveryBlock => true,
+            Self::Checkpoints(checkpoints) => checkpoints.contains(&number),
+        }
+    }
+}
+
+/// The state root after executing a range of blocks doesn't match the state root of the last
+/// block of the range.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+#[display("state root mismatch after executing blocks {}..={}: {root}", range.start(), range.end())]
+pub struct StateRootMismatch {
+    /// The blocks executed since the state root was last validated, one of them is invalid.
+    #[error(not(source))]
+    pub range: RangeInclusive<BlockNumber>,
+    /// The computed and the expected state root.
+    #[error(not(source))]
+    pub root: GotExpected<B256>,
+}
+
 /// A generic block executor that uses a [`BlockExecutor`] to
 /// execute blocks.
 #[expect(missing_debug_implementations)]
@@ -559,6 +659,8 @@ pub struct BasicBlockExecutor<F, DB> {
     pub(crate) strategy_factory: F,
     /// Database.
     pub(crate) db: State<DB>,
+    /// Counts of the transactions executed with [`ExecutionStrategy::Parallel`].
+    pub(crate) parallel_stats: ParallelExecutionStats,
 }
 
 impl<F, DB: Database> BasicBlockExecutor<F, DB> {
@@ -566,7 +668,98 @@ impl<F, DB: Database> BasicBlockExecutor<F, DB> {
     pub fn new(strategy_factory: F, db: DB) -> Self {
         let db =
             State::builder().with_database(db).with_bundle_update().without_state_clear().build();
-        Self { strategy_factory, db }
+        Self { strategy_factory, db, parallel_stats: Default::default() }
+    }
+
+    /// Returns the counts of the transactions executed with [`ExecutionStrategy::Parallel`] so
+    /// far.
+    pub const fn parallel_stats(&self) -> ParallelExecutionStats {
+        self.parallel_stats
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F, DB> BasicBlockExecutor<F, DB>
+where
+    F: ConfigureEvm,
+    DB: Database + revm::DatabaseRef<Error = <DB as revm::Database>::Error> + Sync,
+{
+    /// Executes a single block with the given [`ExecutionStrategy`].
+    ///
+    /// See [`Executor::execute_one`].
+    pub fn execute_one_with_strategy(
+        &mut self,
+        block: &RecoveredBlock<<F::Primitives as NodePrimitives>::Block>,
+        strategy: ExecutionStrategy,
+    ) -> Result<BlockExecutionResult<<F::Primitives as NodePrimitives>::Receipt>, BlockExecutionError>
+    {
+        match strategy {
+            ExecutionStrategy::Sequential => self.execute_one(block),
+            ExecutionStrategy::Parallel { workers } => self.execute_one_parallel(block, workers),
+        }
+    }
+}
+
+impl<F, DB> BasicBlockExecutor<F, DB>
+where
+    F: ConfigureEvm,
+    DB: Database,
+{
+    /// Executes the blocks like [`Executor::execute_batch`] and validates the resulting state root
+    /// against the block headers according to the given [`StateRootValidation`].
+    ///
+    /// The state provider must provide the state before the first block. If a state root doesn't
+    /// match, a [`StateRootMismatch`] with the blocks executed since the state root was last
+    /// validated is returned.
+    ///
+    /// Only the changes since the previous validation are hashed for a state root, and the trie
+    /// nodes computed for it are reused, so validating every block doesn't recompute the state
+    /// root of all preceding blocks of the batch.
+    pub fn execute_batch_with_state_root_validation<'a, I>(
+        mut self,
+        blocks: I,
+        state: impl StateProvider,
+        validation: &StateRootValidation,
+    ) -> Result<ExecutionOutcome<<F::Primitives as NodePrimitives>::Receipt>, BlockExecutionError>
+    where
+        I: IntoIterator<Item = &'a RecoveredBlock<<F::Primitives as NodePrimitives>::Block>>,
+    {
+        let mut blocks = blocks.into_iter().peekable();
+        let mut results = Vec::new();
+        let mut first_block = None;
+        let mut unvalidated_from = None;
+        // the changes of the validated blocks
+        let mut bundle = BundleState::default();
+        // the hashed state of the validated blocks and the trie nodes of their state root
+        let mut trie_input = TrieInput::default();
+        while let Some(block) = blocks.next() {
+            let number = block.header().number();
+            first_block.get_or_insert(number);
+            let from = *unvalidated_from.get_or_insert(number);
+            results.push(self.execute_one(block)?);
+
+            if blocks.peek().is_none() || validation.is_checkpoint(number) {
+                // the bundle state contains the changes since the previous validation
+                let changes = self.db.take_bundle();
+                trie_input.append(state.hashed_post_state(&changes));
+                let (state_root, trie_updates) = state
+                    .state_root_from_nodes_with_updates(trie_input.clone())
+                    .map_err(BlockExecutionError::other)?;
+                let expected = block.header().state_root();
+                if state_root != expected {
+                    return Err(BlockExecutionError::other(StateRootMismatch {
+                        range: from..=number,
+                        root: GotExpected::new(state_root, expected),
+                    }))
+                }
+                trie_input.nodes.extend(trie_updates);
+                trie_input.prefix_sets.clear();
+                bundle.extend(changes);
+                unvalidated_from = None;
+            }
+        }
+
+        Ok(ExecutionOutcome::from_blocks(first_block.unwrap_or_default(), bundle, results))
     }
 }
 
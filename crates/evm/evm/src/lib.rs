@@ -47,6 +47,9 @@ pub use aliases::*;
 mod engine;
 pub use engine::{ConfigureEngineEvm, ExecutableTxIterator};
 
+#[cfg(feature = "std")]
+mod parallel;
+
 #[cfg(feature = "metrics")]
 pub mod metrics;
 pub mod noop;
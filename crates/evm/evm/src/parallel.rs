@@ -0,0 +1,302 @@
+//! Speculative parallel execution of the transactions of a block.
+//!
+//! Transactions are first executed concurrently against the state before the block, recording
+//! every account and storage slot they read. They are then committed in block order: if none of
+//! the recorded values was changed by the pre-execution changes or a preceding transaction, the
+//! speculative result is identical to what sequential execution would produce and is committed
+//! as is. Otherwise, the transaction is executed again on top of the current state.
+//!
+//! Every transaction pays its fees to the block beneficiary, so the beneficiary is left out of the
+//! conflict detection if the transaction only loaded it to pay the fees. The fees are then added
+//! to the current balance of the beneficiary when committing.
+
+use crate::{execute::BasicBlockExecutor, ConfigureEvm, Database, Evm};
+use alloc::vec::Vec;
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_evm::{block::BlockExecutor, ToTxEnv};
+use alloy_primitives::{map::HashMap, Address, B256, U256};
+use reth_execution_errors::BlockExecutionError;
+use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock};
+use revm::{
+    context::{result::ResultAndState, ContextTr},
+    database::{states::bundle_state::BundleRetention, State},
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    state::{AccountInfo, Bytecode},
+    DatabaseRef, Inspector,
+};
+
+/// A database that records all account and storage reads of a transaction.
+#[derive(Debug)]
+struct ReadRecorder<'a, DB> {
+    db: &'a DB,
+    beneficiary: Address,
+    /// Whether the transaction finished executing, set by the [`ExecutionTracker`].
+    executed: bool,
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    storage: HashMap<(Address, U256), U256>,
+    /// The beneficiary account, if it was only loaded after executing the transaction to pay the
+    /// fees.
+    fee_beneficiary: Option<Option<AccountInfo>>,
+}
+
+impl<'a, DB> ReadRecorder<'a, DB> {
+    fn new(db: &'a DB, beneficiary: Address) -> Self {
+        Self {
+            db,
+            beneficiary,
+            executed: false,
+            accounts: Default::default(),
+            storage: Default::default(),
+            fee_beneficiary: None,
+        }
+    }
+}
+
+impl<DB: DatabaseRef> revm::Database for ReadRecorder<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic_ref(address)?;
+        if self.executed && address == self.beneficiary {
+            self.fee_beneficiary = Some(info.clone());
+        } else {
+            self.accounts.insert(address, info.clone());
+        }
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        // code is immutable, so it's not part of the read set
+        self.db.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage_ref(address, index)?;
+        self.storage.insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.db.block_hash_ref(number)
+    }
+}
+
+/// An inspector that marks the end of the execution of a transaction in the [`ReadRecorder`], so
+/// that it can tell the fee payment apart from reads of the transaction itself.
+#[derive(Debug, Default)]
+struct ExecutionTracker {
+    depth: usize,
+}
+
+impl ExecutionTracker {
+    const fn frame_end<DB>(&mut self, recorder: &mut ReadRecorder<'_, DB>) {
+        self.depth -= 1;
+        if self.depth == 0 {
+            recorder.executed = true;
+        }
+    }
+}
+
+impl<'a, DB: 'a, CTX> Inspector<CTX> for ExecutionTracker
+where
+    CTX: ContextTr<Db = ReadRecorder<'a, DB>>,
+{
+    fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.depth += 1;
+        None
+    }
+
+    fn call_end(&mut self, context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        self.frame_end(context.db_mut());
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.depth += 1;
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut CTX,
+        _inputs: &CreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end(context.db_mut());
+    }
+}
+
+/// The result of a speculatively executed transaction and the values it was based on.
+struct Speculative<H> {
+    output: ResultAndState<H>,
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    storage: HashMap<(Address, U256), U256>,
+    fee_beneficiary: Option<Option<AccountInfo>>,
+}
+
+impl<H> Speculative<H> {
+    /// Returns whether all values read during speculative execution still match the given state.
+    ///
+    /// The beneficiary is only compared if it was read by the transaction itself.
+    fn is_valid<DB: Database>(&self, state: &mut State<DB>) -> Result<bool, DB::Error> {
+        use revm::Database as _;
+
+        for (address, info) in &self.accounts {
+            if state.basic(*address)? != *info {
+                return Ok(false)
+            }
+        }
+        for ((address, index), value) in &self.storage {
+            if state.storage(*address, *index)? != *value {
+                return Ok(false)
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns the output of the transaction on top of the given state.
+    ///
+    /// If the beneficiary was only loaded to pay the fees, the fees are added to its current
+    /// balance instead of the balance it had before the block. Returns `None` if the beneficiary
+    /// was removed since.
+    fn into_output<DB: Database>(
+        mut self,
+        state: &mut State<DB>,
+        beneficiary: Address,
+    ) -> Result<Option<ResultAndState<H>>, DB::Error> {
+        use revm::Database as _;
+
+        let Some(read) = self.fee_beneficiary else { return Ok(Some(self.output)) };
+        let current = state.basic(beneficiary)?;
+        if current == read {
+            return Ok(Some(self.output))
+        }
+        let Some(mut info) = current else { return Ok(None) };
+
+        if let Some(account) = self.output.state.get_mut(&beneficiary) {
+            let fees =
+                account.info.balance.saturating_sub(read.map_or(U256::ZERO, |read| read.balance));
+            info.balance = info.balance.saturating_add(fees);
+            account.info = info;
+        }
+        Ok(Some(self.output))
+    }
+}
+
+impl<F, DB> BasicBlockExecutor<F, DB>
+where
+    F: ConfigureEvm,
+    DB: Database + DatabaseRef<Error = <DB as revm::Database>::Error> + Sync,
+{
+    /// Executes the block, speculatively executing its transactions on `workers` threads first.
+    ///
+    /// Produces the same result and state as sequential execution. The number of committed and
+    /// re-executed transactions is added to the executor's
+    /// [`ParallelExecutionStats`](crate::execute::ParallelExecutionStats).
+    pub(crate) fn execute_one_parallel(
+        &mut self,
+        block: &RecoveredBlock<<F::Primitives as NodePrimitives>::Block>,
+        workers: usize,
+    ) -> Result<BlockExecutionResult<<F::Primitives as NodePrimitives>::Receipt>, BlockExecutionError>
+    {
+        let transactions = block.transactions_recovered().collect::<Vec<_>>();
+        let workers = workers.clamp(1, transactions.len().max(1));
+
+        // execute all transactions against the state before the block, worker `n` takes every
+        // `workers`-th transaction starting at `n`
+        let mut speculative = Vec::with_capacity(transactions.len());
+        speculative.resize_with(transactions.len(), || None);
+        let beneficiary = block.header().beneficiary();
+        let state = &self.db;
+        let evm_config = &self.strategy_factory;
+        let transactions_ref = &transactions;
+        std::thread::scope(|scope| {
+            let handles = (0..workers)
+                .map(|worker| {
+                    scope.spawn(move || {
+                        let mut results = Vec::new();
+                        let Ok(evm_env) = evm_config.evm_env(block.header()) else {
+                            return results
+                        };
+                        let mut evm = evm_config.evm_with_env_and_inspector(
+                            ReadRecorder::new(state, beneficiary),
+                            evm_env,
+                            ExecutionTracker::default(),
+                        );
+                        for (index, tx) in
+                            transactions_ref.iter().enumerate().skip(worker).step_by(workers)
+                        {
+                            evm.db_mut().executed = false;
+                            // failed transactions are executed again sequentially, which
+                            // surfaces the error if it persists. Their reads stay in the recorder
+                            // and are attributed to the next transaction, which can only cause a
+                            // needless re-execution of it
+                            let Ok(output) = evm.transact_raw(tx.to_tx_env()) else { continue };
+                            let db = evm.db_mut();
+                            results.push((
+                                index,
+                                Speculative {
+                                    output,
+                                    accounts: core::mem::take(&mut db.accounts),
+                                    storage: core::mem::take(&mut db.storage),
+                                    fee_beneficiary: db.fee_beneficiary.take(),
+                                },
+                            ));
+                        }
+                        results
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            // if a worker panicked, its transactions are executed sequentially instead
+            for results in handles.into_iter().filter_map(|handle| handle.join().ok()) {
+                for (index, result) in results {
+                    speculative[index] = Some(result);
+                }
+            }
+        });
+
+        let gas_limit = block.header().gas_limit();
+        let mut gas_used = 0;
+        let mut executor = self
+            .strategy_factory
+            .executor_for_block(&mut self.db, block)
+            .map_err(BlockExecutionError::other)?;
+        executor.apply_pre_execution_changes()?;
+
+        for (tx, speculative) in transactions.into_iter().zip(speculative) {
+            // the block gas limit check is part of the execution, so leave it to the executor
+            let fits = tx.gas_limit() <= gas_limit.saturating_sub(gas_used);
+            let valid = match speculative {
+                Some(speculative) if fits => {
+                    let state = &mut **executor.evm_mut().db_mut();
+                    if speculative.is_valid(state).map_err(BlockExecutionError::other)? {
+                        speculative
+                            .into_output(state, beneficiary)
+                            .map_err(BlockExecutionError::other)?
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            };
+
+            gas_used += match valid {
+                Some(output) => {
+                    self.parallel_stats.committed += 1;
+                    executor.commit_transaction(output, tx)?
+                }
+                None => {
+                    self.parallel_stats.reexecuted += 1;
+                    executor.execute_transaction(tx)?
+                }
+            };
+        }
+
+        let result = executor.apply_post_execution_changes()?;
+
+        self.db.merge_transitions(BundleRetention::Reverts);
+
+        Ok(result)
+    }
+}
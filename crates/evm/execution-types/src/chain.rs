@@ -2,13 +2,13 @@
 
 use crate::ExecutionOutcome;
 use alloc::{borrow::Cow, collections::BTreeMap, vec::Vec};
-use alloy_consensus::{transaction::Recovered, BlockHeader};
+use alloy_consensus::{transaction::Recovered, BlockHeader, ReceiptWithBloom, TxReceipt};
 use alloy_eips::{eip1898::ForkBlock, eip2718::Encodable2718, BlockNumHash};
-use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash};
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber};
 use core::{fmt, ops::RangeInclusive};
 use reth_primitives_traits::{
-    transaction::signed::SignedTransaction, Block, BlockBody, NodePrimitives, RecoveredBlock,
-    SealedHeader,
+    transaction::signed::SignedTransaction, Block, BlockBody, GotExpected, NodePrimitives,
+    RecoveredBlock, SealedBlock, SealedHeader,
 };
 use reth_trie_common::updates::TrieUpdates;
 use revm::database::BundleState;
@@ -78,16 +78,69 @@ impl<N: NodePrimitives> Chain<N> {
         Self::new([block], execution_outcome, trie_updates)
     }
 
+    /// Creates a chain from the given blocks with an empty execution outcome, e.g. for tests
+    /// that only exercise the chain structure.
+    ///
+    /// Returns `None` if the chain is empty or the blocks are not contiguous, i.e. if any block
+    /// does not directly follow the previous block by number and parent hash.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn from_blocks(blocks: impl IntoIterator<Item = RecoveredBlock<N::Block>>) -> Option<Self> {
+        let blocks = blocks.into_iter().collect::<Vec<_>>();
+        let first_block = blocks.first()?.number();
+        let is_contiguous = blocks.windows(2).all(|pair| {
+            pair[1].number() == pair[0].number() + 1 && pair[1].parent_hash() == pair[0].hash()
+        });
+        if !is_contiguous {
+            return None
+        }
+
+        let execution_outcome = ExecutionOutcome {
+            first_block,
+            receipts: blocks.iter().map(|_| Vec::new()).collect(),
+            ..Default::default()
+        };
+        Some(Self::new(blocks, execution_outcome, None))
+    }
+
     /// Get the blocks in this chain.
     pub const fn blocks(&self) -> &BTreeMap<BlockNumber, RecoveredBlock<N::Block>> {
         &self.blocks
     }
 
+    /// Returns a summary of the chain for display, see [`DisplayChainSummary`].
+    pub const fn display_summary(&self) -> DisplayChainSummary<'_, N> {
+        DisplayChainSummary(self)
+    }
+
     /// Consumes the type and only returns the blocks in this chain.
     pub fn into_blocks(self) -> BTreeMap<BlockNumber, RecoveredBlock<N::Block>> {
         self.blocks
     }
 
+    /// Consumes the chain and returns its blocks with increasing block numbers, each with the
+    /// number of its first transaction and its senders.
+    ///
+    /// Transaction numbers are assigned sequentially starting at `first_tx_id`, which is the
+    /// number of the first transaction of the first block in this chain.
+    ///
+    /// The senders are the ones recovered when the blocks were added to the chain, so no sender
+    /// recovery is performed.
+    pub fn into_write_batch(
+        self,
+        first_tx_id: TxNumber,
+    ) -> Vec<(SealedBlock<N::Block>, TxNumber, Vec<Address>)> {
+        let mut next_tx_id = first_tx_id;
+        self.blocks
+            .into_values()
+            .map(|block| {
+                let start_tx_id = next_tx_id;
+                next_tx_id += block.body().transaction_count() as TxNumber;
+                let (block, senders) = block.split_sealed();
+                (block, start_tx_id, senders)
+            })
+            .collect()
+    }
+
     /// Returns an iterator over all headers in the block with increasing block numbers.
     pub fn headers(&self) -> impl Iterator<Item = SealedHeader<N::BlockHeader>> + '_ {
         self.blocks.values().map(|block| block.clone_sealed_header())
@@ -219,6 +272,11 @@ impl<N: NodePrimitives> Chain<N> {
         self.blocks.len()
     }
 
+    /// Returns the number of block changesets in the execution outcome of the chain.
+    pub fn changesets_len(&self) -> usize {
+        self.execution_outcome.bundle.reverts.len()
+    }
+
     /// Returns the range of block numbers in the chain.
     ///
     /// # Panics
@@ -228,12 +286,41 @@ impl<N: NodePrimitives> Chain<N> {
         self.first().header().number()..=self.tip().header().number()
     }
 
+    /// Returns the total number of transactions in all blocks of the chain.
+    pub fn total_transactions(&self) -> usize {
+        self.blocks.values().map(|block| block.body().transaction_count()).sum()
+    }
+
+    /// Returns the total gas used by all blocks of the chain, taken from the cumulative gas used
+    /// of the last receipt of each block.
+    ///
+    /// This is only accurate once the blocks have been executed and their receipts are part of
+    /// the execution outcome.
+    pub fn cumulative_gas_used(&self) -> u64 {
+        self.execution_outcome
+            .receipts
+            .iter()
+            .filter_map(|receipts| receipts.last())
+            .map(|receipt| receipt.cumulative_gas_used())
+            .sum()
+    }
+
     /// Get all receipts for the given block.
     pub fn receipts_by_block_hash(&self, block_hash: BlockHash) -> Option<Vec<&N::Receipt>> {
         let num = self.block_number(block_hash)?;
         Some(self.execution_outcome.receipts_by_block(num).iter().collect())
     }
 
+    /// Returns the receipts of every block in the chain together with their logs bloom, one inner
+    /// vec per block.
+    ///
+    /// The cumulative gas used of the receipts starts from zero at each block.
+    pub fn receipts_with_bloom(&self) -> Vec<Vec<ReceiptWithBloom<&N::Receipt>>> {
+        self.block_receipts_iter()
+            .map(|receipts| receipts.iter().map(TxReceipt::with_bloom_ref).collect())
+            .collect()
+    }
+
     /// Get all receipts with attachment.
     ///
     /// Attachment includes block number, block hash, transaction hash and transaction index.
@@ -276,12 +363,33 @@ impl<N: NodePrimitives> Chain<N> {
         self.trie_updates.take(); // reset
     }
 
+    /// Append a single block with state to the chain, see [`Chain::append_block`].
+    ///
+    /// Returns an error if the block number does not directly follow the chain tip.
+    pub fn try_append_block(
+        &mut self,
+        block: RecoveredBlock<N::Block>,
+        execution_outcome: ExecutionOutcome<N::Receipt>,
+    ) -> Result<(), GotExpected<BlockNumber>> {
+        if let Some((tip_number, _)) = self.blocks.last_key_value() {
+            let expected = tip_number + 1;
+            let number = block.header().number();
+            if number != expected {
+                return Err(GotExpected { got: number, expected })
+            }
+        }
+
+        self.append_block(block, execution_outcome);
+        Ok(())
+    }
+
     /// Merge two chains by appending the given chain into the current one.
     ///
     /// The state of accounts for this chain is set to the state of the newest chain.
     ///
     /// Returns the passed `other` chain in [`Result::Err`] variant if the chains could not be
-    /// connected.
+    /// connected, either because `other` does not fork off the tip of this chain or because its
+    /// first block number does not directly follow the tip.
     pub fn append_chain(&mut self, other: Self) -> Result<(), Self> {
         let chain_tip = self.tip();
         let other_fork_block = other.fork_block();
@@ -289,6 +397,12 @@ impl<N: NodePrimitives> Chain<N> {
             return Err(other)
         }
 
+        let tip_number = self.blocks.last_key_value().map(|(number, _)| *number);
+        let first_number = other.blocks.first_key_value().map(|(number, _)| *number);
+        if first_number != tip_number.map(|number| number + 1) {
+            return Err(other)
+        }
+
         // Insert blocks from other chain
         self.blocks.extend(other.blocks);
         self.execution_outcome.extend(other.execution_outcome);
@@ -296,6 +410,40 @@ impl<N: NodePrimitives> Chain<N> {
 
         Ok(())
     }
+
+    /// Drops the oldest blocks of the chain together with their receipts, requests and reverts,
+    /// keeping only the most recent `keep_last` blocks. The [fork block](Self::fork_block) then
+    /// becomes the parent of the new first block.
+    ///
+    /// The state is not replayed: the plain state of the bundle still contains the changes of the
+    /// dropped blocks, so the chain no longer describes the state on top of the new fork block by
+    /// itself. This must only be used once the dropped blocks are persisted or below the
+    /// finalized block, so the state at the new fork block can be read from the database.
+    ///
+    /// The trie updates, which cover the full original range, are cleared if any block is dropped.
+    ///
+    /// Does nothing if the chain has at most `keep_last` blocks.
+    ///
+    /// # Panics
+    ///
+    /// If `keep_last` is zero.
+    pub fn truncate_front(&mut self, keep_last: usize) {
+        assert!(keep_last > 0, "chain should keep at least one block");
+        let Some(drop) = self.blocks.len().checked_sub(keep_last).filter(|drop| *drop > 0) else {
+            return
+        };
+
+        let new_first = *self.blocks.keys().nth(drop).expect("chain has more than `drop` blocks");
+        self.blocks = self.blocks.split_off(&new_first);
+
+        let outcome = &mut self.execution_outcome;
+        let drop = drop.min(outcome.receipts.len());
+        outcome.receipts.drain(..drop);
+        outcome.requests.drain(..drop.min(outcome.requests.len()));
+        outcome.bundle.take_n_reverts(drop);
+        outcome.first_block = new_first;
+        self.trie_updates.take(); // reset
+    }
 }
 
 /// Wrapper type for `blocks` display in `Chain`
@@ -319,6 +467,24 @@ impl<B: reth_primitives_traits::Block> fmt::Display for DisplayBlocksChain<'_, B
     }
 }
 
+/// Wrapper type that displays the block and changeset counts, the first and the tip block of a
+/// `Chain`, without its blocks and state.
+#[derive(Debug)]
+pub struct DisplayChainSummary<'a, N: NodePrimitives>(pub &'a Chain<N>);
+
+impl<N: NodePrimitives> fmt::Display for DisplayChainSummary<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let blocks = &self.0.blocks;
+        f.debug_struct("Chain")
+            .field("len", &self.0.len())
+            .field("changesets", &self.0.changesets_len())
+            .field("first", &blocks.values().next().map(|block| block.num_hash()))
+            .field("tip", &blocks.values().next_back().map(|block| block.num_hash()))
+            .field("has_trie_updates", &self.0.trie_updates.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
 /// All blocks in the chain
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct ChainBlocks<'a, B: Block> {
@@ -627,6 +793,157 @@ mod tests {
 
         // chain1 got changed so this will fail
         assert!(chain1.append_chain(chain2).is_err());
+
+        // a chain that connects by hash but skips a block number is rejected
+        let mut block6: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block6.set_parent_hash(block4_hash);
+        let chain3 = Chain { blocks: BTreeMap::from([(6, block6)]), ..Default::default() };
+        assert!(chain1.append_chain(chain3).is_err());
+    }
+
+    #[test]
+    fn chain_truncate_front() {
+        let mut blocks = Vec::new();
+        let mut outcome = ExecutionOutcome::default();
+        for number in 1..=4u8 {
+            let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+            block.set_block_number(number as u64);
+            block.set_hash(B256::new([number; 32]));
+            block.set_parent_hash(B256::new([number - 1; 32]));
+            blocks.push(block);
+
+            let address = Address::new([number; 20]);
+            outcome.extend(ExecutionOutcome::new(
+                BundleState::new(
+                    vec![(address, None, Some(AccountInfo::default()), HashMap::default())],
+                    vec![vec![(address, None, vec![])]],
+                    vec![],
+                ),
+                vec![vec![Receipt { cumulative_gas_used: number as u64, ..Default::default() }]],
+                number as u64,
+                vec![],
+            ));
+        }
+        let mut chain: Chain = Chain::new(blocks, outcome, Some(TrieUpdates::default()));
+
+        // keeping more blocks than the chain has is a no-op
+        chain.truncate_front(10);
+        assert_eq!(chain.range(), 1..=4);
+        assert!(chain.trie_updates().is_some());
+
+        chain.truncate_front(2);
+        assert_eq!(chain.range(), 3..=4);
+        assert_eq!(chain.fork_block(), ForkBlock { number: 2, hash: B256::new([2; 32]) });
+        assert_eq!(chain.execution_outcome().first_block, 3);
+        assert_eq!(chain.execution_outcome().receipts.len(), 2);
+        assert_eq!(chain.execution_outcome().bundle.reverts.len(), 2);
+        assert_eq!(chain.cumulative_gas_used(), 3 + 4);
+        assert!(chain.trie_updates().is_none());
+        // plain state still contains the changes of the dropped blocks
+        assert!(chain.execution_outcome().bundle.account(&Address::new([1; 20])).is_some());
+    }
+
+    #[test]
+    fn append_block_number_gap() {
+        let mut block1: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block1.set_block_number(1);
+        let mut block2 = block1.clone();
+        block2.set_block_number(2);
+        let mut block4 = block1.clone();
+        block4.set_block_number(4);
+
+        let mut chain: Chain = Chain::default();
+        assert!(chain.try_append_block(block1, ExecutionOutcome::default()).is_ok());
+        assert!(chain.try_append_block(block2, ExecutionOutcome::default()).is_ok());
+        assert_eq!(
+            chain.try_append_block(block4, ExecutionOutcome::default()),
+            Err(GotExpected { got: 4, expected: 3 })
+        );
+        assert_eq!(chain.range(), 1..=2);
+    }
+
+    #[test]
+    fn chain_display_summary() {
+        let mut block1: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block1.set_block_number(1);
+        let mut block2 = block1.clone();
+        block2.set_block_number(2);
+        block2.set_hash(B256::new([0x02; 32]));
+
+        let execution_outcome = ExecutionOutcome {
+            bundle: BundleState {
+                reverts: revm::database::states::reverts::Reverts::new(vec![vec![], vec![]]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let chain: Chain = Chain {
+            blocks: BTreeMap::from([(1, block1), (2, block2)]),
+            execution_outcome,
+            ..Default::default()
+        };
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain.changesets_len(), 2);
+        let summary = chain.display_summary().to_string();
+        assert!(summary.contains("len: 2"));
+        assert!(summary.contains("changesets: 2"));
+        assert!(summary.contains(&format!("{:?}", B256::new([0x02; 32]))));
+        assert!(!summary.contains("execution_outcome"));
+        assert!(format!("{chain:?}").contains("execution_outcome"));
+
+        let empty: Chain = Chain::default();
+        assert_eq!(
+            empty.display_summary().to_string(),
+            "Chain { len: 0, changesets: 0, first: None, tip: None, has_trie_updates: false, .. }"
+        );
+    }
+
+    #[test]
+    fn chain_into_write_batch() {
+        let mut block1: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block1.set_block_number(1);
+        let tx = reth_ethereum_primitives::TransactionSigned::new_unhashed(
+            reth_ethereum_primitives::Transaction::Legacy(Default::default()),
+            alloy_primitives::Signature::test_signature(),
+        );
+        block1.block_mut().transactions.extend([tx.clone(), tx]);
+        block1.senders_mut().extend([Address::new([0x01; 20]), Address::new([0x02; 20])]);
+        let mut block2: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block2.set_block_number(2);
+
+        let chain: Chain = Chain {
+            blocks: BTreeMap::from([(1, block1.clone()), (2, block2.clone())]),
+            ..Default::default()
+        };
+        let batch = chain.into_write_batch(10);
+
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch[0], (block1.clone_sealed_block(), 10, block1.senders().to_vec()));
+        assert_eq!(batch[1], (block2.clone_sealed_block(), 12, Vec::new()));
+    }
+
+    #[test]
+    fn chain_from_blocks() {
+        let mut block1: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block1.set_block_number(1);
+        let mut block2: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block2.set_block_number(2);
+        block2.set_parent_hash(block1.hash());
+
+        let chain: Chain = Chain::from_blocks([block1.clone(), block2.clone()]).unwrap();
+        assert_eq!(chain.range(), 1..=2);
+        assert_eq!(chain.execution_outcome().first_block(), 1);
+        assert_eq!(chain.execution_outcome().receipts().len(), 2);
+
+        // empty chain
+        assert!(<Chain>::from_blocks([]).is_none());
+        // gap in block numbers
+        let mut block3 = block2.clone();
+        block3.set_block_number(3);
+        assert!(<Chain>::from_blocks([block1.clone(), block3]).is_none());
+        // parent hash mismatch
+        block2.set_parent_hash(B256::new([0x01; 32]));
+        assert!(<Chain>::from_blocks([block1, block2]).is_none());
     }
 
     #[test]
@@ -760,4 +1077,84 @@ mod tests {
         // Assert that the execution outcome at the tip block contains the whole execution outcome
         assert_eq!(chain.execution_outcome_at_block(11), Some(execution_outcome));
     }
+
+    #[test]
+    fn receipts_with_bloom() {
+        let log = alloy_primitives::Log::new_unchecked(
+            Address::new([0x11; 20]),
+            vec![B256::new([0x22; 32])],
+            Default::default(),
+        );
+        let receipt = |cumulative_gas_used, logs| Receipt {
+            tx_type: TxType::Legacy,
+            cumulative_gas_used,
+            logs,
+            success: true,
+        };
+        let receipts = vec![
+            vec![receipt(21_000, vec![log.clone()]), receipt(42_000, vec![])],
+            vec![receipt(21_000, vec![])],
+        ];
+
+        let chain: Chain = Chain {
+            blocks: BTreeMap::from([(10, Default::default()), (11, Default::default())]),
+            execution_outcome: ExecutionOutcome {
+                receipts: receipts.clone(),
+                first_block: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let with_bloom = chain.receipts_with_bloom();
+        assert_eq!(with_bloom.len(), 2);
+        assert_eq!(with_bloom[0][0].receipt, &receipts[0][0]);
+        assert_eq!(with_bloom[0][0].logs_bloom, alloy_primitives::logs_bloom([&log]));
+        assert_eq!(with_bloom[0][1].logs_bloom, alloy_primitives::Bloom::ZERO);
+        assert_eq!(with_bloom[1][0].receipt.cumulative_gas_used, 21_000);
+    }
+
+    #[test]
+    fn total_transactions_and_gas_used() {
+        let tx = reth_ethereum_primitives::TransactionSigned::Legacy(
+            alloy_consensus::Signed::new_unchecked(
+                Default::default(),
+                alloy_primitives::Signature::test_signature(),
+                B256::ZERO,
+            ),
+        );
+        let block = |number, tx_count| {
+            let mut block = reth_ethereum_primitives::Block::default();
+            block.header.number = number;
+            block.body.transactions = vec![tx.clone(); tx_count];
+            RecoveredBlock::new_unhashed(block, vec![Address::ZERO; tx_count])
+        };
+        let receipt = |cumulative_gas_used| Receipt {
+            tx_type: TxType::Legacy,
+            cumulative_gas_used,
+            logs: vec![],
+            success: true,
+        };
+
+        let chain: Chain = Chain {
+            blocks: BTreeMap::from([(10, block(10, 2)), (11, block(11, 0)), (12, block(12, 1))]),
+            execution_outcome: ExecutionOutcome {
+                receipts: vec![
+                    vec![receipt(21_000), receipt(63_000)],
+                    vec![],
+                    vec![receipt(50_000)],
+                ],
+                first_block: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(chain.total_transactions(), 3);
+        assert_eq!(chain.cumulative_gas_used(), 113_000);
+        assert_eq!(
+            Chain::<reth_ethereum_primitives::EthPrimitives>::default().cumulative_gas_used(),
+            0
+        );
+    }
 }
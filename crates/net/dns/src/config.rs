@@ -28,6 +28,14 @@ pub struct DnsDiscoveryConfig {
     pub dns_record_cache_limit: NonZeroU32,
     /// Links to the DNS networks to bootstrap.
     pub bootstrap_dns_networks: Option<HashSet<LinkEntry>>,
+    /// Whether network startup should fail if the DNS resolver can't be configured.
+    ///
+    /// If `false`, DNS discovery is disabled instead and the node relies on its other peer
+    /// sources.
+    ///
+    /// Default: false
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub required: bool,
 }
 
 impl Default for DnsDiscoveryConfig {
@@ -38,6 +46,7 @@ impl Default for DnsDiscoveryConfig {
             recheck_interval: Duration::from_secs(60 * 30),
             dns_record_cache_limit: NonZeroU32::new(1_000).unwrap(),
             bootstrap_dns_networks: Some(Default::default()),
+            required: false,
         }
     }
 }
@@ -134,6 +134,7 @@ impl<R: Resolver> DnsDiscoveryService<R> {
             recheck_interval,
             dns_record_cache_limit,
             bootstrap_dns_networks,
+            required: _,
         } = config;
         let queries = QueryPool::new(resolver, max_requests_per_sec, lookup_timeout);
         let (command_tx, command_rx) = mpsc::unbounded_channel();
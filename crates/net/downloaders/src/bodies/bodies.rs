@@ -19,7 +19,7 @@ use reth_storage_api::HeaderProvider;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use std::{
     cmp::Ordering,
-    collections::BinaryHeap,
+    collections::{BTreeMap, BinaryHeap},
     fmt::Debug,
     mem,
     ops::RangeInclusive,
@@ -55,6 +55,8 @@ pub struct BodiesDownloader<
     max_buffered_blocks_size_bytes: usize,
     /// Current estimated size of buffered blocks in bytes.
     buffered_blocks_size_bytes: usize,
+    /// Maximum number of responses to buffer internally.
+    max_buffered_responses: usize,
     /// The range of block numbers for body download.
     download_range: RangeInclusive<BlockNumber>,
     /// The latest block number returned.
@@ -63,6 +65,8 @@ pub struct BodiesDownloader<
     in_progress_queue: BodiesRequestQueue<B, C>,
     /// Buffered responses
     buffered_responses: BinaryHeap<OrderedBodiesResponse<B>>,
+    /// Requests whose responses were dropped because the buffer was full, by first block number.
+    shed_requests: BTreeMap<BlockNumber, Vec<SealedHeader<B::Header>>>,
     /// Queued body responses that can be returned for insertion into the database.
     queued_bodies: Vec<BlockResponse<B>>,
     /// The bodies downloader metrics.
@@ -87,6 +91,24 @@ where
         self.query_headers(start_at..=*self.download_range.end(), limit)
     }
 
+    /// Returns the next request to submit, retrying shed requests first.
+    ///
+    /// While the buffer holds the maximum number of responses, only a shed request for the next
+    /// expected block is returned, because its response is required to drain the buffer.
+    fn next_request(&mut self) -> DownloadResult<Option<Vec<SealedHeader<Provider::Header>>>> {
+        let has_room = self.buffered_responses.len() < self.max_buffered_responses;
+        if let Some((&first, _)) = self.shed_requests.first_key_value() &&
+            (has_room || first <= self.next_expected_block_number())
+        {
+            return Ok(self.shed_requests.pop_first().map(|(_, request)| request))
+        }
+
+        if !has_room {
+            return Ok(None)
+        }
+        self.next_headers_request()
+    }
+
     /// Retrieve a batch of headers from the database starting from the provided block number.
     ///
     /// This method is going to return the batch as soon as one of the conditions below
@@ -163,6 +185,19 @@ where
         self.buffered_blocks_size_bytes < self.max_buffered_blocks_size_bytes
     }
 
+    /// Returns the ratio of buffered responses to the configured maximum number of buffered
+    /// responses.
+    ///
+    /// A value near 1.0 means the stage is the bottleneck: bodies are downloaded faster than they
+    /// are consumed, and no new requests are issued until the buffer drains. The value can exceed
+    /// 1.0, because the response containing the next expected block is always buffered.
+    pub fn buffer_pressure(&self) -> f64 {
+        if self.max_buffered_responses == 0 {
+            return 1.0
+        }
+        self.buffered_responses.len() as f64 / self.max_buffered_responses as f64
+    }
+
     // Check if the stream is terminated
     fn is_terminated(&self) -> bool {
         // There is nothing to request if the range is empty
@@ -173,6 +208,7 @@ where
 
         nothing_to_request &&
             self.in_progress_queue.is_empty() &&
+            self.shed_requests.is_empty() &&
             self.buffered_responses.is_empty() &&
             self.queued_bodies.is_empty()
     }
@@ -186,6 +222,7 @@ where
         self.in_progress_queue.clear();
         self.queued_bodies = Vec::new();
         self.buffered_responses = BinaryHeap::new();
+        self.shed_requests.clear();
         self.buffered_blocks_size_bytes = 0;
 
         // reset metrics
@@ -193,6 +230,7 @@ where
         self.metrics.buffered_responses.set(0.);
         self.metrics.buffered_blocks.set(0.);
         self.metrics.buffered_blocks_size_bytes.set(0.);
+        self.metrics.buffer_pressure.set(0.);
         self.metrics.queued_blocks.set(0.);
     }
 
@@ -210,9 +248,31 @@ where
         self.buffered_blocks_size_bytes -= resp.size();
         self.metrics.buffered_blocks.decrement(resp.len() as f64);
         self.metrics.buffered_blocks_size_bytes.set(self.buffered_blocks_size_bytes as f64);
+        self.metrics.buffer_pressure.set(self.buffer_pressure());
         Some(resp)
     }
 
+    /// Buffers a received response.
+    ///
+    /// If the buffer already holds the maximum number of responses, the response is dropped and
+    /// its blocks are requested again once there is room. The response containing the next
+    /// expected block is always buffered, because it is required to drain the buffer.
+    fn on_bodies_response(&mut self, response: Vec<BlockResponse<B>>) {
+        if let (Some(first), Some(last)) = (response.first(), response.last()) {
+            let (first, last) = (first.block_number(), last.block_number());
+            let is_next = (first..=last).contains(&self.next_expected_block_number());
+            if !is_next && self.buffered_responses.len() >= self.max_buffered_responses {
+                tracing::trace!(target: "downloaders::bodies", range = ?first..=last, "Buffer full, shedding response");
+                self.metrics.shed_responses.increment(1);
+                self.shed_requests
+                    .insert(first, response.into_iter().map(BlockResponse::into_header).collect());
+                return
+            }
+        }
+
+        self.buffer_bodies_response(response);
+    }
+
     /// Adds a new response to the internal buffer
     fn buffer_bodies_response(&mut self, response: Vec<BlockResponse<B>>) {
         // take into account capacity
@@ -227,6 +287,7 @@ where
 
         self.metrics.buffered_blocks.increment(response_len as f64);
         self.metrics.buffered_blocks_size_bytes.set(self.buffered_blocks_size_bytes as f64);
+        self.metrics.buffer_pressure.set(self.buffer_pressure());
         self.metrics.buffered_responses.set(self.buffered_responses.len() as f64);
     }
 
@@ -384,7 +445,7 @@ where
                 this.metrics.in_flight_requests.decrement(1.);
                 match response {
                     Ok(response) => {
-                        this.buffer_bodies_response(response);
+                        this.on_bodies_response(response);
                     }
                     Err(error) => {
                         tracing::debug!(target: "downloaders::bodies", %error, "Request failed");
@@ -398,7 +459,7 @@ where
             let mut new_request_submitted = false;
             // Submit new requests
             'inner: while this.can_submit_new_request() {
-                match this.next_headers_request() {
+                match this.next_request() {
                     Ok(Some(request)) => {
                         this.metrics.in_flight_requests.increment(1.);
                         this.in_progress_queue.push_new_request(
@@ -430,7 +491,7 @@ where
         }
 
         // All requests are handled, stream is finished
-        if this.in_progress_queue.is_empty() {
+        if this.in_progress_queue.is_empty() && this.shed_requests.is_empty() {
             if this.queued_bodies.is_empty() {
                 return Poll::Ready(None)
             }
@@ -515,6 +576,8 @@ pub struct BodiesDownloaderBuilder {
     pub stream_batch_size: usize,
     /// Maximum number of bytes of received bodies to buffer internally.
     pub max_buffered_blocks_size_bytes: usize,
+    /// Maximum number of responses to buffer internally.
+    pub max_buffered_responses: usize,
     /// The maximum number of requests to send concurrently.
     pub concurrent_requests_range: RangeInclusive<usize>,
 }
@@ -527,6 +590,7 @@ impl BodiesDownloaderBuilder {
             .with_stream_batch_size(config.downloader_stream_batch_size)
             .with_request_limit(config.downloader_request_limit)
             .with_max_buffered_blocks_size_bytes(config.downloader_max_buffered_blocks_size_bytes)
+            .with_max_buffered_responses(config.downloader_max_buffered_responses)
             .with_concurrent_requests_range(
                 config.downloader_min_concurrent_requests..=
                     config.downloader_max_concurrent_requests,
@@ -540,6 +604,7 @@ impl Default for BodiesDownloaderBuilder {
             request_limit: 200,
             stream_batch_size: 1_000,
             max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
+            max_buffered_responses: 1_000,
             concurrent_requests_range: 5..=100,
         }
     }
@@ -576,6 +641,15 @@ impl BodiesDownloaderBuilder {
         self
     }
 
+    /// Set max buffered responses on the downloader.
+    ///
+    /// Responses received while the buffer is full are dropped and requested again later, which
+    /// bounds memory usage if the consumer stalls at the cost of downloading these bodies twice.
+    pub const fn with_max_buffered_responses(mut self, max_buffered_responses: usize) -> Self {
+        self.max_buffered_responses = max_buffered_responses;
+        self
+    }
+
     /// Consume self and return the concurrent downloader.
     pub fn build<B, C, Provider>(
         self,
@@ -593,6 +667,7 @@ impl BodiesDownloaderBuilder {
             stream_batch_size,
             concurrent_requests_range,
             max_buffered_blocks_size_bytes,
+            max_buffered_responses,
         } = self;
         let metrics = BodyDownloaderMetrics::default();
         let in_progress_queue = BodiesRequestQueue::new(metrics.clone());
@@ -603,12 +678,14 @@ impl BodiesDownloaderBuilder {
             request_limit,
             stream_batch_size,
             max_buffered_blocks_size_bytes,
+            max_buffered_responses,
             concurrent_requests_range,
             in_progress_queue,
             metrics,
             download_range: RangeInclusive::new(1, 0),
             latest_queued_block_number: None,
             buffered_responses: Default::default(),
+            shed_requests: Default::default(),
             queued_bodies: Default::default(),
             buffered_blocks_size_bytes: 0,
         }
@@ -832,4 +909,102 @@ mod tests {
             Some(Ok(res)) => assert_eq!(res, zip_blocks(headers.iter().take(100), &mut bodies))
         );
     }
+
+    #[test]
+    fn buffer_pressure_tracks_buffered_responses() {
+        let factory = create_test_provider_factory();
+        let (headers, _) = generate_bodies(0..=9);
+
+        let mut downloader = BodiesDownloaderBuilder::default()
+            .with_max_buffered_responses(4)
+            .build::<reth_ethereum_primitives::Block, _, _>(
+            Arc::new(TestBodiesClient::default()),
+            Arc::new(TestConsensus::default()),
+            factory,
+        );
+        assert_eq!(downloader.buffer_pressure(), 0.);
+
+        let response = |range: std::ops::Range<usize>| {
+            headers[range].iter().cloned().map(BlockResponse::Empty).collect::<Vec<_>>()
+        };
+        downloader.buffer_bodies_response(response(0..5));
+        assert_eq!(downloader.buffer_pressure(), 0.25);
+        downloader.buffer_bodies_response(response(5..10));
+        assert_eq!(downloader.buffer_pressure(), 0.5);
+
+        downloader.pop_buffered_response();
+        assert_eq!(downloader.buffer_pressure(), 0.25);
+    }
+
+    #[test]
+    fn sheds_responses_when_buffer_is_full() {
+        let factory = create_test_provider_factory();
+        let (headers, _) = generate_bodies(0..=19);
+
+        let mut downloader = BodiesDownloaderBuilder::default()
+            .with_max_buffered_responses(1)
+            .build::<reth_ethereum_primitives::Block, _, _>(
+            Arc::new(TestBodiesClient::default()),
+            Arc::new(TestConsensus::default()),
+            factory,
+        );
+        downloader.set_download_range(0..=19).expect("failed to set download range");
+        let response = |range: std::ops::Range<usize>| {
+            headers[range].iter().cloned().map(BlockResponse::Empty).collect::<Vec<_>>()
+        };
+
+        downloader.on_bodies_response(response(10..15));
+        assert_eq!(downloader.buffered_responses.len(), 1);
+        assert_eq!(downloader.next_request().unwrap(), None);
+
+        // the buffer is full, so the response is dropped and requested again later
+        downloader.on_bodies_response(response(5..10));
+        assert_eq!(downloader.buffered_responses.len(), 1);
+        assert_eq!(downloader.shed_requests.len(), 1);
+        assert_eq!(downloader.next_request().unwrap(), None);
+        assert!(!downloader.is_terminated());
+
+        // the response with the next expected block is never dropped
+        downloader.on_bodies_response(response(0..5));
+        assert_eq!(downloader.buffered_responses.len(), 2);
+        let next = downloader.try_next_buffered().unwrap();
+        downloader.queue_bodies(next);
+
+        // the shed request now contains the next expected block and is retried despite the full
+        // buffer
+        assert_eq!(downloader.next_request().unwrap(), Some(headers[5..10].to_vec()));
+        assert!(downloader.shed_requests.is_empty());
+    }
+
+    // Check that all blocks are downloaded if responses are shed
+    #[tokio::test]
+    async fn can_download_after_shedding_responses() {
+        // Generate some random blocks
+        let factory = create_test_provider_factory();
+        let (headers, mut bodies) = generate_bodies(0..=99);
+
+        insert_headers(&factory, &headers);
+
+        let client = Arc::new(
+            TestBodiesClient::default().with_bodies(bodies.clone()).with_should_delay(true),
+        );
+
+        let mut downloader = BodiesDownloaderBuilder::default()
+            .with_stream_batch_size(10)
+            .with_request_limit(5)
+            .with_max_buffered_responses(1)
+            .build::<reth_ethereum_primitives::Block, _, _>(
+                client.clone(),
+                Arc::new(TestConsensus::default()),
+                factory,
+            );
+
+        downloader.set_download_range(0..=99).expect("failed to set download range");
+        let mut header = 0;
+        while let Some(Ok(resp)) = downloader.next().await {
+            assert_eq!(resp, zip_blocks(headers.iter().skip(header).take(resp.len()), &mut bodies));
+            header += resp.len();
+        }
+        assert_eq!(header, headers.len());
+    }
 }
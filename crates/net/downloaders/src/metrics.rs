@@ -35,8 +35,14 @@ pub struct BodyDownloaderMetrics {
     pub buffered_blocks: Gauge,
     /// Total amount of memory used by the buffered blocks in bytes
     pub buffered_blocks_size_bytes: Gauge,
+    /// The ratio of buffered responses to the configured maximum number of buffered responses.
+    /// A value near 1.0 means the stage is not consuming bodies fast enough.
+    pub buffer_pressure: Gauge,
     /// The number blocks that are contiguous and are queued for insertion into the db.
     pub queued_blocks: Gauge,
+    /// The number of responses that were dropped because the buffer was full, and have to be
+    /// requested again.
+    pub shed_responses: Counter,
     /// The number of out-of-order requests sent by the downloader.
     /// The consumer of the download stream is able to re-request data (bodies) in case
     /// it encountered a recoverable error (e.g. during insertion).
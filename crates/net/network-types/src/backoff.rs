@@ -24,4 +24,13 @@ impl BackoffKind {
     pub const fn is_severe(&self) -> bool {
         matches!(self, Self::Medium | Self::High)
     }
+
+    /// Returns the lowercase name of the backoff, e.g. for use as a metric label.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
 }
@@ -35,6 +35,8 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// Counts number of consecutive failed connection attempts to the peer that timed out.
+    pub consecutive_timeouts: u8,
 }
 
 // === impl Peer ===
@@ -66,6 +68,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            consecutive_timeouts: 0,
         }
     }
 
@@ -3,6 +3,7 @@
 use crate::{
     cache::LruMap,
     error::{NetworkError, ServiceKind},
+    metrics::DiscoveryMetrics,
 };
 use enr::Enr;
 use futures::StreamExt;
@@ -25,7 +26,7 @@ use std::{
 };
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::{wrappers::ReceiverStream, Stream};
-use tracing::trace;
+use tracing::{trace, warn};
 
 /// Default max capacity for cache of discovered peers.
 ///
@@ -107,13 +108,23 @@ impl Discovery {
         let ((discv4, discv4_updates, _discv4_service), (discv5, discv5_updates)) =
             tokio::try_join!(discv4_future, discv5_future)?;
 
-        // setup DNS discovery
+        // setup DNS discovery, which is only fatal if it is required
+        let dns_resolver = match dns_discovery_config {
+            Some(dns_config) => match DnsResolver::from_system_conf() {
+                Ok(resolver) => Some((resolver, dns_config)),
+                Err(err) if dns_config.required => return Err(err.into()),
+                Err(err) => {
+                    warn!(target: "net::discovery", %err, "Failed to configure DNS resolver, disabling DNS discovery");
+                    DiscoveryMetrics::default().dns_resolver_failures.increment(1);
+                    None
+                }
+            },
+            None => None,
+        };
         let (_dns_discovery, dns_discovery_updates, _dns_disc_service) =
-            if let Some(dns_config) = dns_discovery_config {
-                let (mut service, dns_disc) = DnsDiscoveryService::new_pair(
-                    Arc::new(DnsResolver::from_system_conf()?),
-                    dns_config,
-                );
+            if let Some((resolver, dns_config)) = dns_resolver {
+                let (mut service, dns_disc) =
+                    DnsDiscoveryService::new_pair(Arc::new(resolver), dns_config);
                 let dns_discovery_updates = service.node_record_stream();
                 let dns_disc_service = service.spawn();
                 (Some(dns_disc), Some(dns_discovery_updates), Some(dns_disc_service))
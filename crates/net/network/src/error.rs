@@ -87,6 +87,14 @@ pub(crate) trait SessionError: fmt::Debug + fmt::Display {
     /// discovery, for example if it's using a different genesis hash.
     fn merits_discovery_ban(&self) -> bool;
 
+    /// Returns a short, static name of the reason the error merits a discovery ban, e.g. for use
+    /// as a metric label.
+    ///
+    /// Returns `None` if the error does not [merit a discovery ban](Self::merits_discovery_ban).
+    fn discovery_ban_reason(&self) -> Option<&'static str> {
+        self.merits_discovery_ban().then(|| self.category())
+    }
+
     /// Returns true if the error indicates that we'll never be able to establish a connection to
     /// that peer. For example, not matching capabilities or a mismatch in protocols.
     ///
@@ -104,6 +112,55 @@ pub(crate) trait SessionError: fmt::Debug + fmt::Display {
     /// In case of `Some(BackoffKind)` will temporarily prevent additional
     /// connection attempts.
     fn should_backoff(&self) -> Option<BackoffKind>;
+
+    /// Returns true if the error is caused by the peer not responding in time.
+    fn is_timeout(&self) -> bool {
+        false
+    }
+
+    /// Returns a short, static name of the error's category, e.g. for use as a metric label.
+    fn category(&self) -> &'static str {
+        if self.is_timeout() {
+            "timeout"
+        } else {
+            "other"
+        }
+    }
+
+    /// Returns the backoff for the error, escalated by the number of consecutive timeouts of the
+    /// peer, including this one.
+    ///
+    /// Errors that are not timeouts are not escalated. Otherwise, the second consecutive timeout
+    /// is at least [`BackoffKind::Medium`] and any further timeout is [`BackoffKind::High`].
+    fn escalated_backoff(&self, consecutive_timeouts: u8) -> Option<BackoffKind> {
+        let kind = self.should_backoff()?;
+        if !self.is_timeout() {
+            return Some(kind)
+        }
+
+        Some(match (kind, consecutive_timeouts) {
+            (kind, 0..=1) => kind,
+            (BackoffKind::Low, 2) => BackoffKind::Medium,
+            (kind, 2) => kind,
+            _ => BackoffKind::High,
+        })
+    }
+}
+
+/// Returns true if the error is caused by the peer not supporting the capabilities we need.
+///
+/// These errors are fatal protocol errors, so the peer is already removed and banned. This is only
+/// used to report them in their own category.
+const fn is_capability_mismatch(err: &EthStreamError) -> bool {
+    matches!(
+        err,
+        EthStreamError::P2PStreamError(
+            P2PStreamError::HandshakeError(P2PHandshakeError::NoSharedCapabilities) |
+                P2PStreamError::CapabilityNotShared |
+                P2PStreamError::ParseSharedCapability(_) |
+                P2PStreamError::MismatchedProtocolVersion { .. }
+        )
+    )
 }
 
 impl SessionError for EthStreamError {
@@ -118,6 +175,19 @@ impl SessionError for EthStreamError {
         }
     }
 
+    fn discovery_ban_reason(&self) -> Option<&'static str> {
+        if !self.merits_discovery_ban() {
+            return None
+        }
+
+        Some(match self {
+            // EIP-2124: same genesis, but an incompatible fork schedule
+            Self::EthHandshakeError(EthHandshakeError::InvalidFork(_)) => "fork_id_mismatch",
+            Self::EthHandshakeError(EthHandshakeError::MismatchedGenesis(_)) => "genesis_mismatch",
+            _ => self.category(),
+        })
+    }
+
     fn is_fatal_protocol_error(&self) -> bool {
         match self {
             Self::P2PStreamError(err) => {
@@ -199,6 +269,36 @@ impl SessionError for EthStreamError {
             _ => None,
         }
     }
+
+    fn is_timeout(&self) -> bool {
+        if let Some(err) = self.as_io() {
+            return err.is_timeout()
+        }
+
+        matches!(
+            self,
+            Self::EthHandshakeError(EthHandshakeError::NoResponse) |
+                Self::P2PStreamError(
+                    P2PStreamError::HandshakeError(P2PHandshakeError::NoResponse) |
+                        P2PStreamError::PingTimeout |
+                        P2PStreamError::Disconnected(DisconnectReason::PingTimeout)
+                )
+        )
+    }
+
+    fn category(&self) -> &'static str {
+        if self.is_timeout() {
+            "timeout"
+        } else if self.as_io().is_some() {
+            "io"
+        } else if self.as_disconnected().is_some() {
+            "disconnected"
+        } else if is_capability_mismatch(self) {
+            "capability"
+        } else {
+            "protocol"
+        }
+    }
 }
 
 impl SessionError for PendingSessionHandshakeError {
@@ -220,6 +320,13 @@ impl SessionError for PendingSessionHandshakeError {
         }
     }
 
+    fn discovery_ban_reason(&self) -> Option<&'static str> {
+        match self {
+            Self::Eth(eth) => eth.discovery_ban_reason(),
+            _ => self.merits_discovery_ban().then(|| self.category()),
+        }
+    }
+
     fn is_fatal_protocol_error(&self) -> bool {
         match self {
             Self::Eth(eth) => eth.is_fatal_protocol_error(),
@@ -247,6 +354,23 @@ impl SessionError for PendingSessionHandshakeError {
             Self::UnsupportedExtraCapability => Some(BackoffKind::High),
         }
     }
+
+    fn is_timeout(&self) -> bool {
+        match self {
+            Self::Eth(eth) => eth.is_timeout(),
+            Self::Timeout => true,
+            Self::Ecies(_) | Self::UnsupportedExtraCapability => false,
+        }
+    }
+
+    fn category(&self) -> &'static str {
+        match self {
+            Self::Eth(eth) => eth.category(),
+            Self::Ecies(_) => "ecies",
+            Self::Timeout => "timeout",
+            Self::UnsupportedExtraCapability => "capability",
+        }
+    }
 }
 
 impl SessionError for io::Error {
@@ -271,11 +395,26 @@ impl SessionError for io::Error {
             _ => Some(BackoffKind::Medium),
         }
     }
+
+    fn is_timeout(&self) -> bool {
+        self.kind() == ErrorKind::TimedOut
+    }
+
+    fn category(&self) -> &'static str {
+        if self.is_timeout() {
+            "timeout"
+        } else {
+            "io"
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::B256;
+    use reth_ethereum_forks::{ForkHash, ForkId, ValidationError};
+    use reth_primitives_traits::GotExpected;
     use std::net::{Ipv4Addr, SocketAddrV4};
 
     #[test]
@@ -289,6 +428,43 @@ mod tests {
         assert!(err.is_fatal_protocol_error());
     }
 
+    #[test]
+    fn test_escalated_backoff() {
+        let err = EthStreamError::P2PStreamError(P2PStreamError::PingTimeout);
+        assert!(err.is_timeout());
+        assert_eq!(err.escalated_backoff(1), Some(BackoffKind::Low));
+        assert_eq!(err.escalated_backoff(2), Some(BackoffKind::Medium));
+        assert_eq!(err.escalated_backoff(3), Some(BackoffKind::High));
+
+        let err = PendingSessionHandshakeError::Timeout;
+        assert!(err.is_timeout());
+        assert_eq!(err.escalated_backoff(2), Some(BackoffKind::Medium));
+        assert_eq!(err.escalated_backoff(3), Some(BackoffKind::High));
+
+        // errors that are not timeouts are not escalated
+        let err = EthStreamError::P2PStreamError(P2PStreamError::EmptyProtocolMessage);
+        assert!(!err.is_timeout());
+        assert_eq!(err.escalated_backoff(3), Some(BackoffKind::Medium));
+    }
+
+    #[test]
+    fn test_error_category() {
+        let err = EthStreamError::P2PStreamError(P2PStreamError::PingTimeout);
+        assert_eq!(err.category(), "timeout");
+
+        let err = EthStreamError::P2PStreamError(P2PStreamError::HandshakeError(
+            P2PHandshakeError::Disconnected(DisconnectReason::TooManyPeers),
+        ));
+        assert_eq!(err.category(), "disconnected");
+
+        let err = EthStreamError::P2PStreamError(P2PStreamError::EmptyProtocolMessage);
+        assert_eq!(err.category(), "protocol");
+
+        let err = PendingSessionHandshakeError::Eth(io::Error::from(ErrorKind::BrokenPipe).into());
+        assert_eq!(err.category(), "io");
+        assert_eq!(PendingSessionHandshakeError::Timeout.category(), "timeout");
+    }
+
     #[test]
     fn test_should_backoff() {
         let err = EthStreamError::P2PStreamError(P2PStreamError::HandshakeError(
@@ -304,6 +480,55 @@ mod tests {
         assert_eq!(err.should_backoff(), Some(BackoffKind::Low));
     }
 
+    #[test]
+    fn test_capability_mismatch() {
+        for err in [
+            EthStreamError::P2PStreamError(P2PStreamError::CapabilityNotShared),
+            EthStreamError::P2PStreamError(P2PStreamError::HandshakeError(
+                P2PHandshakeError::NoSharedCapabilities,
+            )),
+        ] {
+            // the peer is removed and banned
+            assert!(err.is_fatal_protocol_error());
+            assert_eq!(err.category(), "capability");
+            assert!(!err.is_timeout());
+
+            let err = PendingSessionHandshakeError::Eth(err);
+            assert!(err.is_fatal_protocol_error());
+            assert_eq!(err.category(), "capability");
+        }
+    }
+
+    #[test]
+    fn test_discovery_ban_reason() {
+        let local = ForkId { hash: ForkHash([0xfc, 0x64, 0xec, 0x04]), next: 1150000 };
+        let remote = ForkId { hash: ForkHash([0x97, 0xc2, 0xc3, 0x4c]), next: 0 };
+        let err = EthStreamError::EthHandshakeError(EthHandshakeError::InvalidFork(
+            ValidationError::LocalIncompatibleOrStale { local, remote },
+        ));
+        assert!(err.merits_discovery_ban());
+        assert!(err.is_fatal_protocol_error());
+        assert_eq!(err.discovery_ban_reason(), Some("fork_id_mismatch"));
+        assert_eq!(
+            PendingSessionHandshakeError::Eth(err).discovery_ban_reason(),
+            Some("fork_id_mismatch")
+        );
+
+        let err = EthStreamError::EthHandshakeError(EthHandshakeError::MismatchedGenesis(
+            GotExpected { got: B256::with_last_byte(1), expected: B256::with_last_byte(2) }.into(),
+        ));
+        assert!(err.merits_discovery_ban());
+        assert!(err.is_fatal_protocol_error());
+        assert_eq!(err.discovery_ban_reason(), Some("genesis_mismatch"));
+
+        let err = EthStreamError::EthHandshakeError(EthHandshakeError::NoResponse);
+        assert_eq!(err.discovery_ban_reason(), None);
+        assert_eq!(
+            PendingSessionHandshakeError::UnsupportedExtraCapability.discovery_ban_reason(),
+            Some("capability")
+        );
+    }
+
     #[test]
     fn test_address_in_use_message() {
         let addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 1234));
@@ -24,7 +24,9 @@ use crate::{
     import::{BlockImport, BlockImportEvent, BlockImportOutcome, BlockValidation, NewBlockEvent},
     listener::ConnectionListener,
     message::{NewBlockMessage, PeerMessage},
-    metrics::{DisconnectMetrics, NetworkMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
+    metrics::{
+        DisconnectMetrics, NetworkMetrics, SessionErrorMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE,
+    },
     network::{NetworkHandle, NetworkHandleMessage},
     peers::PeersManager,
     poll_nested_stream_with_budget,
@@ -139,6 +141,8 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
     disconnect_metrics: DisconnectMetrics,
+    /// Metrics for failed sessions by error classification
+    session_error_metrics: SessionErrorMetrics,
 }
 
 impl NetworkManager {
@@ -354,6 +358,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
+            session_error_metrics: Default::default(),
         })
     }
 
@@ -848,11 +853,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 let reason = if let Some(ref err) = error {
                     // If the connection was closed due to an error, we report
                     // the peer
-                    self.swarm.state_mut().peers_mut().on_active_session_dropped(
+                    let backoff = self.swarm.state_mut().peers_mut().on_active_session_dropped(
                         &remote_addr,
                         &peer_id,
                         err,
                     );
+                    self.session_error_metrics.record_session_error(err, backoff);
                     err.as_disconnected()
                 } else {
                     // Gracefully disconnected
@@ -889,6 +895,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                         .state_mut()
                         .peers_mut()
                         .on_incoming_pending_session_dropped(remote_addr, err);
+                    self.session_error_metrics.record_session_error(err, None);
                     self.metrics.pending_session_failures.increment(1);
                     if let Some(reason) = err.as_disconnected() {
                         self.disconnect_metrics.increment(reason);
@@ -922,11 +929,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 );
 
                 if let Some(ref err) = error {
-                    self.swarm.state_mut().peers_mut().on_outgoing_pending_session_dropped(
-                        &remote_addr,
-                        &peer_id,
-                        err,
-                    );
+                    let backoff = self
+                        .swarm
+                        .state_mut()
+                        .peers_mut()
+                        .on_outgoing_pending_session_dropped(&remote_addr, &peer_id, err);
+                    self.session_error_metrics.record_session_error(err, backoff);
                     self.metrics.pending_session_failures.increment(1);
                     if let Some(reason) = err.as_disconnected() {
                         self.disconnect_metrics.increment(reason);
@@ -958,11 +966,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     "Outgoing connection error"
                 );
 
-                self.swarm.state_mut().peers_mut().on_outgoing_connection_failure(
+                let backoff = self.swarm.state_mut().peers_mut().on_outgoing_connection_failure(
                     &remote_addr,
                     &peer_id,
                     &error,
                 );
+                self.session_error_metrics.record_session_error(&error, backoff);
 
                 self.metrics.backed_off_peers.set(
                         self.swarm
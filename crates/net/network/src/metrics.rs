@@ -1,10 +1,12 @@
-use metrics::Histogram;
+use crate::error::SessionError;
+use metrics::{counter, Histogram};
 use reth_eth_wire::DisconnectReason;
 use reth_ethereum_primitives::TxType;
 use reth_metrics::{
     metrics::{Counter, Gauge},
     Metrics,
 };
+use reth_network_types::BackoffKind;
 
 /// Scope for monitoring transactions sent from the manager to the tx manager
 pub(crate) const NETWORK_POOL_TRANSACTIONS_SCOPE: &str = "network.pool.transactions";
@@ -77,6 +79,57 @@ pub struct NetworkMetrics {
     pub(crate) acc_duration_poll_swarm: Gauge,
 }
 
+/// Metrics for the discovery services.
+#[derive(Metrics)]
+#[metrics(scope = "network.discovery")]
+pub struct DiscoveryMetrics {
+    /// Number of times DNS discovery was disabled because the DNS resolver could not be
+    /// configured.
+    pub(crate) dns_resolver_failures: Counter,
+}
+
+/// Metrics for failed sessions, by the classification of the [`SessionError`].
+#[derive(Metrics)]
+#[metrics(scope = "network.session_errors")]
+pub struct SessionErrorMetrics {
+    /// Number of session errors that are fatal protocol errors
+    pub(crate) fatal: Counter,
+    /// Number of fatal session errors that also merit a discovery ban
+    pub(crate) discovery_ban: Counter,
+    /// Number of session errors that result in a low backoff
+    pub(crate) backoff_low: Counter,
+    /// Number of session errors that result in a medium backoff
+    pub(crate) backoff_medium: Counter,
+    /// Number of session errors that result in a high backoff
+    pub(crate) backoff_high: Counter,
+}
+
+impl SessionErrorMetrics {
+    /// Increments the counters matching the classification of the given error and the backoff
+    /// the peers manager applied because of it.
+    pub(crate) fn record_session_error(
+        &self,
+        err: &impl SessionError,
+        applied_backoff: Option<BackoffKind>,
+    ) {
+        if err.is_fatal_protocol_error() {
+            self.fatal.increment(1);
+            if let Some(reason) = err.discovery_ban_reason() {
+                self.discovery_ban.increment(1);
+                counter!("network.session_errors.discovery_ban_reasons", "reason" => reason)
+                    .increment(1);
+            }
+            return
+        }
+        match applied_backoff {
+            Some(BackoffKind::Low) => self.backoff_low.increment(1),
+            Some(BackoffKind::Medium) => self.backoff_medium.increment(1),
+            Some(BackoffKind::High) => self.backoff_high.increment(1),
+            None => {}
+        }
+    }
+}
+
 /// Metrics for `SessionManager`
 #[derive(Metrics)]
 #[metrics(scope = "network")]
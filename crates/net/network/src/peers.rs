@@ -10,6 +10,7 @@ use futures::StreamExt;
 
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_ethereum_forks::ForkId;
+use reth_metrics::metrics::counter;
 use reth_net_banlist::BanList;
 use reth_network_api::test_utils::{PeerCommand, PeersHandle};
 use reth_network_peers::{NodeRecord, PeerId};
@@ -19,7 +20,7 @@ use reth_network_types::{
         config::PeerBackoffDurations,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
+    BackoffKind, ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
     ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use std::{
@@ -36,7 +37,7 @@ use tokio::{
     time::{Instant, Interval},
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use tracing::{trace, warn};
+use tracing::{debug, trace, warn};
 
 /// Maintains the state of _all_ the peers known to the network.
 ///
@@ -513,12 +514,14 @@ impl PeersManager {
 
     /// Invoked when an _outgoing_ pending session was closed during authentication or the
     /// handshake.
+    ///
+    /// Returns the backoff applied to the peer, if any.
     pub(crate) fn on_outgoing_pending_session_dropped(
         &mut self,
         remote_addr: &SocketAddr,
         peer_id: &PeerId,
         err: &PendingSessionHandshakeError,
-    ) {
+    ) -> Option<BackoffKind> {
         self.on_connection_failure(remote_addr, peer_id, err, ReputationChangeKind::FailedToConnect)
     }
 
@@ -537,6 +540,7 @@ impl PeersManager {
                     // we reset the backoff counter since we're able to establish a successful
                     // session to that peer
                     entry.get_mut().severe_backoff_counter = 0;
+                    entry.get_mut().consecutive_timeouts = 0;
                     entry.get_mut().state = PeerConnectionState::Idle;
                     return
                 }
@@ -560,30 +564,34 @@ impl PeersManager {
     ///
     /// Depending on whether the error is fatal, the peer will be removed from the peer set
     /// otherwise its reputation is slashed.
+    ///
+    /// Returns the backoff applied to the peer, if any.
     pub(crate) fn on_active_session_dropped(
         &mut self,
         remote_addr: &SocketAddr,
         peer_id: &PeerId,
         err: &EthStreamError,
-    ) {
+    ) -> Option<BackoffKind> {
         self.on_connection_failure(remote_addr, peer_id, err, ReputationChangeKind::Dropped)
     }
 
     /// Called when an attempt to create an _outgoing_ pending session failed while setting up a tcp
     /// connection.
+    ///
+    /// Returns the backoff applied to the peer, if any.
     pub(crate) fn on_outgoing_connection_failure(
         &mut self,
         remote_addr: &SocketAddr,
         peer_id: &PeerId,
         err: &io::Error,
-    ) {
+    ) -> Option<BackoffKind> {
         // there's a race condition where we accepted an incoming connection while we were trying to
         // connect to the same peer at the same time. if the outgoing connection failed
         // after the incoming connection was accepted, we can ignore this error
         if let Some(peer) = self.peers.get(peer_id) {
             if peer.state.is_incoming() {
                 // we already have an active connection to the peer, so we can ignore this error
-                return
+                return None
             }
 
             if peer.is_trusted() && is_connection_failed_reputation(peer.reputation) {
@@ -596,15 +604,20 @@ impl PeersManager {
         self.on_connection_failure(remote_addr, peer_id, err, ReputationChangeKind::FailedToConnect)
     }
 
+    /// Handles a failed connection to the peer and returns the backoff applied to it, if any.
+    ///
+    /// The applied backoff can differ from [`SessionError::should_backoff`], because it's
+    /// escalated for repeated timeouts and lowered for trusted and static peers.
     fn on_connection_failure(
         &mut self,
         remote_addr: &SocketAddr,
         peer_id: &PeerId,
         err: impl SessionError,
         reputation_change: ReputationChangeKind,
-    ) {
+    ) -> Option<BackoffKind> {
         trace!(target: "net::peers", ?remote_addr, ?peer_id, %err, "handling failed connection");
 
+        let mut applied_backoff = None;
         if err.is_fatal_protocol_error() {
             trace!(target: "net::peers", ?remote_addr, ?peer_id, %err, "fatal connection error");
             // remove the peer to which we can't establish a connection due to protocol related
@@ -634,15 +647,21 @@ impl PeersManager {
             let mut remove_peer = false;
 
             if let Some(peer) = self.peers.get_mut(peer_id) {
-                if let Some(kind) = err.should_backoff() {
-                    if peer.is_trusted() || peer.is_static() {
+                if err.is_timeout() {
+                    peer.consecutive_timeouts = peer.consecutive_timeouts.saturating_add(1);
+                } else {
+                    peer.consecutive_timeouts = 0;
+                }
+
+                if let Some(mut kind) = err.escalated_backoff(peer.consecutive_timeouts) {
+                    let until = if peer.is_trusted() || peer.is_static() {
                         // provide a bit more leeway for trusted peers and use a lower backoff so
                         // that we keep re-trying them after backing off shortly, but we should at
                         // least backoff for the low duration to not violate the ip based inbound
                         // connection throttle that peer has in place, because this peer might not
                         // have us registered as a trusted peer.
-                        let backoff = self.backoff_durations.low;
-                        backoff_until = Some(std::time::Instant::now() + backoff);
+                        kind = BackoffKind::Low;
+                        std::time::Instant::now() + self.backoff_durations.low
                     } else {
                         // Increment peer.backoff_counter
                         if kind.is_severe() {
@@ -650,14 +669,29 @@ impl PeersManager {
                                 peer.severe_backoff_counter.saturating_add(1);
                         }
 
-                        let backoff_time =
-                            self.backoff_durations.backoff_until(kind, peer.severe_backoff_counter);
-
                         // The peer has signaled that it is currently unable to process any more
                         // connections, so we will hold off on attempting any new connections for a
                         // while
-                        backoff_until = Some(backoff_time);
-                    }
+                        self.backoff_durations
+                            .backoff_until(kind.clone(), peer.severe_backoff_counter)
+                    };
+                    backoff_until = Some(until);
+
+                    let bucket = kind.as_str();
+                    let category = err.category();
+                    let remaining = until.saturating_duration_since(std::time::Instant::now());
+                    debug!(
+                        target: "net::peers",
+                        ?peer_id,
+                        bucket,
+                        category,
+                        ?remaining,
+                        %err,
+                        "backing off peer"
+                    );
+                    counter!("network.peer_backoffs", "bucket" => bucket, "category" => category)
+                        .increment(1);
+                    applied_backoff = Some(kind);
                 } else {
                     // If the error was not a backoff error, we reduce the peer's reputation
                     let reputation_change = self.reputation_weights.change(reputation_change);
@@ -688,6 +722,7 @@ impl PeersManager {
         }
 
         self.fill_outbound_slots();
+        applied_backoff
     }
 
     /// Invoked if a pending session was disconnected because there's already a connection to the
@@ -2212,6 +2247,29 @@ mod tests {
         assert_eq!(peers.num_outbound_connections(), 0);
     }
 
+    #[tokio::test]
+    async fn test_outgoing_connection_failure_returns_applied_backoff() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+
+        // the backoff of repeated timeouts is escalated
+        let timeout = io::Error::new(io::ErrorKind::TimedOut, "");
+        assert_eq!(
+            peers.on_outgoing_connection_failure(&socket_addr, &peer, &timeout),
+            Some(BackoffKind::Medium)
+        );
+        assert_eq!(
+            peers.on_outgoing_connection_failure(&socket_addr, &peer, &timeout),
+            Some(BackoffKind::Medium)
+        );
+        assert_eq!(
+            peers.on_outgoing_connection_failure(&socket_addr, &peer, &timeout),
+            Some(BackoffKind::High)
+        );
+    }
+
     #[tokio::test]
     async fn test_outgoing_connection_gracefully_closed() {
         let peer = PeerId::random();
@@ -30,6 +30,14 @@ where
         }
     }
 
+    /// Consumes the response and returns the header of the block.
+    pub fn into_header(self) -> SealedHeader<B::Header> {
+        match self {
+            Self::Full(block) => block.into_sealed_header(),
+            Self::Empty(header) => header,
+        }
+    }
+
     /// Return the reference to the response body
     pub fn into_body(self) -> Option<B::Body> {
         match self {
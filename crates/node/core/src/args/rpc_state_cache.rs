@@ -1,7 +1,8 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_STATE_ACCOUNT_CACHE_MAX_LEN,
+    DEFAULT_STATE_STORAGE_CACHE_MAX_LEN,
 };
 
 /// Parameters to configure RPC state cache.
@@ -30,6 +31,20 @@ pub struct RpcStateCacheArgs {
     )]
     pub max_headers: u32,
 
+    /// Max number of accounts in the state read cache.
+    #[arg(
+        long = "rpc-cache.max-state-accounts",
+        default_value_t = DEFAULT_STATE_ACCOUNT_CACHE_MAX_LEN,
+    )]
+    pub max_state_accounts: u32,
+
+    /// Max number of storage slots in the state read cache.
+    #[arg(
+        long = "rpc-cache.max-state-storage",
+        default_value_t = DEFAULT_STATE_STORAGE_CACHE_MAX_LEN,
+    )]
+    pub max_state_storage: u32,
+
     /// Max number of concurrent database requests.
     #[arg(
         long = "rpc-cache.max-concurrent-db-requests",
@@ -44,6 +59,8 @@ impl RpcStateCacheArgs {
         self.max_blocks = 0;
         self.max_receipts = 0;
         self.max_headers = 0;
+        self.max_state_accounts = 0;
+        self.max_state_storage = 0;
     }
 }
 
@@ -53,6 +70,8 @@ impl Default for RpcStateCacheArgs {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
+            max_state_accounts: DEFAULT_STATE_ACCOUNT_CACHE_MAX_LEN,
+            max_state_storage: DEFAULT_STATE_STORAGE_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
         }
     }
@@ -15,7 +15,10 @@ use alloy_trie::EMPTY_ROOT_HASH;
 use reth_consensus::ConsensusError;
 use reth_optimism_forks::OpHardforks;
 use reth_optimism_primitives::DepositReceipt;
-use reth_primitives_traits::{receipt::gas_spent_by_transactions, BlockBody, GotExpected};
+use reth_primitives_traits::{
+    receipt::{aggregate_bloom, gas_spent_by_transactions},
+    BlockBody, GotExpected,
+};
 
 /// Ensures the block response data matches the header.
 ///
@@ -137,7 +140,7 @@ fn verify_receipts_optimism<R: DepositReceipt>(
         calculate_receipt_root_optimism(&receipts_with_bloom, chain_spec, timestamp);
 
     // Calculate header logs bloom.
-    let logs_bloom = receipts_with_bloom.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom_ref());
+    let logs_bloom = aggregate_bloom(&receipts_with_bloom);
 
     compare_receipts_root_and_logs_bloom(
         receipts_root,
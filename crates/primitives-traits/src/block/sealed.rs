@@ -311,6 +311,10 @@ impl<B: Block> Encodable for SealedBlock<B> {
         // TODO: https://github.com/paradigmxyz/reth/issues/18002
         self.clone().into_block().encode(out);
     }
+
+    fn length(&self) -> usize {
+        self.rlp_length()
+    }
 }
 
 impl<B: Block> Decodable for SealedBlock<B> {
@@ -550,4 +554,37 @@ mod tests {
         assert_eq!(sealed_block.header().state_root, decoded.header().state_root);
         assert_eq!(sealed_block.body().transactions.len(), decoded.body().transactions.len());
     }
+
+    #[test]
+    fn test_sealed_block_rlp_length() {
+        let tx = |nonce| {
+            alloy_consensus::TxEnvelope::Legacy(alloy_consensus::Signed::new_unchecked(
+                alloy_consensus::TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_price: 21_000_000_000,
+                    gas_limit: 21_000,
+                    to: alloy_primitives::TxKind::Call(Address::ZERO),
+                    value: alloy_primitives::U256::from(100),
+                    input: alloy_primitives::Bytes::from(vec![0xab; nonce as usize]),
+                },
+                alloy_primitives::Signature::test_signature(),
+                B256::ZERO,
+            ))
+        };
+
+        for (tx_count, withdrawals) in [(0, None), (1, Some(Default::default())), (64, None)] {
+            let header = alloy_consensus::Header { number: tx_count, ..Default::default() };
+            let body = alloy_consensus::BlockBody {
+                transactions: (0..tx_count).map(tx).collect(),
+                ommers: vec![alloy_consensus::Header::default()],
+                withdrawals,
+            };
+            let sealed_block = SealedBlock::seal_slow(alloy_consensus::Block::new(header, body));
+
+            let mut encoded = Vec::new();
+            sealed_block.encode(&mut encoded);
+            assert_eq!(sealed_block.length(), encoded.len());
+        }
+    }
 }
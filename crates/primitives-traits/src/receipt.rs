@@ -5,6 +5,7 @@ use alloc::vec::Vec;
 use alloy_consensus::{
     Eip2718EncodableReceipt, RlpDecodableReceipt, RlpEncodableReceipt, TxReceipt, Typed2718,
 };
+use alloy_primitives::Bloom;
 use alloy_rlp::{Decodable, Encodable};
 use core::fmt;
 
@@ -65,3 +66,46 @@ where
         .map(|(id, receipt)| (id as u64, receipt.cumulative_gas_used()))
         .collect()
 }
+
+/// Aggregates the logs blooms of the receipts into the logs bloom of the block.
+pub fn aggregate_bloom<'a, I, T>(receipts: I) -> Bloom
+where
+    I: IntoIterator<Item = &'a T>,
+    T: TxReceipt + 'a,
+{
+    receipts.into_iter().fold(Bloom::ZERO, |bloom, receipt| bloom | receipt.bloom())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, Bytes, Log, B256};
+
+    #[test]
+    fn test_aggregate_bloom() {
+        let log = |address: u8, topic: u8| {
+            Log::new_unchecked(
+                Address::with_last_byte(address),
+                vec![B256::with_last_byte(topic)],
+                Bytes::default(),
+            )
+        };
+        let receipts: Vec<alloy_consensus::Receipt> = vec![
+            alloy_consensus::Receipt { logs: vec![log(1, 1), log(2, 2)], ..Default::default() },
+            alloy_consensus::Receipt::default(),
+            alloy_consensus::Receipt {
+                logs: vec![log(3, 3), log(3, 4), log(4, 5)],
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(aggregate_bloom(&receipts[..0]), Bloom::ZERO);
+
+        let logs_bloom = aggregate_bloom(&receipts);
+        for receipt in &receipts {
+            assert_eq!(logs_bloom | receipt.bloom(), logs_bloom);
+        }
+        // bloom missing the logs of the last receipt
+        assert_ne!(aggregate_bloom(&receipts[..2]), logs_bloom);
+    }
+}
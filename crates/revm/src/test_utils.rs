@@ -1,4 +1,4 @@
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, vec::Vec};
 use alloy_primitives::{
     keccak256, map::HashMap, Address, BlockNumber, Bytes, StorageKey, B256, U256,
 };
@@ -9,8 +9,9 @@ use reth_storage_api::{
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, KeccakKeyHasher,
-    MultiProof, MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+    test_utils::state_root_prehashed, updates::TrieUpdates, AccountProof, HashedPostState,
+    HashedStorage, KeccakKeyHasher, MultiProof, MultiProofTargets, StorageMultiProof, StorageProof,
+    TrieInput,
 };
 
 /// Mock state for testing
@@ -69,27 +70,71 @@ impl BlockHashReader for StateProviderTest {
     }
 }
 
+impl StateProviderTest {
+    /// Computes the state root of the accounts with the given hashed state applied on top.
+    ///
+    /// The root is computed from scratch, no trie nodes are returned or reused.
+    fn compute_state_root(&self, hashed_state: &HashedPostState) -> B256 {
+        let mut accounts = self
+            .accounts
+            .iter()
+            .map(|(address, (storage, account))| {
+                let storage = storage
+                    .iter()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(|(slot, value)| (keccak256(slot), *value))
+                    .collect::<BTreeMap<_, _>>();
+                (keccak256(address), (*account, storage))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        for (hashed_address, account) in &hashed_state.accounts {
+            match account {
+                Some(account) => accounts.entry(*hashed_address).or_default().0 = *account,
+                None => {
+                    accounts.remove(hashed_address);
+                }
+            }
+        }
+        for (hashed_address, hashed_storage) in &hashed_state.storages {
+            let Some((_, storage)) = accounts.get_mut(hashed_address) else { continue };
+            if hashed_storage.wiped {
+                storage.clear();
+            }
+            for (slot, value) in &hashed_storage.storage {
+                if value.is_zero() {
+                    storage.remove(slot);
+                } else {
+                    storage.insert(*slot, *value);
+                }
+            }
+        }
+
+        state_root_prehashed(accounts)
+    }
+}
+
 impl StateRootProvider for StateProviderTest {
-    fn state_root(&self, _hashed_state: HashedPostState) -> ProviderResult<B256> {
-        unimplemented!("state root computation is not supported")
+    fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
+        Ok(self.compute_state_root(&hashed_state))
     }
 
-    fn state_root_from_nodes(&self, _input: TrieInput) -> ProviderResult<B256> {
-        unimplemented!("state root computation is not supported")
+    fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
+        Ok(self.compute_state_root(&input.state))
     }
 
     fn state_root_with_updates(
         &self,
-        _hashed_state: HashedPostState,
+        hashed_state: HashedPostState,
     ) -> ProviderResult<(B256, TrieUpdates)> {
-        unimplemented!("state root computation is not supported")
+        Ok((self.compute_state_root(&hashed_state), TrieUpdates::default()))
     }
 
     fn state_root_from_nodes_with_updates(
         &self,
-        _input: TrieInput,
+        input: TrieInput,
     ) -> ProviderResult<(B256, TrieUpdates)> {
-        unimplemented!("state root computation is not supported")
+        Ok((self.compute_state_root(&input.state), TrieUpdates::default()))
     }
 }
 
@@ -119,6 +119,8 @@ impl RethRpcServerConfig for RpcServerArgs {
             max_blocks: self.rpc_state_cache.max_blocks,
             max_receipts: self.rpc_state_cache.max_receipts,
             max_headers: self.rpc_state_cache.max_headers,
+            max_state_accounts: self.rpc_state_cache.max_state_accounts,
+            max_state_storage: self.rpc_state_cache.max_state_storage,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
         }
     }
@@ -1144,6 +1144,117 @@ mod transaction_response_tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_pre_eip155_legacy_transaction_into_rpc() {
+        let r = U256::from(0x1234);
+        let s = U256::from(0x5678);
+        for (parity, v) in [(false, "0x1b"), (true, "0x1c")] {
+            // legacy transactions signed before EIP-155 don't commit to a chain id
+            let tx = TxLegacy { chain_id: None, ..Default::default() };
+            let envelope = EthereumTxEnvelope::<TxEip4844>::Legacy(Signed::new_unchecked(
+                tx,
+                Signature::new(r, s, parity),
+                B256::ZERO,
+            ));
+
+            let rpc_tx: Transaction<EthereumTxEnvelope<TxEip4844>> =
+                envelope.into_rpc_tx(Address::ZERO, TransactionInfo::default()).unwrap();
+            let json = serde_json::to_value(&rpc_tx).unwrap();
+
+            assert_eq!(json["v"], v);
+            assert!(json.get("chainId").is_none());
+            assert_eq!(json["r"], serde_json::to_value(r).unwrap());
+            assert_eq!(json["s"], serde_json::to_value(s).unwrap());
+        }
+    }
+
+    // Pins the serialized RPC representation of signed legacy, EIP-2930 and EIP-1559
+    // transactions, including a contract creation and access lists.
+    #[test]
+    fn test_golden_transactions_into_rpc() {
+        use alloy_consensus::{
+            transaction::SignerRecoverable, SignableTransaction, TxEip1559, TxEip2930,
+        };
+        use alloy_network::TxSignerSync;
+        use alloy_primitives::{address, b256, bytes, TxKind};
+        use alloy_rpc_types_eth::{AccessList, AccessListItem};
+        use alloy_signer_local::PrivateKeySigner;
+
+        let signer: PrivateKeySigner =
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap();
+        let from = address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+        assert_eq!(signer.address(), from);
+
+        let to = address!("0x3535353535353535353535353535353535353535");
+        let access_list = AccessList(vec![AccessListItem {
+            address: to,
+            storage_keys: vec![B256::ZERO, B256::with_last_byte(1)],
+        }]);
+        let tx_info = TransactionInfo {
+            hash: None,
+            index: Some(3),
+            block_hash: Some(b256!(
+                "0x8e38b4dbf6b11fcc3b9dee84fb7986e29ca0a02cecd8977c161ff7333329681e"
+            )),
+            block_number: Some(0x10),
+            base_fee: Some(7),
+        };
+
+        let envelopes: Vec<(&str, EthereumTxEnvelope<TxEip4844>)> = vec![
+            (include_str!("../testdata/transaction/legacy_create.json"), {
+                let mut tx = TxLegacy {
+                    chain_id: Some(1),
+                    nonce: 0,
+                    gas_price: 20_000_000_000,
+                    gas_limit: 1_000_000,
+                    to: TxKind::Create,
+                    value: U256::ZERO,
+                    input: bytes!("0x6080604052"),
+                };
+                let signature = signer.sign_transaction_sync(&mut tx).unwrap();
+                tx.into_signed(signature).into()
+            }),
+            (include_str!("../testdata/transaction/eip2930.json"), {
+                let mut tx = TxEip2930 {
+                    chain_id: 1,
+                    nonce: 1,
+                    gas_price: 20_000_000_000,
+                    gas_limit: 50_000,
+                    to: TxKind::Call(to),
+                    value: U256::from(1_000),
+                    access_list: access_list.clone(),
+                    input: Default::default(),
+                };
+                let signature = signer.sign_transaction_sync(&mut tx).unwrap();
+                tx.into_signed(signature).into()
+            }),
+            (include_str!("../testdata/transaction/eip1559.json"), {
+                let mut tx = TxEip1559 {
+                    chain_id: 1,
+                    nonce: 2,
+                    gas_limit: 50_000,
+                    max_fee_per_gas: 30_000_000_000,
+                    max_priority_fee_per_gas: 2_000_000_000,
+                    to: TxKind::Call(to),
+                    value: U256::from(1_000),
+                    access_list,
+                    input: bytes!("0xa9059cbb"),
+                };
+                let signature = signer.sign_transaction_sync(&mut tx).unwrap();
+                tx.into_signed(signature).into()
+            }),
+        ];
+
+        for (expected, envelope) in envelopes {
+            assert_eq!(envelope.recover_signer().unwrap(), from);
+
+            let rpc_tx: Transaction<EthereumTxEnvelope<TxEip4844>> =
+                envelope.into_rpc_tx(from, tx_info).unwrap();
+            let json = serde_json::to_string_pretty(&rpc_tx).unwrap();
+            assert_eq!(json, expected.trim_end());
+        }
+    }
+
     #[cfg(feature = "op")]
     mod op {
         use super::*;
@@ -697,6 +697,57 @@ pub trait Call:
         }
     }
 
+    /// Executes the closure with the state just before the transaction at the given index in the
+    /// block.
+    ///
+    /// Block changesets only record the state at block boundaries, so the state of the parent
+    /// block is loaded and the transactions prior to `tx_index` are replayed on top of it. The
+    /// closure is invoked with the database that points to the beginning of the transaction.
+    ///
+    /// Returns an error if the block doesn't exist, the index is out of bounds, or the state of
+    /// the parent block is not available.
+    ///
+    /// Note: Implementers should use a threadpool where blocking is allowed, such as
+    /// [`BlockingTaskPool`](reth_tasks::pool::BlockingTaskPool).
+    fn spawn_with_state_before_tx<F, R>(
+        &self,
+        block_id: BlockId,
+        tx_index: usize,
+        f: F,
+    ) -> impl Future<Output = Result<R, Self::Error>> + Send
+    where
+        Self: LoadBlock,
+        F: FnOnce(StateCacheDb<'_>) -> Result<R, Self::Error> + Send + 'static,
+        R: Send + 'static,
+    {
+        async move {
+            let block = self
+                .recovered_block(block_id)
+                .await?
+                .ok_or(EthApiError::HeaderNotFound(block_id))?;
+            let target_tx_hash = *block
+                .body()
+                .transactions()
+                .get(tx_index)
+                .ok_or(EthApiError::UnknownBlockOrTxIndex)?
+                .tx_hash();
+
+            let (evm_env, _) = self.evm_env_at(block.hash().into()).await?;
+
+            let this = self.clone();
+            self.spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let block_txs = block.transactions_recovered();
+
+                // replay all transactions prior to the targeted transaction
+                this.replay_transactions_until(&mut db, evm_env, block_txs, target_tx_hash)?;
+
+                f(db)
+            })
+            .await
+        }
+    }
+
     /// Replays all the transactions until the target transaction is found.
     ///
     /// All transactions before the target transaction are executed and their changes are written to
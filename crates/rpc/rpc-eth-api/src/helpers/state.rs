@@ -13,7 +13,8 @@ use reth_errors::RethError;
 use reth_evm::{ConfigureEvm, EvmEnvFor};
 use reth_rpc_convert::RpcConvert;
 use reth_rpc_eth_types::{
-    error::FromEvmError, EthApiError, PendingBlockEnv, RpcInvalidTransactionError,
+    cache::state::CachedStateProvider, error::FromEvmError, EthApiError, PendingBlockEnv,
+    RpcInvalidTransactionError,
 };
 use reth_storage_api::{
     BlockIdReader, BlockNumReader, StateProvider, StateProviderBox, StateProviderFactory,
@@ -205,8 +206,17 @@ pub trait LoadState:
     > + RpcNodeCoreExt
 {
     /// Returns the state at the given block number
+    ///
+    /// Account and storage reads are served from the cache of the canonical tip if the block is
+    /// the tip, see [`EthStateCache::state_reads`](reth_rpc_eth_types::EthStateCache::state_reads).
     fn state_at_hash(&self, block_hash: B256) -> Result<StateProviderBox, Self::Error> {
-        self.provider().history_by_block_hash(block_hash).map_err(Self::Error::from_eth_err)
+        let state =
+            self.provider().history_by_block_hash(block_hash).map_err(Self::Error::from_eth_err)?;
+        Ok(Box::new(CachedStateProvider::new(
+            state,
+            block_hash,
+            self.cache().state_reads().clone(),
+        )))
     }
 
     /// Returns the state at the given [`BlockId`] enum.
@@ -232,8 +242,19 @@ pub trait LoadState:
     }
 
     /// Returns the _latest_ state
+    ///
+    /// Account and storage reads are served from the cache of the canonical tip, see
+    /// [`EthStateCache::state_reads`](reth_rpc_eth_types::EthStateCache::state_reads).
     fn latest_state(&self) -> Result<StateProviderBox, Self::Error> {
-        self.provider().latest().map_err(Self::Error::from_eth_err)
+        let best_hash = self.provider().chain_info().map_err(Self::Error::from_eth_err)?.best_hash;
+        let state = self.provider().latest().map_err(Self::Error::from_eth_err)?;
+
+        // the latest state can't be attributed to a block if the tip moved in the meantime
+        if self.provider().chain_info().map_err(Self::Error::from_eth_err)?.best_hash != best_hash {
+            return Ok(state)
+        }
+
+        Ok(Box::new(CachedStateProvider::new(state, best_hash, self.cache().state_reads().clone())))
     }
 
     /// Returns the state at the given [`BlockId`] enum or the latest.
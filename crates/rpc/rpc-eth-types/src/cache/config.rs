@@ -4,7 +4,8 @@ use serde::{Deserialize, Serialize};
 
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_STATE_ACCOUNT_CACHE_MAX_LEN,
+    DEFAULT_STATE_STORAGE_CACHE_MAX_LEN,
 };
 
 /// Settings for the [`EthStateCache`](super::EthStateCache).
@@ -23,6 +24,14 @@ pub struct EthStateCacheConfig {
     ///
     /// Default is 1000.
     pub max_headers: u32,
+    /// Max number of accounts in the state read cache.
+    ///
+    /// Default is 10000.
+    pub max_state_accounts: u32,
+    /// Max number of storage slots in the state read cache.
+    ///
+    /// Default is 50000.
+    pub max_state_storage: u32,
     /// Max number of concurrent database requests.
     ///
     /// Default is 512.
@@ -35,6 +44,8 @@ impl Default for EthStateCacheConfig {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
+            max_state_accounts: DEFAULT_STATE_ACCOUNT_CACHE_MAX_LEN,
+            max_state_storage: DEFAULT_STATE_STORAGE_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
         }
     }
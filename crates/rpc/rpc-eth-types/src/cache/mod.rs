@@ -28,6 +28,9 @@ pub mod config;
 pub mod db;
 pub mod metrics;
 pub mod multi_consumer;
+pub mod state;
+
+use state::StateReadCache;
 
 /// The type that can send the response to a requested [`RecoveredBlock`]
 type BlockTransactionsResponseSender<T> = oneshot::Sender<ProviderResult<Option<Vec<T>>>>;
@@ -72,11 +75,13 @@ type HeaderLruCache<H, L> = MultiConsumerLruCache<B256, H, L, HeaderResponseSend
 #[derive(Debug)]
 pub struct EthStateCache<N: NodePrimitives> {
     to_service: UnboundedSender<CacheAction<N::Block, N::Receipt>>,
+    /// Account and storage reads of the canonical tip.
+    state_reads: StateReadCache,
 }
 
 impl<N: NodePrimitives> Clone for EthStateCache<N> {
     fn clone(&self) -> Self {
-        Self { to_service: self.to_service.clone() }
+        Self { to_service: self.to_service.clone(), state_reads: self.state_reads.clone() }
     }
 }
 
@@ -85,14 +90,19 @@ impl<N: NodePrimitives> EthStateCache<N> {
     fn create<Provider, Tasks>(
         provider: Provider,
         action_task_spawner: Tasks,
-        max_blocks: u32,
-        max_receipts: u32,
-        max_headers: u32,
-        max_concurrent_db_operations: usize,
+        config: EthStateCacheConfig,
     ) -> (Self, EthStateCacheService<Provider, Tasks>)
     where
         Provider: BlockReader<Block = N::Block, Receipt = N::Receipt>,
     {
+        let EthStateCacheConfig {
+            max_blocks,
+            max_receipts,
+            max_headers,
+            max_state_accounts,
+            max_state_storage,
+            max_concurrent_db_requests,
+        } = config;
         let (to_service, rx) = unbounded_channel();
         let service = EthStateCacheService {
             provider,
@@ -102,9 +112,12 @@ impl<N: NodePrimitives> EthStateCache<N> {
             action_tx: to_service.clone(),
             action_rx: UnboundedReceiverStream::new(rx),
             action_task_spawner,
-            rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_operations)),
+            rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_requests)),
+        };
+        let cache = Self {
+            to_service,
+            state_reads: StateReadCache::new(max_state_accounts, max_state_storage),
         };
-        let cache = Self { to_service };
         (cache, service)
     }
 
@@ -132,24 +145,18 @@ impl<N: NodePrimitives> EthStateCache<N> {
         Provider: BlockReader<Block = N::Block, Receipt = N::Receipt> + Clone + Unpin + 'static,
         Tasks: TaskSpawner + Clone + 'static,
     {
-        let EthStateCacheConfig {
-            max_blocks,
-            max_receipts,
-            max_headers,
-            max_concurrent_db_requests,
-        } = config;
-        let (this, service) = Self::create(
-            provider,
-            executor.clone(),
-            max_blocks,
-            max_receipts,
-            max_headers,
-            max_concurrent_db_requests,
-        );
+        let (this, service) = Self::create(provider, executor.clone(), config);
         executor.spawn_critical("eth state cache", Box::pin(service));
         this
     }
 
+    /// Returns the cache for account and storage reads of the canonical tip.
+    ///
+    /// The cache is rebound to the new tip by [`cache_new_blocks_task`].
+    pub const fn state_reads(&self) -> &StateReadCache {
+        &self.state_reads
+    }
+
     /// Requests the  [`RecoveredBlock`] for the block hash
     ///
     /// Returns `None` if the block does not exist.
@@ -789,6 +796,9 @@ pub async fn cache_new_blocks_task<St, N: NodePrimitives>(
     St: Stream<Item = CanonStateNotification<N>> + Unpin + 'static,
 {
     while let Some(event) = events.next().await {
+        // drops the state reads of the previous tip, including the reorged ones
+        eth_state_cache.state_reads.on_new_tip(event.tip().hash());
+
         if let Some(reverted) = event.reverted() {
             let chain_change = ChainChange::new(reverted);
 
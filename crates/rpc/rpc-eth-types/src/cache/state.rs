@@ -0,0 +1,338 @@
+//! Bounded LRU cache for account and storage reads against the canonical tip.
+
+use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use parking_lot::Mutex;
+use reth_errors::ProviderResult;
+use reth_primitives_traits::{Account, Bytecode};
+use reth_rpc_server_types::constants::cache::{
+    DEFAULT_STATE_ACCOUNT_CACHE_MAX_LEN, DEFAULT_STATE_STORAGE_CACHE_MAX_LEN,
+};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
+    StateProvider, StateRootProvider, StorageRootProvider,
+};
+use reth_trie::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+};
+use revm::database::BundleState;
+use schnellru::{ByLength, LruMap};
+use std::sync::Arc;
+
+/// Shared LRU caches for account and storage reads of a single block.
+///
+/// The cache only serves reads for the block it is currently bound to, which is updated with
+/// [`StateReadCache::on_new_tip`] whenever the canonical chain changes, see
+/// [`cache_new_blocks_task`](super::cache_new_blocks_task). Rebinding the cache drops
+/// all entries, so values from a reorged block are never served.
+#[derive(Debug, Clone)]
+pub struct StateReadCache {
+    inner: Arc<Mutex<StateReadCacheInner>>,
+}
+
+impl StateReadCache {
+    /// Creates a new cache with the given maximum number of accounts and storage slots.
+    pub fn new(max_accounts: u32, max_storage_slots: u32) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(StateReadCacheInner {
+                block_hash: None,
+                accounts: LruMap::new(ByLength::new(max_accounts)),
+                storage: LruMap::new(ByLength::new(max_storage_slots)),
+            })),
+        }
+    }
+
+    /// Returns the hash of the block the cached entries belong to.
+    pub fn block_hash(&self) -> Option<B256> {
+        self.inner.lock().block_hash
+    }
+
+    /// Drops all cached entries and binds the cache to the given block.
+    pub fn on_new_tip(&self, block_hash: B256) {
+        let mut inner = self.inner.lock();
+        inner.block_hash = Some(block_hash);
+        inner.accounts.clear();
+        inner.storage.clear();
+    }
+
+    /// Returns the cached account for the given block, if any.
+    fn account(&self, block_hash: B256, address: &Address) -> Option<Option<Account>> {
+        let mut inner = self.inner.lock();
+        if inner.block_hash != Some(block_hash) {
+            return None
+        }
+        inner.accounts.get(address).copied()
+    }
+
+    /// Caches the account if the cache is bound to the given block.
+    fn insert_account(&self, block_hash: B256, address: Address, account: Option<Account>) {
+        let mut inner = self.inner.lock();
+        if inner.block_hash == Some(block_hash) {
+            inner.accounts.insert(address, account);
+        }
+    }
+
+    /// Returns the cached storage value for the given block, if any.
+    fn storage(
+        &self,
+        block_hash: B256,
+        address: Address,
+        key: StorageKey,
+    ) -> Option<Option<StorageValue>> {
+        let mut inner = self.inner.lock();
+        if inner.block_hash != Some(block_hash) {
+            return None
+        }
+        inner.storage.get(&(address, key)).copied()
+    }
+
+    /// Caches the storage value if the cache is bound to the given block.
+    fn insert_storage(
+        &self,
+        block_hash: B256,
+        address: Address,
+        key: StorageKey,
+        value: Option<StorageValue>,
+    ) {
+        let mut inner = self.inner.lock();
+        if inner.block_hash == Some(block_hash) {
+            inner.storage.insert((address, key), value);
+        }
+    }
+}
+
+impl Default for StateReadCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_STATE_ACCOUNT_CACHE_MAX_LEN, DEFAULT_STATE_STORAGE_CACHE_MAX_LEN)
+    }
+}
+
+#[derive(Debug)]
+struct StateReadCacheInner {
+    /// The block the cached entries belong to.
+    block_hash: Option<B256>,
+    /// Cached accounts, `None` if the account does not exist.
+    accounts: LruMap<Address, Option<Account>, ByLength>,
+    /// Cached storage slots, `None` if the slot is not set.
+    storage: LruMap<(Address, StorageKey), Option<StorageValue>, ByLength>,
+}
+
+/// A [`StateProvider`] that serves account and storage reads from a [`StateReadCache`].
+///
+/// Reads fall through to the inner provider on a miss. Reads are only cached if the cache is bound
+/// to the same block as this provider.
+#[derive(Debug)]
+pub struct CachedStateProvider<P> {
+    /// The state provider of the block.
+    inner: P,
+    /// The hash of the block this provider reads state for.
+    block_hash: B256,
+    /// The shared read cache.
+    cache: StateReadCache,
+}
+
+impl<P> CachedStateProvider<P> {
+    /// Creates a new [`CachedStateProvider`] for the state of the given block.
+    pub const fn new(inner: P, block_hash: B256, cache: StateReadCache) -> Self {
+        Self { inner, block_hash, cache }
+    }
+}
+
+impl<P: AccountReader> AccountReader for CachedStateProvider<P> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.cache.account(self.block_hash, address) {
+            return Ok(account)
+        }
+
+        let account = self.inner.basic_account(address)?;
+        self.cache.insert_account(self.block_hash, *address, account);
+        Ok(account)
+    }
+}
+
+impl<P: StateProvider> StateProvider for CachedStateProvider<P> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) = self.cache.storage(self.block_hash, account, storage_key) {
+            return Ok(value)
+        }
+
+        let value = self.inner.storage(account, storage_key)?;
+        self.cache.insert_storage(self.block_hash, account, storage_key, value);
+        Ok(value)
+    }
+}
+
+impl<P: BytecodeReader> BytecodeReader for CachedStateProvider<P> {
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        self.inner.bytecode_by_hash(code_hash)
+    }
+}
+
+impl<P: BlockHashReader> BlockHashReader for CachedStateProvider<P> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.inner.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.inner.canonical_hashes_range(start, end)
+    }
+}
+
+impl<P: StateRootProvider> StateRootProvider for CachedStateProvider<P> {
+    fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
+        self.inner.state_root(hashed_state)
+    }
+
+    fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
+        self.inner.state_root_from_nodes(input)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        hashed_state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_with_updates(hashed_state)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_from_nodes_with_updates(input)
+    }
+}
+
+impl<P: StorageRootProvider> StorageRootProvider for CachedStateProvider<P> {
+    fn storage_root(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        self.inner.storage_root(address, hashed_storage)
+    }
+
+    fn storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageProof> {
+        self.inner.storage_proof(address, slot, hashed_storage)
+    }
+
+    fn storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        self.inner.storage_multiproof(address, slots, hashed_storage)
+    }
+}
+
+impl<P: StateProofProvider> StateProofProvider for CachedStateProvider<P> {
+    fn proof(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        self.inner.proof(input, address, slots)
+    }
+
+    fn multiproof(
+        &self,
+        input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        self.inner.multiproof(input, targets)
+    }
+
+    fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        self.inner.witness(input, target)
+    }
+}
+
+impl<P: HashedPostStateProvider> HashedPostStateProvider for CachedStateProvider<P> {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.inner.hashed_post_state(bundle_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cache::cache_new_blocks_task, EthStateCache};
+    use alloy_primitives::U256;
+    use reth_chain_state::CanonStateNotification;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_execution_types::Chain;
+    use reth_primitives_traits::RecoveredBlock;
+    use reth_storage_api::noop::NoopProvider;
+
+    #[test]
+    fn cache_is_bound_to_tip() {
+        let cache = StateReadCache::new(2, 2);
+        let tip = B256::with_last_byte(1);
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, balance: U256::from(1), bytecode_hash: None };
+
+        // not bound to any block yet
+        cache.insert_account(tip, address, Some(account));
+        assert_eq!(cache.account(tip, &address), None);
+
+        cache.on_new_tip(tip);
+        cache.insert_account(tip, address, Some(account));
+        assert_eq!(cache.account(tip, &address), Some(Some(account)));
+        assert_eq!(cache.account(B256::with_last_byte(2), &address), None);
+
+        // a new tip drops all entries
+        cache.on_new_tip(B256::with_last_byte(2));
+        assert_eq!(cache.account(tip, &address), None);
+        assert_eq!(cache.account(B256::with_last_byte(2), &address), None);
+    }
+
+    #[test]
+    fn cached_provider_populates_cache() {
+        let cache = StateReadCache::new(2, 2);
+        let tip = B256::with_last_byte(1);
+        cache.on_new_tip(tip);
+
+        let provider = CachedStateProvider::new(NoopProvider::default(), tip, cache.clone());
+        let address = Address::with_last_byte(1);
+        assert_eq!(provider.basic_account(&address).unwrap(), None);
+        assert_eq!(provider.storage(address, B256::ZERO).unwrap(), None);
+
+        assert_eq!(cache.account(tip, &address), Some(None));
+        assert_eq!(cache.storage(tip, address, B256::ZERO), Some(None));
+    }
+
+    #[test]
+    fn new_blocks_rebind_cache() {
+        let (cache, _service) = EthStateCache::<EthPrimitives>::create(
+            NoopProvider::default(),
+            reth_tasks::TokioTaskExecutor::default(),
+            Default::default(),
+        );
+        assert_eq!(cache.state_reads().block_hash(), None);
+
+        let block = RecoveredBlock::<reth_ethereum_primitives::Block>::default();
+        let tip = block.hash();
+        let notification = CanonStateNotification::Commit {
+            new: Arc::new(Chain::from_block(block, Default::default(), None)),
+        };
+        futures::executor::block_on(cache_new_blocks_task(
+            cache.clone(),
+            futures::stream::iter([notification]),
+        ));
+        assert_eq!(cache.state_reads().block_hash(), Some(tip));
+    }
+}
@@ -3,7 +3,7 @@
 use crate::EthApiError;
 use alloy_consensus::{ReceiptEnvelope, Transaction};
 use alloy_eips::eip7840::BlobParams;
-use alloy_primitives::{Address, TxKind};
+use alloy_primitives::{Address, TxKind, B256};
 use alloy_rpc_types_eth::{Log, TransactionReceipt};
 use reth_chainspec::EthChainSpec;
 use reth_ethereum_primitives::Receipt;
@@ -50,6 +50,17 @@ where
     }
 }
 
+/// Predicts the address of a contract deployed via `CREATE2`.
+///
+/// Transactions can only deploy contracts via `CREATE`, for which the contract address is derived
+/// from the sender and nonce in [`build_receipt`]. This helper exposes the [EIP-1014] derivation
+/// for tooling that deploys contracts through a factory.
+///
+/// [EIP-1014]: https://eips.ethereum.org/EIPS/eip-1014
+pub fn predict_create2_address(deployer: Address, salt: B256, init_code_hash: B256) -> Address {
+    deployer.create2(salt, init_code_hash)
+}
+
 /// Converter for Ethereum receipts.
 #[derive(derive_more::Debug)]
 pub struct EthReceiptConverter<
@@ -116,3 +127,26 @@ where
         Ok(receipts)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, bytes, keccak256};
+
+    // <https://eips.ethereum.org/EIPS/eip-1014#examples>
+    #[test]
+    fn create2_address_eip1014_vectors() {
+        assert_eq!(
+            predict_create2_address(Address::ZERO, B256::ZERO, keccak256(bytes!("00"))),
+            address!("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38")
+        );
+        assert_eq!(
+            predict_create2_address(
+                address!("0x00000000000000000000000000000000deadbeef"),
+                b256!("0x00000000000000000000000000000000000000000000000000000000cafebabe"),
+                keccak256(bytes!("deadbeef")),
+            ),
+            address!("0x60f3f640a8508fC6a86d45DF051962668E1e8AC7")
+        );
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! Transaction wrapper that labels transaction with its origin.
 
-use alloy_primitives::B256;
+use alloy_primitives::{Address, B256};
 use alloy_rpc_types_eth::TransactionInfo;
 use reth_ethereum_primitives::TransactionSigned;
 use reth_primitives_traits::{NodePrimitives, Recovered, SignedTransaction};
 use reth_rpc_convert::{RpcConvert, RpcTransaction};
+use serde::{
+    ser::{SerializeMap, SerializeStruct},
+    Serialize, Serializer,
+};
+use std::fmt::{self, Write as _};
 
 /// Represents from where a transaction was fetched.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -38,6 +43,27 @@ impl<T: SignedTransaction> TransactionSource<T> {
         self.into()
     }
 
+    /// Returns the base fee of the block that includes the transaction, if any.
+    ///
+    /// This is `None` for pending transactions and transactions of pre-London blocks.
+    pub const fn base_fee(&self) -> Option<u64> {
+        match self {
+            Self::Pool(_) => None,
+            Self::Block { base_fee, .. } => *base_fee,
+        }
+    }
+
+    /// Returns the effective gas price of the transaction.
+    ///
+    /// This is computed from the base fee of the including block. If there is no base fee, this
+    /// falls back to the max fee per gas, which is the gas price for legacy transactions.
+    pub fn effective_gas_price(&self) -> u128 {
+        match self {
+            Self::Pool(tx) => tx.effective_gas_price(None),
+            Self::Block { transaction, base_fee, .. } => transaction.effective_gas_price(*base_fee),
+        }
+    }
+
     /// Conversion into network specific transaction type.
     pub fn into_transaction<Builder>(
         self,
@@ -94,3 +120,336 @@ impl<T> From<TransactionSource<T>> for Recovered<T> {
         }
     }
 }
+
+/// Fields of an RPC transaction that hold addresses.
+const ADDRESS_FIELDS: [&str; 3] = ["from", "to", "creates"];
+
+/// Wrapper around an RPC transaction that serializes its `from`, `to` and `creates` addresses
+/// with an [EIP-55](https://eips.ethereum.org/EIPS/eip-55) checksum.
+///
+/// All other fields, including raw bytes, are serialized exactly as by the wrapped type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksummedTransaction<T>(pub T);
+
+impl<T: Serialize> Serialize for ChecksummedTransaction<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(ChecksumSerializer { inner: serializer, target: ChecksumTarget::Fields })
+    }
+}
+
+/// Value that is serialized with an EIP-55 checksum if it's an address.
+struct Checksummed<'a, T: ?Sized>(&'a T);
+
+impl<T: ?Sized + Serialize> Serialize for Checksummed<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(ChecksumSerializer { inner: serializer, target: ChecksumTarget::Address })
+    }
+}
+
+/// What a [`ChecksumSerializer`] checksums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChecksumTarget {
+    /// The [`ADDRESS_FIELDS`] of the serialized struct or map.
+    Fields,
+    /// The serialized value itself.
+    Address,
+}
+
+/// Serializer that forwards everything to the inner serializer, checksumming the addresses of
+/// its [`ChecksumTarget`] on the way.
+struct ChecksumSerializer<S> {
+    inner: S,
+    target: ChecksumTarget,
+}
+
+macro_rules! forward_serialize {
+    ($($method:ident($($arg:ident: $ty:ty),*) -> $ok:ty;)*) => {
+        $(
+            fn $method(self, $($arg: $ty),*) -> Result<$ok, S::Error> {
+                self.inner.$method($($arg),*)
+            }
+        )*
+    };
+}
+
+impl<S: Serializer> Serializer for ChecksumSerializer<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = S::SerializeSeq;
+    type SerializeTuple = S::SerializeTuple;
+    type SerializeTupleStruct = S::SerializeTupleStruct;
+    type SerializeTupleVariant = S::SerializeTupleVariant;
+    type SerializeMap = ChecksumFields<S::SerializeMap>;
+    type SerializeStruct = ChecksumFields<S::SerializeStruct>;
+    type SerializeStructVariant = S::SerializeStructVariant;
+
+    forward_serialize! {
+        serialize_bool(v: bool) -> S::Ok;
+        serialize_i8(v: i8) -> S::Ok;
+        serialize_i16(v: i16) -> S::Ok;
+        serialize_i32(v: i32) -> S::Ok;
+        serialize_i64(v: i64) -> S::Ok;
+        serialize_i128(v: i128) -> S::Ok;
+        serialize_u8(v: u8) -> S::Ok;
+        serialize_u16(v: u16) -> S::Ok;
+        serialize_u32(v: u32) -> S::Ok;
+        serialize_u64(v: u64) -> S::Ok;
+        serialize_u128(v: u128) -> S::Ok;
+        serialize_f32(v: f32) -> S::Ok;
+        serialize_f64(v: f64) -> S::Ok;
+        serialize_char(v: char) -> S::Ok;
+        serialize_bytes(v: &[u8]) -> S::Ok;
+        serialize_none() -> S::Ok;
+        serialize_unit() -> S::Ok;
+        serialize_unit_struct(name: &'static str) -> S::Ok;
+        serialize_unit_variant(name: &'static str, index: u32, variant: &'static str) -> S::Ok;
+        serialize_seq(len: Option<usize>) -> S::SerializeSeq;
+        serialize_tuple(len: usize) -> S::SerializeTuple;
+        serialize_tuple_struct(name: &'static str, len: usize) -> S::SerializeTupleStruct;
+        serialize_tuple_variant(
+            name: &'static str,
+            index: u32,
+            variant: &'static str,
+            len: usize
+        ) -> S::SerializeTupleVariant;
+        serialize_struct_variant(
+            name: &'static str,
+            index: u32,
+            variant: &'static str,
+            len: usize
+        ) -> S::SerializeStructVariant;
+    }
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        if self.target == ChecksumTarget::Address &&
+            let Ok(address) = v.parse::<Address>()
+        {
+            return self.inner.serialize_str(&address.to_checksum(None))
+        }
+        self.inner.serialize_str(v)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<S::Ok, S::Error> {
+        match self.target {
+            ChecksumTarget::Fields => self.inner.serialize_some(value),
+            ChecksumTarget::Address => self.inner.serialize_some(&Checksummed(value)),
+        }
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        match self.target {
+            ChecksumTarget::Fields => self.inner.serialize_newtype_struct(name, value),
+            ChecksumTarget::Address => {
+                self.inner.serialize_newtype_struct(name, &Checksummed(value))
+            }
+        }
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_newtype_variant(name, index, variant, value)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        let enabled = self.target == ChecksumTarget::Fields;
+        Ok(ChecksumFields { inner: self.inner.serialize_map(len)?, enabled, checksum_value: false })
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        let enabled = self.target == ChecksumTarget::Fields;
+        Ok(ChecksumFields {
+            inner: self.inner.serialize_struct(name, len)?,
+            enabled,
+            checksum_value: false,
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// Struct or map serializer that checksums the values of the [`ADDRESS_FIELDS`], if enabled.
+///
+/// Flattened fields are serialized as map entries, so both are handled.
+struct ChecksumFields<M> {
+    inner: M,
+    enabled: bool,
+    /// Whether the value of the last serialized map key is checksummed.
+    checksum_value: bool,
+}
+
+impl<M: SerializeMap> SerializeMap for ChecksumFields<M> {
+    type Ok = M::Ok;
+    type Error = M::Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), M::Error> {
+        self.checksum_value = self.enabled && is_address_field(key);
+        self.inner.serialize_key(key)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), M::Error> {
+        if std::mem::take(&mut self.checksum_value) {
+            self.inner.serialize_value(&Checksummed(value))
+        } else {
+            self.inner.serialize_value(value)
+        }
+    }
+
+    fn end(self) -> Result<M::Ok, M::Error> {
+        self.inner.end()
+    }
+}
+
+impl<M: SerializeStruct> SerializeStruct for ChecksumFields<M> {
+    type Ok = M::Ok;
+    type Error = M::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), M::Error> {
+        if self.enabled && ADDRESS_FIELDS.contains(&key) {
+            self.inner.serialize_field(key, &Checksummed(value))
+        } else {
+            self.inner.serialize_field(key, value)
+        }
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), M::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> Result<M::Ok, M::Error> {
+        self.inner.end()
+    }
+}
+
+/// Returns `true` if the map key serializes to one of the [`ADDRESS_FIELDS`].
+fn is_address_field<T: ?Sized + Serialize>(key: &T) -> bool {
+    struct Key<'a, T: ?Sized>(&'a T);
+
+    impl<T: ?Sized + Serialize> fmt::Display for Key<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.serialize(f)
+        }
+    }
+
+    let mut name = String::new();
+    write!(name, "{}", Key(key)).is_ok() && ADDRESS_FIELDS.contains(&name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes};
+    use serde_json::json;
+
+    #[test]
+    fn checksummed_transaction() {
+        let tx = json!({
+            "from": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "to": "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359",
+            "input": bytes!("abcdef"),
+            "hash": B256::ZERO,
+        });
+
+        let checksummed = serde_json::to_value(ChecksummedTransaction(&tx)).unwrap();
+        assert_eq!(checksummed["from"], "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert_eq!(checksummed["to"], "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359");
+        assert_eq!(checksummed["input"], tx["input"]);
+        assert_eq!(checksummed["hash"], tx["hash"]);
+
+        // contract creations have no recipient
+        let creation =
+            json!({ "from": address!("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"), "to": null });
+        let checksummed = serde_json::to_value(ChecksummedTransaction(&creation)).unwrap();
+        assert_eq!(checksummed["to"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn checksummed_rpc_transaction() {
+        use alloy_consensus::{Signed, TxEnvelope, TxLegacy};
+        use alloy_primitives::{Signature, TxKind};
+        use alloy_rpc_types_eth::Transaction;
+
+        let from = address!("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+        let to = address!("0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359");
+        let tx = Transaction {
+            inner: Recovered::new_unchecked(
+                TxEnvelope::Legacy(Signed::new_unchecked(
+                    TxLegacy { to: TxKind::Call(to), ..Default::default() },
+                    Signature::test_signature(),
+                    B256::ZERO,
+                )),
+                from,
+            ),
+            block_hash: Some(B256::ZERO),
+            block_number: Some(1),
+            transaction_index: Some(0),
+            effective_gas_price: None,
+        };
+
+        let plain = serde_json::to_value(&tx).unwrap();
+        let checksummed = serde_json::to_value(ChecksummedTransaction(&tx)).unwrap();
+        assert_eq!(checksummed["from"], "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert_eq!(checksummed["to"], "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359");
+
+        // everything else is serialized as is
+        let (mut plain, mut checksummed) =
+            (plain.as_object().unwrap().clone(), checksummed.as_object().unwrap().clone());
+        for field in ADDRESS_FIELDS {
+            plain.remove(field);
+            checksummed.remove(field);
+        }
+        assert_eq!(checksummed, plain);
+    }
+
+    #[test]
+    fn access_list_serialization() {
+        use alloy_consensus::{Signed, TxEip1559, TxEnvelope, TxLegacy};
+        use alloy_primitives::Signature;
+        use alloy_rpc_types_eth::Transaction;
+
+        let rpc_tx = |inner: TxEnvelope| {
+            let tx = Transaction {
+                inner: Recovered::new_unchecked(inner, Address::ZERO),
+                block_hash: None,
+                block_number: None,
+                transaction_index: None,
+                effective_gas_price: None,
+            };
+            serde_json::to_value(tx).unwrap()
+        };
+
+        // typed transactions always include the access list, even if it's empty
+        let typed = rpc_tx(TxEnvelope::Eip1559(Signed::new_unchecked(
+            TxEip1559::default(),
+            Signature::test_signature(),
+            B256::ZERO,
+        )));
+        assert_eq!(typed["accessList"], json!([]));
+
+        // legacy transactions don't have an access list
+        let legacy = rpc_tx(TxEnvelope::Legacy(Signed::new_unchecked(
+            TxLegacy::default(),
+            Signature::test_signature(),
+            B256::ZERO,
+        )));
+        assert!(legacy.get("accessList").is_none());
+    }
+}
@@ -116,6 +116,12 @@ pub mod cache {
     /// Default cache size for the header cache: 1000 headers.
     pub const DEFAULT_HEADER_CACHE_MAX_LEN: u32 = 1000;
 
+    /// Default cache size for the state account cache: 10000 accounts.
+    pub const DEFAULT_STATE_ACCOUNT_CACHE_MAX_LEN: u32 = 10_000;
+
+    /// Default cache size for the state storage cache: 50000 storage slots.
+    pub const DEFAULT_STATE_STORAGE_CACHE_MAX_LEN: u32 = 50_000;
+
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
 }
@@ -5,7 +5,7 @@ use std::fmt;
 use alloy_eips::BlockId;
 use alloy_rpc_types_engine::PayloadError;
 use jsonrpsee_core::RpcResult;
-use reth_errors::ConsensusError;
+use reth_errors::{ConsensusError, ProviderError, ProviderResult};
 
 /// Helper trait to easily convert various `Result` types into [`RpcResult`]
 pub trait ToRpcResult<Ok, Err>: Sized {
@@ -108,6 +108,38 @@ impl_to_rpc_result!(reth_errors::RethError);
 impl_to_rpc_result!(reth_errors::ProviderError);
 impl_to_rpc_result!(reth_network_api::NetworkError);
 
+/// Helper trait to convert a [`ProviderResult`] of data that may not exist into an [`RpcResult`].
+pub trait ToRpcOptionResult<Ok>: Sized {
+    /// Converts the result into an [`RpcResult`], mapping errors signaling that the requested
+    /// data does not exist to `None`.
+    ///
+    /// Only errors for genuinely missing data, see [`ProviderError::is_not_found`], result in
+    /// `None`. Every other error is converted with [`provider_rpc_err`].
+    fn to_rpc_option_result(self) -> RpcResult<Option<Ok>>;
+}
+
+impl<Ok> ToRpcOptionResult<Ok> for ProviderResult<Option<Ok>> {
+    fn to_rpc_option_result(self) -> RpcResult<Option<Ok>> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(err) if err.is_not_found() => Ok(None),
+            Err(err) => Err(provider_rpc_err(&err)),
+        }
+    }
+}
+
+/// Converts a [`ProviderError`] into an internal JSON-RPC error.
+///
+/// Gaps in the stored data, see [`ProviderError::is_gap`], are reported as such, since they
+/// indicate an inconsistent database rather than a failed request.
+pub fn provider_rpc_err(err: &ProviderError) -> jsonrpsee_types::error::ErrorObject<'static> {
+    if err.is_gap() {
+        internal_rpc_err(format!("missing data in storage: {err}"))
+    } else {
+        internal_rpc_err(err.to_string())
+    }
+}
+
 /// Constructs an invalid params JSON-RPC error.
 pub fn invalid_params_rpc_err(
     msg: impl Into<String>,
@@ -182,4 +214,22 @@ mod tests {
         let val = rpc_res.unwrap();
         assert_eq!(val, 100);
     }
+
+    #[test]
+    fn can_convert_provider_option_result() {
+        let res: ProviderResult<Option<u64>> = Err(ProviderError::BestBlockNotFound);
+        let err = res.to_rpc_option_result().unwrap_err();
+        assert_eq!(err.code(), jsonrpsee_types::error::INTERNAL_ERROR_CODE);
+
+        let res: ProviderResult<Option<u64>> = Err(ProviderError::HeaderNotFound(1.into()));
+        assert_eq!(res.to_rpc_option_result().unwrap(), None);
+
+        let res: ProviderResult<Option<u64>> = Err(ProviderError::BlockBodyIndicesNotFound(1));
+        let err = res.to_rpc_option_result().unwrap_err();
+        assert_eq!(err.code(), jsonrpsee_types::error::INTERNAL_ERROR_CODE);
+        assert_eq!(err.message(), "missing data in storage: block meta not found for block #1");
+
+        let res: ProviderResult<Option<u64>> = Ok(Some(1));
+        assert_eq!(res.to_rpc_option_result().unwrap(), Some(1));
+    }
 }
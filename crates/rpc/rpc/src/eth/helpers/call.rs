@@ -40,3 +40,75 @@ where
     Rpc: RpcConvert<Primitives = N::Primitives, Error = EthApiError, Evm = N::Evm>,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, Transaction as _, TxLegacy};
+    use alloy_primitives::{Address, TxKind, U256};
+    use reth_ethereum_primitives::{Block, BlockBody, Transaction};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        ChainSpecProvider,
+    };
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+    use reth_transaction_pool::test_utils::testing_pool;
+    use revm::Database as _;
+
+    #[tokio::test]
+    async fn spawn_with_state_before_tx() {
+        let mut rng = generators::rng();
+        let key_pair = generators::generate_key(&mut rng);
+        let transactions: Vec<_> = (0..3)
+            .map(|nonce| {
+                sign_tx_with_key_pair(
+                    key_pair,
+                    Transaction::Legacy(TxLegacy {
+                        nonce,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(Address::random()),
+                        ..Default::default()
+                    }),
+                )
+            })
+            .collect();
+        let sender = transactions[0].recover_signer().unwrap();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::ZERO));
+        let parent = Header { gas_limit: 30_000_000, ..Default::default() };
+        let parent_hash = parent.hash_slow();
+        provider.add_header(parent_hash, parent);
+        let block = Block {
+            header: Header { number: 1, parent_hash, gas_limit: 30_000_000, ..Default::default() },
+            body: BlockBody { transactions: transactions.clone(), ..Default::default() },
+        };
+        let block_hash = block.header.hash_slow();
+        provider.add_block(block_hash, block);
+
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let eth_api =
+            EthApi::builder(provider, testing_pool(), NoopNetwork::default(), evm_config).build();
+
+        // the state is positioned right before the transaction, so the sender's nonce is the
+        // nonce of the transaction
+        for (index, tx) in transactions.iter().enumerate() {
+            let nonce = eth_api
+                .spawn_with_state_before_tx(block_hash.into(), index, move |mut db| {
+                    Ok(db.basic(sender)?.map(|account| account.nonce))
+                })
+                .await
+                .unwrap();
+            assert_eq!(nonce, Some(tx.nonce()));
+        }
+
+        let err = eth_api
+            .spawn_with_state_before_tx(block_hash.into(), transactions.len(), |_| Ok(()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::UnknownBlockOrTxIndex));
+    }
+}
@@ -72,17 +72,24 @@ fn senders(c: &mut Criterion, runtime: &Runtime) {
 
     let db = setup::txs_testdata(DEFAULT_NUM_BLOCKS);
 
-    let stage = SenderRecoveryStage { commit_threshold: DEFAULT_NUM_BLOCKS };
-
-    measure_stage(
-        runtime,
-        &mut group,
-        &db,
-        setup::stage_unwind,
-        stage,
-        0..=DEFAULT_NUM_BLOCKS,
-        "SendersRecovery".to_string(),
-    );
+    for max_threads in [None, Some(1), Some(2), Some(4), Some(8)] {
+        let stage = SenderRecoveryStage { commit_threshold: DEFAULT_NUM_BLOCKS, max_threads };
+
+        let label = match max_threads {
+            Some(threads) => format!("SendersRecovery-{threads}-threads"),
+            None => "SendersRecovery".to_string(),
+        };
+
+        measure_stage(
+            runtime,
+            &mut group,
+            &db,
+            setup::stage_unwind,
+            stage,
+            0..=DEFAULT_NUM_BLOCKS,
+            label,
+        );
+    }
 }
 
 fn transaction_lookup(c: &mut Criterion, runtime: &Runtime) {
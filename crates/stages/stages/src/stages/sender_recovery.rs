@@ -20,7 +20,11 @@ use reth_stages_api::{
     StageId, UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
-use std::{fmt::Debug, ops::Range, sync::mpsc};
+use std::{
+    fmt::Debug,
+    ops::Range,
+    sync::{mpsc, Arc},
+};
 use thiserror::Error;
 use tracing::*;
 
@@ -43,18 +47,21 @@ pub struct SenderRecoveryStage {
     /// The size of inserted items after which the control
     /// flow will be returned to the pipeline for commit
     pub commit_threshold: u64,
+    /// The maximum number of threads used to recover senders. Uses the global rayon pool if
+    /// `None`.
+    pub max_threads: Option<usize>,
 }
 
 impl SenderRecoveryStage {
     /// Create new instance of [`SenderRecoveryStage`].
     pub const fn new(config: SenderRecoveryConfig) -> Self {
-        Self { commit_threshold: config.commit_threshold }
+        Self { commit_threshold: config.commit_threshold, max_threads: config.max_threads }
     }
 }
 
 impl Default for SenderRecoveryStage {
     fn default() -> Self {
-        Self { commit_threshold: 5_000_000 }
+        Self { commit_threshold: 5_000_000, max_threads: None }
     }
 }
 
@@ -106,7 +113,20 @@ where
             .map(|start| start..std::cmp::min(start + BATCH_SIZE as u64, tx_range.end))
             .collect::<Vec<Range<u64>>>();
 
-        let tx_batch_sender = setup_range_recovery(provider);
+        // Use a dedicated pool if the number of recovery threads is limited
+        let thread_pool = self
+            .max_threads
+            .map(|num_threads| {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(num_threads)
+                    .thread_name(|i| format!("sender-recovery-{i:02}"))
+                    .build()
+            })
+            .transpose()
+            .map_err(|err| StageError::Fatal(err.into()))?
+            .map(Arc::new);
+
+        let tx_batch_sender = setup_range_recovery(provider, thread_pool);
 
         for range in batch {
             recover_range(range, provider, tx_batch_sender.clone(), &mut senders_cursor)?;
@@ -231,9 +251,10 @@ where
 
 /// Spawns a thread to handle the recovery of transaction senders for
 /// specified chunks of a given batch. It processes incoming ranges, fetching and recovering
-/// transactions in parallel using global rayon pool
+/// transactions in parallel using the given rayon pool, or the global one if `None`.
 fn setup_range_recovery<Provider>(
     provider: &Provider,
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
 ) -> mpsc::Sender<Vec<(Range<u64>, RecoveryResultSender)>>
 where
     Provider: DBProvider
@@ -275,10 +296,10 @@ where
                     }
                 };
 
-                // Spawn the task onto the global rayon pool
+                // Spawn the task onto the rayon pool
                 // This task will send the results through the channel after it has read the
                 // transaction and calculated the sender.
-                rayon::spawn(move || {
+                let recover_chunk = move || {
                     let mut rlp_buf = Vec::with_capacity(128);
                     for (number, tx) in chunk {
                         let res = tx
@@ -297,7 +318,11 @@ where
                             break
                         }
                     }
-                });
+                };
+                match &thread_pool {
+                    Some(pool) => pool.spawn(recover_chunk),
+                    None => rayon::spawn(recover_chunk),
+                }
             }
         }
     });
@@ -630,7 +655,7 @@ mod tests {
         }
 
         fn stage(&self) -> Self::S {
-            SenderRecoveryStage { commit_threshold: self.threshold }
+            SenderRecoveryStage { commit_threshold: self.threshold, max_threads: None }
         }
     }
 
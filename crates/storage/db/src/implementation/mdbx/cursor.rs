@@ -14,7 +14,7 @@ use reth_db_api::{
     table::{Compress, Decode, Decompress, DupSort, Encode, Table},
 };
 use reth_libmdbx::{Error as MDBXError, TransactionKind, WriteFlags, RO, RW};
-use reth_storage_errors::db::{DatabaseErrorInfo, DatabaseWriteError, DatabaseWriteOperation};
+use reth_storage_errors::db::{DatabaseWriteError, DatabaseWriteOperation};
 use std::{borrow::Cow, collections::Bound, marker::PhantomData, ops::RangeBounds, sync::Arc};
 
 /// Read only Cursor.
@@ -63,15 +63,13 @@ impl<K: TransactionKind, T: Table> Cursor<K, T> {
 
 /// Decodes a `(key, value)` pair from the database.
 #[expect(clippy::type_complexity)]
-pub fn decode<T>(
-    res: Result<Option<(Cow<'_, [u8]>, Cow<'_, [u8]>)>, impl Into<DatabaseErrorInfo>>,
-) -> PairResult<T>
+pub fn decode<T>(res: Result<Option<(Cow<'_, [u8]>, Cow<'_, [u8]>)>, MDBXError>) -> PairResult<T>
 where
     T: Table,
     T::Key: Decode,
     T::Value: Decompress,
 {
-    res.map_err(|e| DatabaseError::Read(e.into()))?.map(decoder::<T>).transpose()
+    res.map_err(read_error)?.map(decoder::<T>).transpose()
 }
 
 /// Some types don't support compression (eg. B256), and we don't want to be copying them to the
@@ -170,11 +168,7 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
 
     /// Returns the next `value` of a duplicate `key`.
     fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
-        self.inner
-            .next_dup()
-            .map_err(|e| DatabaseError::Read(e.into()))?
-            .map(decode_value::<T>)
-            .transpose()
+        self.inner.next_dup().map_err(read_error)?.map(decode_value::<T>).transpose()
     }
 
     fn seek_by_key_subkey(
@@ -184,7 +178,7 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
     ) -> ValueOnlyResult<T> {
         self.inner
             .get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
-            .map_err(|e| DatabaseError::Read(e.into()))?
+            .map_err(read_error)?
             .map(decode_one::<T>)
             .transpose()
     }
@@ -205,14 +199,14 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
                 let key: Vec<u8> = key.encode().into();
                 self.inner
                     .get_both_range(key.as_ref(), subkey.encode().as_ref())
-                    .map_err(|e| DatabaseError::Read(e.into()))?
+                    .map_err(read_error)?
                     .map(|val| decoder::<T>((Cow::Owned(key), val)))
             }
             (Some(key), None) => {
                 let key: Vec<u8> = key.encode().into();
                 self.inner
                     .set(key.as_ref())
-                    .map_err(|e| DatabaseError::Read(e.into()))?
+                    .map_err(read_error)?
                     .map(|val| decoder::<T>((Cow::Owned(key), val)))
             }
             (None, Some(subkey)) => {
@@ -220,10 +214,10 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
                     let key: Vec<u8> = key.encode().into();
                     self.inner
                         .get_both_range(key.as_ref(), subkey.encode().as_ref())
-                        .map_err(|e| DatabaseError::Read(e.into()))?
+                        .map_err(read_error)?
                         .map(|val| decoder::<T>((Cow::Owned(key), val)))
                 } else {
-                    Some(Err(DatabaseError::Read(MDBXError::NotFound.into())))
+                    Some(Err(read_error(MDBXError::NotFound)))
                 }
             }
             (None, None) => self.first().transpose(),
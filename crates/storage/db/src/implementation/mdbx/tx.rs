@@ -289,7 +289,7 @@ impl<K: TransactionKind> DbTx for Tx<K> {
     ) -> Result<Option<T::Value>, DatabaseError> {
         self.execute_with_operation_metric::<T, _>(Operation::Get, None, |tx| {
             tx.get(self.get_dbi::<T>()?, key.as_ref())
-                .map_err(|e| DatabaseError::Read(e.into()))?
+                .map_err(read_error)?
                 .map(decode_one::<T>)
                 .transpose()
         })
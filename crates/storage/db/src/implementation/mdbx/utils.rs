@@ -4,8 +4,19 @@ use crate::{
     table::{Decode, Decompress, Table, TableRow},
     DatabaseError,
 };
+use reth_libmdbx::Error as MDBXError;
+use reth_storage_errors::db::DatabaseErrorInfo;
 use std::borrow::Cow;
 
+/// Converts an error returned by a read into a [`DatabaseError::Read`].
+///
+/// `MDBX_BUSY` is reported as transient, since the read is expected to succeed once the
+/// conflicting operation is done.
+pub(crate) fn read_error(error: MDBXError) -> DatabaseError {
+    let transient = matches!(error, MDBXError::Busy);
+    DatabaseError::Read(DatabaseErrorInfo::from(error).with_transient(transient))
+}
+
 /// Helper function to decode a `(key, value)` pair.
 pub(crate) fn decoder<'a, T>(
     (k, v): (Cow<'a, [u8]>, Cow<'a, [u8]>),
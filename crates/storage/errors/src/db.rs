@@ -50,6 +50,57 @@ pub enum DatabaseError {
     Other(String),
 }
 
+impl DatabaseError {
+    /// Returns the error information reported by the database backend, if any.
+    pub const fn info(&self) -> Option<&DatabaseErrorInfo> {
+        match self {
+            Self::Open(info) |
+            Self::CreateTable(info) |
+            Self::Read(info) |
+            Self::Delete(info) |
+            Self::Commit(info) |
+            Self::InitTx(info) |
+            Self::InitCursor(info) |
+            Self::Stats(info) => Some(info),
+            Self::Write(err) => Some(&err.info),
+            Self::Decode | Self::LogLevelUnavailable(_) | Self::Other(_) => None,
+        }
+    }
+
+    /// Returns the error code reported by the database backend, if any.
+    pub const fn code(&self) -> Option<i32> {
+        match self.info() {
+            Some(info) => Some(info.code),
+            None => None,
+        }
+    }
+
+    /// Classifies this error, see [`DatabaseErrorKind`].
+    pub const fn kind(&self) -> DatabaseErrorKind {
+        match self.info() {
+            Some(info) if info.is_transient() => DatabaseErrorKind::Transient,
+            _ => DatabaseErrorKind::Permanent,
+        }
+    }
+
+    /// Returns `true` if the error is [`DatabaseErrorKind::Transient`].
+    pub const fn is_transient(&self) -> bool {
+        matches!(self.kind(), DatabaseErrorKind::Transient)
+    }
+}
+
+/// Classification of a [`DatabaseError`] by whether retrying the operation can succeed.
+///
+/// The classification is up to the database backend, see [`DatabaseErrorInfo::is_transient`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DatabaseErrorKind {
+    /// The error is caused by a benign condition and the operation is expected to succeed on
+    /// retry.
+    Transient,
+    /// Retrying the operation will fail with the same error.
+    Permanent,
+}
+
 /// Common error struct to propagate implementation-specific error information.
 #[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
 #[display("{message} ({code})")]
@@ -58,6 +109,27 @@ pub struct DatabaseErrorInfo {
     pub message: Box<str>,
     /// Error code.
     pub code: i32,
+    /// Whether the database backend considers the error transient.
+    transient: bool,
+}
+
+impl DatabaseErrorInfo {
+    /// Creates a new permanent error with the given message and code.
+    pub fn new(message: impl Into<Box<str>>, code: i32) -> Self {
+        Self { message: message.into(), code, transient: false }
+    }
+
+    /// Returns `true` if the database backend considers the error transient, i.e. retrying the
+    /// operation is expected to succeed.
+    pub const fn is_transient(&self) -> bool {
+        self.transient
+    }
+
+    /// Sets whether the error is transient.
+    pub const fn with_transient(mut self, transient: bool) -> Self {
+        self.transient = transient;
+        self
+    }
 }
 
 impl<E> From<E> for DatabaseErrorInfo
@@ -66,7 +138,7 @@ where
 {
     #[inline]
     fn from(error: E) -> Self {
-        Self { message: error.to_string().into(), code: error.into() }
+        Self::new(error.to_string(), error.into())
     }
 }
 
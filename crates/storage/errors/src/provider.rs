@@ -29,6 +29,16 @@ pub enum ProviderError {
     /// Error when recovering the sender for a transaction
     #[error("failed to recover sender for transaction")]
     SenderRecoveryError,
+    /// The transaction or sender table has a gap within the transaction range of a block.
+    #[error("block #{block_number} is missing transaction #{tx_number} in the {table} table")]
+    BlockTransactionGap {
+        /// The block whose transaction range has the gap.
+        block_number: BlockNumber,
+        /// The first missing transaction number.
+        tx_number: TxNumber,
+        /// The name of the table with the gap.
+        table: &'static str,
+    },
     /// The header number was not found for the given block hash.
     #[error("block hash {_0} does not exist in Headers table")]
     BlockHashNotFound(BlockHash),
@@ -58,6 +68,14 @@ pub enum ProviderError {
         /// The account address.
         address: Address,
     },
+    /// The stored hash of a header does not match the hash computed from the header.
+    #[error("block hash mismatch for block #{number}: {hash}")]
+    BlockHashMismatch {
+        /// The block number of the header.
+        number: BlockNumber,
+        /// The computed and the stored block hash.
+        hash: Box<GotExpected<B256>>,
+    },
     /// When required header related data was not found but was required.
     #[error("no header found for {_0:?}")]
     HeaderNotFound(BlockHashOrNumber),
@@ -94,9 +112,17 @@ pub enum ProviderError {
     /// Root mismatch during unwind
     #[error("unwind merkle trie {_0}")]
     UnwindStateRootMismatch(Box<RootMismatch>),
-    /// State is not available for the given block number because it is pruned.
-    #[error("state at block #{_0} is pruned")]
-    StateAtBlockPruned(BlockNumber),
+    /// State is not available for the requested block number because the history before the
+    /// oldest available block is pruned.
+    #[error(
+        "state at block #{requested} is pruned, oldest available block is #{oldest_available}"
+    )]
+    StateAtBlockPruned {
+        /// The requested block number.
+        requested: BlockNumber,
+        /// The oldest block number for which state is available.
+        oldest_available: BlockNumber,
+    },
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
@@ -178,6 +204,38 @@ impl ProviderError {
     pub fn is_other<T: core::error::Error + 'static>(&self) -> bool {
         self.as_other().map(|err| err.is::<T>()).unwrap_or(false)
     }
+
+    /// Returns true if this is a [`ProviderError::Database`] error that is expected to succeed on
+    /// retry, see [`DatabaseError::is_transient`].
+    pub const fn is_transient(&self) -> bool {
+        matches!(self, Self::Database(err) if err.is_transient())
+    }
+
+    /// Returns true if the error only signals that the requested block, transaction or receipt
+    /// does not exist.
+    ///
+    /// Missing data that is expected to exist, e.g. the body of a known block, is not included.
+    pub const fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            Self::HeaderNotFound(_) |
+                Self::BlockHashNotFound(_) |
+                Self::UnknownBlockHash(_) |
+                Self::TransactionNotFound(_) |
+                Self::ReceiptNotFound(_)
+        )
+    }
+
+    /// Returns true if the error signals a gap in the stored data.
+    pub const fn is_gap(&self) -> bool {
+        matches!(
+            self,
+            Self::BlockTransactionGap { .. } |
+                Self::BlockBodyIndicesNotFound(_) |
+                Self::MissingStaticFileBlock(..) |
+                Self::MissingStaticFileTx(..)
+        )
+    }
 }
 
 impl DBErrorMarker for ProviderError {}
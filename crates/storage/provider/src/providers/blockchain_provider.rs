@@ -28,7 +28,7 @@ use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{updates::TrieUpdatesSorted, HashedPostState, KeccakKeyHasher};
 use revm_database::BundleState;
 use std::{
-    ops::{RangeBounds, RangeInclusive},
+    ops::{Range, RangeBounds, RangeInclusive},
     sync::Arc,
     time::Instant,
 };
@@ -190,6 +190,10 @@ impl<N: ProviderNodeTypes> HeaderProvider for BlockchainProvider<N> {
         self.consistent_provider()?.sealed_header(number)
     }
 
+    fn best_header(&self) -> ProviderResult<SealedHeader<Self::Header>> {
+        self.consistent_provider()?.best_header()
+    }
+
     fn sealed_headers_range(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -218,6 +222,10 @@ impl<N: ProviderNodeTypes> BlockHashReader for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<B256>> {
         self.consistent_provider()?.canonical_hashes_range(start, end)
     }
+
+    fn block_hashes(&self, range: Range<BlockNumber>) -> ProviderResult<Vec<Option<B256>>> {
+        self.consistent_provider()?.block_hashes(range)
+    }
 }
 
 impl<N: ProviderNodeTypes> BlockNumReader for BlockchainProvider<N> {
@@ -248,11 +256,17 @@ impl<N: ProviderNodeTypes> BlockIdReader for BlockchainProvider<N> {
     }
 
     fn safe_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
-        Ok(self.canonical_in_memory_state.get_safe_num_hash())
+        match self.canonical_in_memory_state.get_safe_num_hash() {
+            Some(num_hash) => Ok(Some(num_hash)),
+            None => self.consistent_provider()?.safe_block_num_hash(),
+        }
     }
 
     fn finalized_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
-        Ok(self.canonical_in_memory_state.get_finalized_num_hash())
+        match self.canonical_in_memory_state.get_finalized_num_hash() {
+            Some(num_hash) => Ok(Some(num_hash)),
+            None => self.consistent_provider()?.finalized_block_num_hash(),
+        }
     }
 }
 
@@ -271,6 +285,10 @@ impl<N: ProviderNodeTypes> BlockReader for BlockchainProvider<N> {
         self.consistent_provider()?.block(id)
     }
 
+    fn genesis_hash(&self) -> ProviderResult<B256> {
+        self.database.genesis_hash()
+    }
+
     fn pending_block(&self) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
         Ok(self.canonical_in_memory_state.pending_recovered_block())
     }
@@ -770,9 +788,9 @@ mod tests {
     use reth_primitives_traits::{RecoveredBlock, SealedBlock, SignerRecoverable};
     use reth_storage_api::{
         BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
-        BlockReaderIdExt, BlockSource, ChangeSetReader, DBProvider, DatabaseProviderFactory,
-        HeaderProvider, ReceiptProvider, ReceiptProviderIdExt, StateProviderFactory, StateWriter,
-        TransactionVariant, TransactionsProvider,
+        BlockReaderIdExt, BlockSource, ChainStateBlockWriter, ChangeSetReader, DBProvider,
+        DatabaseProviderFactory, HeaderProvider, ReceiptProvider, ReceiptProviderIdExt,
+        StateProviderFactory, StateWriter, TransactionVariant, TransactionsProvider,
     };
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, random_changeset_range, random_eoa_accounts,
@@ -1394,6 +1412,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_block_reader_id_ext_transaction_by_block_and_index() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, database_blocks, in_memory_blocks, _) = provider_with_random_blocks(
+            &mut rng,
+            TEST_BLOCKS_COUNT,
+            TEST_BLOCKS_COUNT,
+            BlockRangeParams { tx_count: 1..3, ..Default::default() },
+        )?;
+
+        for block in [database_blocks.last().unwrap(), in_memory_blocks.last().unwrap()] {
+            let transactions = &block.body().transactions;
+            for (index, transaction) in transactions.iter().enumerate() {
+                assert_eq!(
+                    provider.transaction_by_block_and_index(block.hash().into(), index)?,
+                    Some(transaction.clone())
+                );
+            }
+            assert_eq!(
+                provider.transaction_by_block_and_index(block.number.into(), transactions.len())?,
+                None
+            );
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_block_reader_id_ext_header_by_number_or_tag() -> eyre::Result<()> {
         let mut rng = generators::rng();
@@ -1555,6 +1600,76 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_block_reader_id_ext_logs_by_block() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, database_blocks, in_memory_blocks, receipts) = provider_with_random_blocks(
+            &mut rng,
+            TEST_BLOCKS_COUNT,
+            TEST_BLOCKS_COUNT,
+            BlockRangeParams { tx_count: 2..4, ..Default::default() },
+        )?;
+
+        for block in [database_blocks.first().unwrap(), in_memory_blocks.last().unwrap()] {
+            let block_receipts = receipts.get(block.number as usize).unwrap();
+            let logs = provider.logs_by_block(block.hash().into())?.unwrap();
+            assert_eq!(logs, provider.logs_by_block(block.number.into())?.unwrap());
+
+            assert_eq!(logs.len(), block.body().transactions.len());
+            let mut log_index = 0;
+            for ((tx, receipt), (tx_hash, tx_logs)) in
+                block.body().transactions.iter().zip(block_receipts).zip(logs)
+            {
+                assert_eq!(tx_hash, *tx.tx_hash());
+                assert_eq!(tx_logs.len(), receipt.logs.len());
+                for ((index, log), expected) in tx_logs.into_iter().zip(&receipt.logs) {
+                    assert_eq!(index, log_index);
+                    assert_eq!(&log, expected);
+                    log_index += 1;
+                }
+            }
+        }
+
+        assert_eq!(provider.logs_by_block(B256::random().into())?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_reader_id_ext_confirmations() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, database_blocks, in_memory_blocks, _) = provider_with_random_blocks(
+            &mut rng,
+            TEST_BLOCKS_COUNT,
+            TEST_BLOCKS_COUNT,
+            BlockRangeParams::default(),
+        )?;
+
+        let tip = in_memory_blocks.last().unwrap();
+        assert_eq!(provider.confirmations(tip.hash())?, Some(1));
+        let first = database_blocks.first().unwrap();
+        assert_eq!(provider.confirmations(first.hash())?, Some(tip.number - first.number + 1));
+
+        // the pending block has no confirmations yet
+        let pending = random_block(
+            &mut rng,
+            tip.number + 1,
+            BlockParams { parent: Some(tip.hash()), ..Default::default() },
+        );
+        provider.canonical_in_memory_state.set_pending_block(ExecutedBlock {
+            recovered_block: Arc::new(RecoveredBlock::new_sealed(
+                pending.clone(),
+                Default::default(),
+            )),
+            ..Default::default()
+        });
+        assert_eq!(provider.confirmations(pending.hash())?, Some(0));
+
+        assert_eq!(provider.confirmations(B256::random())?, None);
+
+        Ok(())
+    }
+
     #[test]
     fn test_receipt_provider_id_ext_receipts_by_block_number_or_tag() -> eyre::Result<()> {
         let mut rng = generators::rng();
@@ -1882,6 +1997,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_block_id_reader_falls_back_to_database() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, database_blocks, in_memory_blocks, _) = provider_with_random_blocks(
+            &mut rng,
+            TEST_BLOCKS_COUNT,
+            TEST_BLOCKS_COUNT,
+            BlockRangeParams::default(),
+        )?;
+
+        let persisted_safe = &database_blocks[database_blocks.len() - 1];
+        let persisted_finalized = &database_blocks[database_blocks.len() - 2];
+        let provider_rw = provider.database.provider_rw()?;
+        provider_rw.save_safe_block_number(persisted_safe.number)?;
+        provider_rw.save_finalized_block_number(persisted_finalized.number)?;
+        provider_rw.commit()?;
+
+        // in-memory state without safe and finalized blocks
+        let provider = BlockchainProvider {
+            database: provider.database.clone(),
+            canonical_in_memory_state: CanonicalInMemoryState::with_head(
+                in_memory_blocks.last().unwrap().clone_sealed_header(),
+                None,
+                None,
+            ),
+        };
+        assert_eq!(provider.safe_block_num_hash()?, Some(persisted_safe.num_hash()));
+        assert_eq!(provider.finalized_block_num_hash()?, Some(persisted_finalized.num_hash()));
+
+        // the in-memory state takes precedence once a forkchoice update sets the blocks
+        let safe = &in_memory_blocks[in_memory_blocks.len() - 2];
+        let finalized = &in_memory_blocks[in_memory_blocks.len() - 3];
+        provider.set_safe(safe.clone_sealed_header());
+        provider.set_finalized(finalized.clone_sealed_header());
+        assert_eq!(provider.safe_block_num_hash()?, Some(safe.num_hash()));
+        assert_eq!(provider.finalized_block_num_hash()?, Some(finalized.num_hash()));
+        assert_eq!(provider.convert_block_number(BlockNumberOrTag::Safe)?, Some(safe.number));
+
+        Ok(())
+    }
+
     #[test]
     fn test_block_id_reader() -> eyre::Result<()> {
         // Create a new provider
@@ -24,14 +24,14 @@ use reth_primitives_traits::{Account, BlockBody, RecoveredBlock, SealedHeader, S
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, ChainStateBlockReader, DatabaseProviderFactory,
+    NodePrimitivesProvider, StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::updates::TrieUpdatesSorted;
 use revm_database::states::PlainStorageRevert;
 use std::{
-    ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
+    ops::{Add, Bound, Range, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
 };
 use tracing::trace;
@@ -75,6 +75,18 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
         Ok(Self { storage_provider, head_block, canonical_in_memory_state: state })
     }
 
+    /// Returns the number and hash of the given block persisted in the database, if any.
+    ///
+    /// Used as a fallback for the safe and finalized blocks, which the in-memory state tracks
+    /// from the latest forkchoice update even before they're persisted.
+    fn persisted_num_hash(
+        &self,
+        number: Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumHash>> {
+        let Some(number) = number else { return Ok(None) };
+        Ok(self.storage_provider.block_hash(number)?.map(|hash| BlockNumHash::new(number, hash)))
+    }
+
     // Helper function to convert range bounds
     fn convert_range_bounds<T>(
         &self,
@@ -575,6 +587,10 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
     }
 
     /// Fetches data from either in-memory state or persistent storage by [`BlockHashOrNumber`].
+    ///
+    /// The in-memory canonical chain is consulted first, so blocks that have been made canonical
+    /// but are not yet persisted are resolved consistently. If the block is not part of the
+    /// in-memory canonical chain, the database is queried.
     pub(crate) fn get_in_memory_or_storage_by_block<S, M, R>(
         &self,
         id: BlockHashOrNumber,
@@ -632,6 +648,30 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
             Ok(())
         }
     }
+
+    /// Returns [`ProviderError::StateAtBlockPruned`] if the history of the given segment was
+    /// pruned at the given block, according to the prune checkpoint of the segment.
+    fn ensure_history_available(
+        &self,
+        segment: PruneSegment,
+        block_number: BlockNumber,
+    ) -> ProviderResult<()> {
+        // No prune checkpoint means history should exist
+        let oldest_available = self
+            .storage_provider
+            .get_prune_checkpoint(segment)?
+            // The checkpoint stores the highest pruned block number, so the history is available
+            // starting from the next block.
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map(|checkpoint| checkpoint + 1);
+
+        match oldest_available {
+            Some(oldest_available) if oldest_available > block_number => {
+                Err(ProviderError::StateAtBlockPruned { requested: block_number, oldest_available })
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 impl<N: ProviderNodeTypes> NodePrimitivesProvider for ConsistentProvider<N> {
@@ -686,6 +726,13 @@ impl<N: ProviderNodeTypes> HeaderProvider for ConsistentProvider<N> {
         )
     }
 
+    fn best_header(&self) -> ProviderResult<SealedHeader<Self::Header>> {
+        if let Some(head) = &self.head_block {
+            return Ok(head.block_ref().recovered_block().clone_sealed_header())
+        }
+        self.sealed_header(self.best_block_number()?)?.ok_or(ProviderError::BestBlockNotFound)
+    }
+
     fn sealed_headers_range(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -716,6 +763,10 @@ impl<N: ProviderNodeTypes> HeaderProvider for ConsistentProvider<N> {
 }
 
 impl<N: ProviderNodeTypes> BlockHashReader for ConsistentProvider<N> {
+    /// Returns the canonical hash for the given block number.
+    ///
+    /// Recent canonical blocks that are still in memory are resolved from the in-memory state,
+    /// older blocks are resolved from the database.
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
         self.get_in_memory_or_storage_by_block(
             number.into(),
@@ -739,6 +790,18 @@ impl<N: ProviderNodeTypes> BlockHashReader for ConsistentProvider<N> {
             |_| true,
         )
     }
+
+    fn block_hashes(&self, range: Range<BlockNumber>) -> ProviderResult<Vec<Option<B256>>> {
+        // Canonical blocks are contiguous, so the available hashes are a prefix of the range.
+        let len = range.end.saturating_sub(range.start) as usize;
+        let mut hashes = self
+            .canonical_hashes_range(range.start, range.end)?
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        hashes.resize(len, None);
+        Ok(hashes)
+    }
 }
 
 impl<N: ProviderNodeTypes> BlockNumReader for ConsistentProvider<N> {
@@ -770,11 +833,17 @@ impl<N: ProviderNodeTypes> BlockIdReader for ConsistentProvider<N> {
     }
 
     fn safe_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
-        Ok(self.canonical_in_memory_state.get_safe_num_hash())
+        if let Some(num_hash) = self.canonical_in_memory_state.get_safe_num_hash() {
+            return Ok(Some(num_hash))
+        }
+        self.persisted_num_hash(self.storage_provider.last_safe_block_number()?)
     }
 
     fn finalized_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
-        Ok(self.canonical_in_memory_state.get_finalized_num_hash())
+        if let Some(num_hash) = self.canonical_in_memory_state.get_finalized_num_hash() {
+            return Ok(Some(num_hash))
+        }
+        self.persisted_num_hash(self.storage_provider.last_finalized_block_number()?)
     }
 }
 
@@ -1325,23 +1394,7 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for ConsistentProvider<N> {
             Ok(changesets)
         } else {
             // Perform checks on whether or not changesets exist for the block.
-
-            // No prune checkpoint means history should exist and we should `unwrap_or(true)`
-            let storage_history_exists = self
-                .storage_provider
-                .get_prune_checkpoint(PruneSegment::StorageHistory)?
-                .and_then(|checkpoint| {
-                    // return true if the block number is ahead of the prune checkpoint.
-                    //
-                    // The checkpoint stores the highest pruned block number, so we should make
-                    // sure the block_number is strictly greater.
-                    checkpoint.block_number.map(|checkpoint| block_number > checkpoint)
-                })
-                .unwrap_or(true);
-
-            if !storage_history_exists {
-                return Err(ProviderError::StateAtBlockPruned(block_number))
-            }
+            self.ensure_history_available(PruneSegment::StorageHistory, block_number)?;
 
             self.storage_provider.storage_changeset(block_number)
         }
@@ -1371,23 +1424,7 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
             Ok(changesets)
         } else {
             // Perform checks on whether or not changesets exist for the block.
-
-            // No prune checkpoint means history should exist and we should `unwrap_or(true)`
-            let account_history_exists = self
-                .storage_provider
-                .get_prune_checkpoint(PruneSegment::AccountHistory)?
-                .and_then(|checkpoint| {
-                    // return true if the block number is ahead of the prune checkpoint.
-                    //
-                    // The checkpoint stores the highest pruned block number, so we should make
-                    // sure the block_number is strictly greater.
-                    checkpoint.block_number.map(|checkpoint| block_number > checkpoint)
-                })
-                .unwrap_or(true);
-
-            if !account_history_exists {
-                return Err(ProviderError::StateAtBlockPruned(block_number))
-            }
+            self.ensure_history_available(PruneSegment::AccountHistory, block_number)?;
 
             self.storage_provider.account_block_changeset(block_number)
         }
@@ -1417,22 +1454,7 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
             Ok(changeset)
         } else {
             // Perform checks on whether or not changesets exist for the block.
-            // No prune checkpoint means history should exist and we should `unwrap_or(true)`
-            let account_history_exists = self
-                .storage_provider
-                .get_prune_checkpoint(PruneSegment::AccountHistory)?
-                .and_then(|checkpoint| {
-                    // return true if the block number is ahead of the prune checkpoint.
-                    //
-                    // The checkpoint stores the highest pruned block number, so we should make
-                    // sure the block_number is strictly greater.
-                    checkpoint.block_number.map(|checkpoint| block_number > checkpoint)
-                })
-                .unwrap_or(true);
-
-            if !account_history_exists {
-                return Err(ProviderError::StateAtBlockPruned(block_number))
-            }
+            self.ensure_history_available(PruneSegment::AccountHistory, block_number)?;
 
             // Delegate to the storage provider for database lookups
             self.storage_provider.get_account_before_block(block_number, address)
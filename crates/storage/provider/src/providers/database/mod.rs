@@ -2,10 +2,10 @@ use crate::{
     providers::{state::latest::LatestStateProvider, StaticFileProvider},
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, HeaderProvider, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider,
+    BlockHashReader, BlockNumReader, BlockReader, BlockWriter, ChainSpecProvider,
+    DatabaseProviderFactory, HashedPostStateProvider, HeaderProvider, HeaderSyncGapProvider,
+    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProviderBox,
+    StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::BlockHashOrNumber;
@@ -31,7 +31,7 @@ use revm_database::BundleState;
 use std::{
     ops::{RangeBounds, RangeInclusive},
     path::Path,
-    sync::Arc,
+    sync::{Arc, OnceLock},
 };
 
 use tracing::trace;
@@ -64,6 +64,8 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     prune_modes: PruneModes,
     /// The node storage handler.
     storage: Arc<N::Storage>,
+    /// The hash of the genesis block, cached on first access.
+    genesis_hash: Arc<OnceLock<B256>>,
 }
 
 impl<N: NodeTypes> ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>> {
@@ -86,6 +88,7 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::default(),
             storage: Default::default(),
+            genesis_hash: Default::default(),
         }
     }
 
@@ -128,6 +131,7 @@ impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::default(),
             storage: Default::default(),
+            genesis_hash: Default::default(),
         })
     }
 }
@@ -194,6 +198,51 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
         trace!(target: "providers::db", ?block_number, %block_hash, "Returning historical state provider for block hash");
         Ok(state_provider)
     }
+
+    /// Inserts the given blocks with [`BlockWriter::insert_block`], committing a separate write
+    /// transaction for every `batch_size` blocks to bound the memory used by each transaction.
+    ///
+    /// Unlike inserting all blocks using a single [`DatabaseProviderRW`], this is atomic per batch
+    /// only: if a block fails to be inserted, its batch is not committed, while all previous
+    /// batches remain written. The returned [`BatchedInsertOutcome`] reports how many blocks were
+    /// committed.
+    ///
+    /// A `batch_size` of zero is treated as one.
+    pub fn insert_blocks_batched(
+        &self,
+        blocks: impl IntoIterator<Item = RecoveredBlock<BlockTy<N>>>,
+        batch_size: usize,
+    ) -> BatchedInsertOutcome {
+        let batch_size = batch_size.max(1);
+        let mut blocks = blocks.into_iter().peekable();
+        let mut written = 0;
+        while blocks.peek().is_some() {
+            let batch = blocks.by_ref().take(batch_size);
+            let result = self.provider_rw().and_then(|provider| {
+                let mut count = 0;
+                for block in batch {
+                    provider.insert_block(block)?;
+                    count += 1;
+                }
+                provider.commit()?;
+                Ok(count)
+            });
+            match result {
+                Ok(count) => written += count,
+                Err(error) => return BatchedInsertOutcome { written, error: Some(error) },
+            }
+        }
+        BatchedInsertOutcome { written, error: None }
+    }
+}
+
+/// Outcome of [`ProviderFactory::insert_blocks_batched`].
+#[derive(Debug)]
+pub struct BatchedInsertOutcome {
+    /// Number of blocks that were committed.
+    pub written: usize,
+    /// The error that stopped the insertion, if any.
+    pub error: Option<ProviderError>,
 }
 
 impl<N: NodeTypesWithDB> NodePrimitivesProvider for ProviderFactory<N> {
@@ -256,6 +305,10 @@ impl<N: ProviderNodeTypes> HeaderProvider for ProviderFactory<N> {
         self.static_file_provider.sealed_header(number)
     }
 
+    fn best_header(&self) -> ProviderResult<SealedHeader<Self::Header>> {
+        self.provider()?.best_header()
+    }
+
     fn sealed_headers_range(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -325,6 +378,16 @@ impl<N: ProviderNodeTypes> BlockReader for ProviderFactory<N> {
         self.provider()?.block(id)
     }
 
+    fn genesis_hash(&self) -> ProviderResult<B256> {
+        if let Some(hash) = self.genesis_hash.get() {
+            return Ok(*hash)
+        }
+
+        // the genesis block never changes once it's written, so only successful reads are cached
+        let hash = self.provider()?.genesis_hash()?;
+        Ok(*self.genesis_hash.get_or_init(|| hash))
+    }
+
     fn pending_block(&self) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
         self.provider()?.pending_block()
     }
@@ -545,13 +608,15 @@ where
     N: NodeTypesWithDB<DB: fmt::Debug, ChainSpec: fmt::Debug, Storage: fmt::Debug>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { db, chain_spec, static_file_provider, prune_modes, storage } = self;
+        let Self { db, chain_spec, static_file_provider, prune_modes, storage, genesis_hash } =
+            self;
         f.debug_struct("ProviderFactory")
             .field("db", &db)
             .field("chain_spec", &chain_spec)
             .field("static_file_provider", &static_file_provider)
             .field("prune_modes", &prune_modes)
             .field("storage", &storage)
+            .field("genesis_hash", &genesis_hash)
             .finish()
     }
 }
@@ -564,6 +629,7 @@ impl<N: NodeTypesWithDB> Clone for ProviderFactory<N> {
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
             storage: self.storage.clone(),
+            genesis_hash: self.genesis_hash.clone(),
         }
     }
 }
@@ -575,8 +641,9 @@ mod tests {
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::{blocks::TEST_BLOCK, create_test_provider_factory, MockNodeTypesWithDB},
         BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGapProvider,
-        TransactionsProvider,
+        TransactionsProvider, TransactionsProviderExt,
     };
+    use alloy_consensus::transaction::TxHashRef;
     use alloy_primitives::{TxNumber, B256};
     use assert_matches::assert_matches;
     use reth_chainspec::ChainSpecBuilder;
@@ -670,6 +737,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_blocks_batched() {
+        let mut rng = generators::rng();
+        let mut parent = B256::ZERO;
+        let blocks: Vec<_> = (0..5)
+            .map(|number| {
+                let block = random_block(
+                    &mut rng,
+                    number,
+                    BlockParams { parent: Some(parent), tx_count: Some(1), ..Default::default() },
+                );
+                parent = block.hash();
+                block.try_recover().unwrap()
+            })
+            .collect();
+
+        let factory = create_test_provider_factory();
+        let outcome = factory.insert_blocks_batched(blocks[..4].to_vec(), 3);
+        assert_eq!(outcome.written, 4);
+        assert!(outcome.error.is_none());
+        assert_eq!(factory.provider().unwrap().last_block_number().unwrap(), 3);
+
+        // the third batch fails on the already written block 3, the first two batches remain
+        let factory = create_test_provider_factory();
+        let retried = blocks[..4].iter().chain(&blocks[3..]).cloned();
+        let outcome = factory.insert_blocks_batched(retried, 2);
+        assert_eq!(outcome.written, 4);
+        assert!(outcome.error.is_some());
+        assert_eq!(factory.provider().unwrap().last_block_number().unwrap(), 3);
+    }
+
+    #[test]
+    fn genesis_hash_is_cached() {
+        let mut rng = generators::rng();
+        let genesis = random_block(&mut rng, 0, BlockParams::default());
+
+        let factory = create_test_provider_factory();
+        assert_matches!(factory.genesis_hash(), Err(ProviderError::HeaderNotFound(_)));
+
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_block(genesis.clone().try_recover().unwrap()).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(factory.genesis_hash().unwrap(), genesis.hash());
+        assert_eq!(factory.genesis_block().unwrap(), genesis);
+        // clones share the cache
+        assert_eq!(factory.clone().genesis_hash.get(), Some(&genesis.hash()));
+    }
+
+    #[test]
+    fn transaction_block_by_hash() {
+        let mut rng = generators::rng();
+        let genesis =
+            random_block(&mut rng, 0, BlockParams { tx_count: Some(2), ..Default::default() });
+        let block = random_block(
+            &mut rng,
+            1,
+            BlockParams { parent: Some(genesis.hash()), tx_count: Some(3), ..Default::default() },
+        );
+
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        for block in [&genesis, &block] {
+            provider.insert_block(block.clone().try_recover().unwrap()).unwrap();
+        }
+
+        for (index, tx) in block.body().transactions.iter().enumerate() {
+            assert_eq!(
+                provider.transaction_block_by_hash(*tx.tx_hash()).unwrap(),
+                Some((1, block.hash(), index))
+            );
+        }
+        assert_eq!(provider.transaction_block_by_hash(B256::random()).unwrap(), None);
+    }
+
     #[test]
     fn take_block_transaction_range_recover_senders() {
         let mut rng = generators::rng();
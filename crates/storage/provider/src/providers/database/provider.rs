@@ -28,7 +28,7 @@ use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256,
+    Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256,
 };
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
@@ -49,7 +49,8 @@ use reth_db_api::{
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_node_types::{BlockTy, BodyTy, HeaderTy, NodeTypes, ReceiptTy, TxTy};
 use reth_primitives_traits::{
-    Account, Block as _, BlockBody as _, Bytecode, RecoveredBlock, SealedHeader, StorageEntry,
+    Account, Block as _, BlockBody as _, Bytecode, GotExpected, RecoveredBlock, SealedBlock,
+    SealedHeader, StorageEntry,
 };
 use reth_prune_types::{
     PruneCheckpoint, PruneMode, PruneModes, PruneSegment, MINIMUM_PRUNING_DISTANCE,
@@ -174,8 +175,16 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         &'a self,
         block_hash: BlockHash,
     ) -> ProviderResult<Box<dyn StateProvider + 'a>> {
-        let mut block_number =
+        let block_number =
             self.block_number(block_hash)?.ok_or(ProviderError::BlockHashNotFound(block_hash))?;
+        self.history_by_block_number(block_number)
+    }
+
+    /// Storage provider for state at that given block number
+    pub fn history_by_block_number<'a>(
+        &'a self,
+        mut block_number: BlockNumber,
+    ) -> ProviderResult<Box<dyn StateProvider + 'a>> {
         if block_number == self.best_block_number().unwrap_or_default() &&
             block_number == self.last_block_number().unwrap_or_default()
         {
@@ -212,6 +221,21 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         Ok(Box::new(state_provider))
     }
 
+    /// Returns the account state at the end of the given block.
+    ///
+    /// Changes made after the block are undone by looking up the first account changeset after
+    /// the block through the account history index, instead of walking all changesets.
+    ///
+    /// Returns `None` if the account did not exist at that block, e.g. because it was created
+    /// later or was deleted at that point.
+    pub fn account_at_block(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<Account>> {
+        self.history_by_block_number(block_number)?.basic_account(&address)
+    }
+
     #[cfg(feature = "test-utils")]
     /// Sets the prune modes for provider.
     pub const fn set_prune_modes(&mut self, prune_modes: PruneModes) {
@@ -517,6 +541,115 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
     pub fn chain_spec(&self) -> &N::ChainSpec {
         &self.chain_spec
     }
+
+    /// Returns the sealed headers in the given range, verifying that the hash of each header
+    /// matches the stored canonical hash.
+    ///
+    /// Unlike [`HeaderProvider::sealed_headers_range`], which trusts the stored hash, this
+    /// recomputes the hash of every header. This is intended for database integrity checks and
+    /// should not be used on the hot path.
+    ///
+    /// Returns [`ProviderError::BlockHashMismatch`] for the first header with a mismatching hash.
+    pub fn verified_sealed_headers_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader<HeaderTy<N>>>> {
+        let headers = self.sealed_headers_range(range)?;
+        for header in &headers {
+            let computed = header.header().hash_slow();
+            if computed != header.hash() {
+                return Err(ProviderError::BlockHashMismatch {
+                    number: header.number(),
+                    hash: GotExpected { got: computed, expected: header.hash() }.into(),
+                })
+            }
+        }
+        Ok(headers)
+    }
+
+    /// Returns an iterator over the transactions of the given block, reading each transaction as
+    /// the iterator advances.
+    ///
+    /// Unlike [`TransactionsProvider::transactions_by_block`], this does not collect all
+    /// transactions of the block up front. The iterator is empty for a block without
+    /// transactions.
+    ///
+    /// Returns an error if the block or its body indices are not found.
+    pub fn transactions_by_block_iter(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<TxTy<N>>> + '_> {
+        let block_number = match id {
+            BlockHashOrNumber::Hash(hash) => {
+                self.block_number(hash)?.ok_or(ProviderError::BlockHashNotFound(hash))?
+            }
+            BlockHashOrNumber::Number(number) => number,
+        };
+        let body = self
+            .block_body_indices(block_number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+
+        Ok(body.tx_num_range().map(move |tx_id| {
+            self.transaction_by_id(tx_id)?
+                .ok_or_else(|| ProviderError::TransactionNotFound(tx_id.into()))
+        }))
+    }
+
+    /// Verifies that the transactions and senders of the given block are present and contiguous
+    /// over the block's transaction range.
+    ///
+    /// Senders are not checked if they are pruned for the block.
+    ///
+    /// Returns [`ProviderError::BlockTransactionGap`] with the first missing transaction number.
+    pub fn verify_block_tx_integrity(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        let body = self
+            .block_body_indices(block_number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+        let tx_range = body.tx_num_range();
+        if tx_range.is_empty() {
+            return Ok(())
+        }
+
+        let transactions = self.transactions_by_tx_range(tx_range.clone())?;
+        if transactions.len() as u64 != body.tx_count() {
+            return Err(ProviderError::BlockTransactionGap {
+                block_number,
+                tx_number: tx_range.start + transactions.len() as TxNumber,
+                table: tables::Transactions::NAME,
+            })
+        }
+
+        let senders_pruned =
+            self.prune_modes.sender_recovery.as_ref().is_some_and(|mode| mode.is_full()) ||
+                self.get_prune_checkpoint(PruneSegment::SenderRecovery)?
+                    .and_then(|checkpoint| checkpoint.block_number)
+                    .is_some_and(|pruned| pruned >= block_number);
+        if senders_pruned {
+            return Ok(())
+        }
+
+        let mut expected = tx_range.clone();
+        for entry in self.tx.cursor_read::<tables::TransactionSenders>()?.walk_range(tx_range)? {
+            let (tx_number, _) = entry?;
+            let next = expected.next();
+            if next != Some(tx_number) {
+                return Err(ProviderError::BlockTransactionGap {
+                    block_number,
+                    tx_number: next.unwrap_or(tx_number),
+                    table: tables::TransactionSenders::NAME,
+                })
+            }
+        }
+        if let Some(tx_number) = expected.next() {
+            return Err(ProviderError::BlockTransactionGap {
+                block_number,
+                tx_number,
+                table: tables::TransactionSenders::NAME,
+            })
+        }
+
+        Ok(())
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
@@ -879,6 +1012,19 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
             .map(|result| -> ProviderResult<_> { Ok(result?) })
             .collect()
     }
+
+    fn storage_changesets_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let storage_range: Range<BlockNumberAddress> =
+            (range.start, Address::ZERO).into()..(range.end, Address::ZERO).into();
+        self.tx
+            .cursor_dup_read::<tables::StorageChangeSets>()?
+            .walk_range(storage_range)?
+            .map(|result| -> ProviderResult<_> { Ok(result?) })
+            .collect()
+    }
 }
 
 impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
@@ -909,6 +1055,17 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
             .map(Ok)
             .transpose()
     }
+
+    fn account_changesets_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        self.tx
+            .cursor_read::<tables::AccountChangeSets>()?
+            .walk_range(range)?
+            .map(|result| -> ProviderResult<_> { Ok(result?) })
+            .collect()
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderSyncGapProvider
@@ -985,6 +1142,10 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderProvider for DatabasePro
         self.static_file_provider.sealed_header(number)
     }
 
+    fn best_header(&self) -> ProviderResult<SealedHeader<Self::Header>> {
+        self.sealed_header(self.best_block_number()?)?.ok_or(ProviderError::BestBlockNotFound)
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -1006,6 +1167,10 @@ impl<TX: DbTx + 'static, N: NodeTypes> BlockHashReader for DatabaseProvider<TX,
     ) -> ProviderResult<Vec<B256>> {
         self.static_file_provider.canonical_hashes_range(start, end)
     }
+
+    fn block_hashes(&self, range: Range<BlockNumber>) -> ProviderResult<Vec<Option<B256>>> {
+        self.static_file_provider.block_hashes(range)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> BlockNumReader for DatabaseProvider<TX, N> {
@@ -1146,6 +1311,17 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockReader for DatabaseProvid
         )
     }
 
+    fn sealed_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedBlock<Self::Block>>> {
+        self.block_range(
+            range,
+            |range| self.sealed_headers_range(range),
+            |header, body, _| Ok(Self::Block::new_sealed(header, body)),
+        )
+    }
+
     fn block_with_senders_range(
         &self,
         range: RangeInclusive<BlockNumber>,
@@ -3131,10 +3307,411 @@ mod tests {
     use super::*;
     use crate::{
         test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
-        BlockWriter,
+        BlockWriter, CanonicalProof,
     };
+    use alloy_primitives::U256;
+    use assert_matches::assert_matches;
     use reth_testing_utils::generators::{self, random_block, BlockParams};
 
+    #[test]
+    fn test_verified_sealed_headers_range_detects_hash_mismatch() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let headers = generators::random_header_range(&mut rng, 0..3, B256::ZERO);
+
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for (idx, header) in headers.iter().enumerate() {
+            // corrupt the stored hash of the last header
+            let hash = if idx == 2 { B256::random() } else { header.hash() };
+            writer.append_header(header.header(), &hash).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.verified_sealed_headers_range(0..=1).unwrap(), headers[..2]);
+
+        let err = provider.verified_sealed_headers_range(0..=2).unwrap_err();
+        assert!(matches!(err, ProviderError::BlockHashMismatch { number: 2, .. }));
+    }
+
+    #[test]
+    fn test_block_hashes_aligned_with_range() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let headers = generators::random_header_range(&mut rng, 0..3, B256::ZERO);
+
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for header in &headers {
+            writer.append_header(header.header(), &header.hash()).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.block_hashes(1..5).unwrap(),
+            vec![Some(headers[1].hash()), Some(headers[2].hash()), None, None]
+        );
+        assert!(provider.block_hashes(3..3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_transactions_by_block_iter() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        provider_rw.insert_block(data.blocks[0].0.clone()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let transactions = provider
+            .transactions_by_block_iter(data.blocks[0].0.hash().into())
+            .unwrap()
+            .collect::<ProviderResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(transactions, data.blocks[0].0.body().transactions);
+
+        // genesis has no transactions
+        assert_eq!(provider.transactions_by_block_iter(0.into()).unwrap().count(), 0);
+
+        assert!(matches!(
+            provider.transactions_by_block_iter(2.into()),
+            Err(ProviderError::BlockBodyIndicesNotFound(2))
+        ));
+    }
+
+    #[test]
+    fn test_verify_block_tx_integrity() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        provider_rw.insert_block(data.blocks[0].0.clone()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        provider.verify_block_tx_integrity(0).unwrap();
+        provider.verify_block_tx_integrity(1).unwrap();
+        assert_matches!(
+            provider.verify_block_tx_integrity(2),
+            Err(ProviderError::BlockBodyIndicesNotFound(2))
+        );
+        drop(provider);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx_number = provider_rw.block_body_indices(1).unwrap().unwrap().first_tx_num();
+        provider_rw.tx_ref().delete::<tables::TransactionSenders>(tx_number, None).unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_matches!(
+            factory.provider().unwrap().verify_block_tx_integrity(1),
+            Err(ProviderError::BlockTransactionGap { block_number: 1, tx_number: missing, .. })
+                if missing == tx_number
+        );
+    }
+
+    #[test]
+    fn test_account_changesets_range() {
+        let factory = create_test_provider_factory();
+        let address_a = Address::with_last_byte(1);
+        let address_b = Address::with_last_byte(2);
+        let account = Account { nonce: 1, ..Default::default() };
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let changesets = [
+            (1, AccountBeforeTx { address: address_a, info: None }),
+            (2, AccountBeforeTx { address: address_a, info: Some(account) }),
+            (2, AccountBeforeTx { address: address_b, info: None }),
+            (4, AccountBeforeTx { address: address_b, info: Some(account) }),
+        ];
+        for (block_number, account_before) in changesets.clone() {
+            provider_rw
+                .tx_ref()
+                .put::<tables::AccountChangeSets>(block_number, account_before)
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.account_changesets_range(0..4).unwrap(), changesets[..3]);
+        assert_eq!(provider.account_changesets_range(2..5).unwrap(), changesets[1..]);
+        assert!(provider.account_changesets_range(5..10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_storage_changesets_range() {
+        let factory = create_test_provider_factory();
+        let address_a = Address::with_last_byte(1);
+        let address_b = Address::with_last_byte(2);
+        let entry = |key: u8, value: u64| StorageEntry {
+            key: B256::with_last_byte(key),
+            value: U256::from(value),
+        };
+
+        let changesets = [
+            (BlockNumberAddress((1, address_a)), entry(1, 0)),
+            (BlockNumberAddress((1, address_a)), entry(2, 10)),
+            (BlockNumberAddress((1, address_b)), entry(1, 20)),
+            (BlockNumberAddress((3, address_a)), entry(1, 30)),
+        ];
+
+        // write out of order, the table keeps them sorted
+        let provider_rw = factory.provider_rw().unwrap();
+        for (key, entry) in changesets.iter().rev().cloned() {
+            provider_rw.tx_ref().put::<tables::StorageChangeSets>(key, entry).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.storage_changesets_range(0..4).unwrap(), changesets);
+        assert_eq!(provider.storage_changesets_range(1..2).unwrap(), changesets[..3]);
+        assert_eq!(provider.storage_changesets_range(2..4).unwrap(), changesets[3..]);
+        assert!(provider.storage_changesets_range(4..10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_account_at_block() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let created = Account { nonce: 1, ..Default::default() };
+        let recreated = Account { nonce: 5, ..Default::default() };
+
+        // created in block 2, deleted in block 3 and recreated in block 4
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::AccountChangeSets>(2, AccountBeforeTx { address, info: None }).unwrap();
+        tx.put::<tables::AccountChangeSets>(3, AccountBeforeTx { address, info: Some(created) })
+            .unwrap();
+        tx.put::<tables::AccountChangeSets>(4, AccountBeforeTx { address, info: None }).unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(address, u64::MAX),
+            BlockNumberList::new([2, 3, 4]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(address, recreated).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.account_at_block(address, 1).unwrap(), None);
+        assert_eq!(provider.account_at_block(address, 2).unwrap(), Some(created));
+        assert_eq!(provider.account_at_block(address, 3).unwrap(), None);
+        assert_eq!(provider.account_at_block(address, 4).unwrap(), Some(recreated));
+        assert_eq!(provider.account_at_block(address, 10).unwrap(), Some(recreated));
+        assert_eq!(provider.account_at_block(Address::with_last_byte(2), 2).unwrap(), None);
+    }
+
+    #[test]
+    fn test_fee_history_data() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        provider_rw.insert_block(data.blocks[0].0.clone()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert!(provider.fee_history_data(1..1).unwrap().is_empty());
+
+        let entries = provider.fee_history_data(0..2).unwrap();
+        assert_eq!(entries.len(), 2);
+        let header = data.blocks[0].0.header();
+        assert_eq!(entries[1].base_fee_per_gas, header.base_fee_per_gas.unwrap_or_default());
+        assert_eq!(entries[1].gas_used, header.gas_used);
+        assert_eq!(entries[1].gas_limit, header.gas_limit);
+        assert!(entries[1].priority_fees.is_sorted());
+    }
+
+    #[test]
+    fn test_sealed_block_range() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        provider_rw.insert_block(data.blocks[0].0.clone()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.sealed_block_range(0..=2).unwrap(),
+            vec![data.genesis.clone(), data.blocks[0].0.clone_sealed_block()]
+        );
+    }
+
+    #[test]
+    fn test_recovered_block_range_partial() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        provider_rw.insert_block(data.blocks[0].0.clone()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let (blocks, err) = provider.recovered_block_range_partial(0..=2);
+        assert!(err.is_none());
+        assert_eq!(blocks, provider.recovered_block_range(0..=2).unwrap());
+        assert_eq!(
+            blocks.iter().map(|block| block.hash()).collect::<Vec<_>>(),
+            vec![data.genesis.hash(), data.blocks[0].0.hash()]
+        );
+    }
+
+    #[test]
+    fn test_recovered_block_range_partial_failure() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        // block 2 has a transaction with an invalid signature and no stored sender
+        let (header, mut body) = data.blocks[1].0.clone_sealed_block().split_header_body();
+        body.transactions = vec![reth_ethereum_primitives::TransactionSigned::new_unhashed(
+            reth_ethereum_primitives::Transaction::Legacy(Default::default()),
+            alloy_primitives::Signature::new(U256::ZERO, U256::ZERO, false),
+        )];
+        let invalid =
+            RecoveredBlock::new_sealed(SealedBlock::seal_parts(header, body), vec![Address::ZERO]);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        provider_rw.insert_block(data.blocks[0].0.clone()).unwrap();
+        let indices = provider_rw.insert_block(invalid).unwrap();
+        provider_rw
+            .tx_ref()
+            .delete::<tables::TransactionSenders>(indices.first_tx_num(), None)
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_matches!(
+            provider.recovered_block_range(0..=2),
+            Err(ProviderError::SenderRecoveryError)
+        );
+
+        // the blocks before the failing block are kept
+        let (blocks, err) = provider.recovered_block_range_partial(0..=2);
+        assert_matches!(err, Some(ProviderError::SenderRecoveryError));
+        assert_eq!(
+            blocks.iter().map(|block| block.hash()).collect::<Vec<_>>(),
+            vec![data.genesis.hash(), data.blocks[0].0.hash()]
+        );
+    }
+
+    #[test]
+    fn test_sealed_blocks_with_td() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        provider_rw.insert_block(data.blocks[0].0.clone()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let genesis_td = data.genesis.difficulty;
+        let block_td = genesis_td + data.blocks[0].0.difficulty;
+        assert_eq!(
+            provider.sealed_blocks_with_td(0..=1, U256::ZERO).unwrap(),
+            vec![
+                (data.genesis.clone(), genesis_td),
+                (data.blocks[0].0.clone_sealed_block(), block_td)
+            ]
+        );
+        assert_eq!(
+            provider.sealed_blocks_with_td(1..=1, genesis_td).unwrap(),
+            vec![(data.blocks[0].0.clone_sealed_block(), block_td)]
+        );
+    }
+
+    #[test]
+    fn test_recent_blocks() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        for i in 0..2 {
+            provider_rw.insert_block(data.blocks[i].0.clone()).unwrap();
+        }
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(2)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert!(provider.recent_blocks(0).unwrap().is_empty());
+        assert_eq!(
+            provider.recent_blocks(2).unwrap(),
+            vec![data.blocks[1].0.clone_sealed_block(), data.blocks[0].0.clone_sealed_block()]
+        );
+        // the chain is shorter than requested
+        assert_eq!(provider.recent_blocks(10).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_canonical_proof() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        let fork_hash = B256::random();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        for i in 0..2 {
+            provider_rw.insert_block(data.blocks[i].0.clone()).unwrap();
+        }
+        // a block that is known, but not canonical
+        provider_rw.tx_ref().put::<tables::HeaderNumbers>(fork_hash, 2).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(2)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let tip = &data.blocks[1].0;
+        assert_eq!(provider.canonical_proof(B256::random(), 10).unwrap(), None);
+        assert_eq!(provider.canonical_proof(fork_hash, 10).unwrap(), None);
+
+        // the tip is its own checkpoint
+        assert_eq!(
+            provider.canonical_proof(tip.hash(), 0).unwrap(),
+            Some(CanonicalProof { header: tip.header().clone(), descendants: Vec::new() })
+        );
+
+        let proof = provider.canonical_proof(data.genesis.hash(), 2).unwrap().unwrap();
+        assert_eq!(proof.header, *data.genesis.header());
+        assert_eq!(
+            proof.descendants,
+            vec![data.blocks[0].0.header().clone(), tip.header().clone()]
+        );
+        assert!(proof.verify(data.genesis.hash(), tip.hash()));
+
+        // the block is too far below the tip
+        assert_eq!(provider.canonical_proof(data.genesis.hash(), 1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_best_header() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        assert_matches!(
+            factory.provider().unwrap().best_header(),
+            Err(ProviderError::BestBlockNotFound)
+        );
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        provider_rw.insert_block(data.blocks[0].0.clone()).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(1)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.best_header().unwrap(), data.blocks[0].0.clone_sealed_header());
+    }
+
     #[test]
     fn test_receipts_by_block_range_empty_range() {
         let factory = create_test_provider_factory();
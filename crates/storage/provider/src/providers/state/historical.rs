@@ -78,9 +78,7 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
 
     /// Lookup an account in the `AccountsHistory` table
     pub fn account_history_lookup(&self, address: Address) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        self.ensure_history_available(self.lowest_available_blocks.account_history_block_number)?;
 
         // history key to search IntegerList of block number changesets.
         let history_key = ShardedKey::new(address, self.block_number);
@@ -97,9 +95,7 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        self.ensure_history_available(self.lowest_available_blocks.storage_history_block_number)?;
 
         // history key to search IntegerList of block number changesets.
         let history_key = StorageShardedKey::new(address, storage_key, self.block_number);
@@ -110,6 +106,23 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
         )
     }
 
+    /// Returns [`ProviderError::StateAtBlockPruned`] if the history with the given lowest available
+    /// block number doesn't reach back to the block of this provider.
+    fn ensure_history_available(
+        &self,
+        lowest_available_block_number: Option<BlockNumber>,
+    ) -> ProviderResult<()> {
+        match lowest_available_block_number {
+            Some(oldest_available) if oldest_available > self.block_number => {
+                Err(ProviderError::StateAtBlockPruned {
+                    requested: self.block_number,
+                    oldest_available,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Checks and returns `true` if distance to historical block exceeds the provided limit.
     fn check_distance_against_limit(&self, limit: u64) -> ProviderResult<bool> {
         let tip = self.provider.last_block_number()?;
@@ -119,11 +132,8 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
 
     /// Retrieve revert hashed state for this history provider.
     fn revert_state(&self) -> ProviderResult<HashedPostState> {
-        if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
-            !self.lowest_available_blocks.is_storage_history_available(self.block_number)
-        {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        self.ensure_history_available(self.lowest_available_blocks.account_history_block_number)?;
+        self.ensure_history_available(self.lowest_available_blocks.storage_history_block_number)?;
 
         if self.check_distance_against_limit(EPOCH_SLOTS)? {
             tracing::warn!(
@@ -138,9 +148,7 @@ impl<'b, Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'b, P
 
     /// Retrieve revert hashed storage for this history provider and target address.
     fn revert_storage(&self, address: Address) -> ProviderResult<HashedStorage> {
-        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        self.ensure_history_available(self.lowest_available_blocks.storage_history_block_number)?;
 
         if self.check_distance_against_limit(EPOCH_SLOTS * 10)? {
             tracing::warn!(
@@ -773,11 +781,17 @@ mod tests {
         );
         assert!(matches!(
             provider.account_history_lookup(ADDRESS),
-            Err(ProviderError::StateAtBlockPruned(number)) if number == provider.block_number
+            Err(ProviderError::StateAtBlockPruned { requested, oldest_available: 3 })
+                if requested == provider.block_number
         ));
         assert!(matches!(
             provider.storage_history_lookup(ADDRESS, STORAGE),
-            Err(ProviderError::StateAtBlockPruned(number)) if number == provider.block_number
+            Err(ProviderError::StateAtBlockPruned { requested, oldest_available: 3 })
+                if requested == provider.block_number
+        ));
+        assert!(matches!(
+            provider.basic_account(&ADDRESS),
+            Err(ProviderError::StateAtBlockPruned { requested: 2, oldest_available: 3 })
         ));
 
         // provider block_number == lowest available block number,
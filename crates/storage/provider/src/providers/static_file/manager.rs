@@ -1471,6 +1471,18 @@ impl<N: NodePrimitives> BlockHashReader for StaticFileProvider<N> {
             |_| true,
         )
     }
+
+    fn block_hashes(&self, range: Range<BlockNumber>) -> ProviderResult<Vec<Option<B256>>> {
+        // Static files have no gaps, so the available hashes are a prefix of the range.
+        let len = range.end.saturating_sub(range.start) as usize;
+        let mut hashes = self
+            .canonical_hashes_range(range.start, range.end)?
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        hashes.resize(len, None);
+        Ok(hashes)
+    }
 }
 
 impl<N: NodePrimitives<SignedTx: Value + SignedTransaction, Receipt: Value>> ReceiptProvider
@@ -68,6 +68,8 @@ pub struct MockEthProvider<T: NodePrimitives = EthPrimitives, ChainSpec = reth_c
     pub state_roots: Arc<Mutex<Vec<B256>>>,
     /// Local block body indices store
     pub block_body_indices: Arc<Mutex<HashMap<BlockNumber, StoredBlockBodyIndices>>>,
+    /// Local account changeset store indexed by block number
+    pub account_changesets: Arc<Mutex<HashMap<BlockNumber, Vec<AccountBeforeTx>>>>,
     tx: TxMock,
     prune_modes: Arc<PruneModes>,
 }
@@ -85,6 +87,7 @@ where
             chain_spec: self.chain_spec.clone(),
             state_roots: self.state_roots.clone(),
             block_body_indices: self.block_body_indices.clone(),
+            account_changesets: self.account_changesets.clone(),
             tx: self.tx.clone(),
             prune_modes: self.prune_modes.clone(),
         }
@@ -102,6 +105,7 @@ impl<T: NodePrimitives> MockEthProvider<T, reth_chainspec::ChainSpec> {
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
             state_roots: Default::default(),
             block_body_indices: Default::default(),
+            account_changesets: Default::default(),
             tx: Default::default(),
             prune_modes: Default::default(),
         }
@@ -171,6 +175,15 @@ impl<T: NodePrimitives, ChainSpec> MockEthProvider<T, ChainSpec> {
         self.block_body_indices.lock().insert(block_number, indices);
     }
 
+    /// Add the account changeset of a block to local account changeset store
+    pub fn add_account_changeset(
+        &self,
+        block_number: BlockNumber,
+        changeset: Vec<AccountBeforeTx>,
+    ) {
+        self.account_changesets.lock().insert(block_number, changeset);
+    }
+
     /// Add state root to local state root store
     pub fn add_state_root(&self, state_root: B256) {
         self.state_roots.lock().push(state_root);
@@ -186,6 +199,7 @@ impl<T: NodePrimitives, ChainSpec> MockEthProvider<T, ChainSpec> {
             chain_spec: Arc::new(chain_spec),
             state_roots: self.state_roots,
             block_body_indices: self.block_body_indices,
+            account_changesets: self.account_changesets,
             tx: self.tx,
             prune_modes: self.prune_modes,
         }
@@ -661,18 +675,20 @@ impl<T: NodePrimitives, ChainSpec: EthChainSpec + Send + Sync + 'static> BlockRe
 
     fn recovered_block(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        Ok(None)
+        self.block(id)?
+            .map(|block| block.try_into_recovered().map_err(|_| ProviderError::SenderRecoveryError))
+            .transpose()
     }
 
     fn sealed_block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
-        _transaction_kind: TransactionVariant,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        Ok(None)
+        self.recovered_block(id, transaction_kind)
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
@@ -963,17 +979,22 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> BlockBodyIndicesProvider
 impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvider<T, ChainSpec> {
     fn account_block_changeset(
         &self,
-        _block_number: BlockNumber,
+        block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        Ok(Vec::default())
+        Ok(self.account_changesets.lock().get(&block_number).cloned().unwrap_or_default())
     }
 
     fn get_account_before_block(
         &self,
-        _block_number: BlockNumber,
-        _address: Address,
+        block_number: BlockNumber,
+        address: Address,
     ) -> ProviderResult<Option<AccountBeforeTx>> {
-        Ok(None)
+        Ok(self
+            .account_changesets
+            .lock()
+            .get(&block_number)
+            .and_then(|changeset| changeset.iter().find(|account| account.address == address))
+            .cloned())
     }
 }
 
@@ -2,9 +2,9 @@ use alloc::{
     collections::{BTreeMap, BTreeSet},
     vec::Vec,
 };
-use alloy_primitives::{Address, BlockNumber};
+use alloy_primitives::{Address, BlockNumber, U256};
 use auto_impl::auto_impl;
-use core::ops::{RangeBounds, RangeInclusive};
+use core::ops::{Range, RangeBounds, RangeInclusive};
 use reth_db_models::AccountBeforeTx;
 use reth_primitives_traits::Account;
 use reth_storage_errors::provider::ProviderResult;
@@ -16,6 +16,14 @@ pub trait AccountReader {
     ///
     /// Returns `None` if the account doesn't exist.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>>;
+
+    /// Get the nonce and balance of an account with a single account read.
+    ///
+    /// Returns `None` if the account doesn't exist. An existing account with zero nonce and
+    /// balance, e.g. one that only holds storage, is returned as `Some((0, 0))`.
+    fn account_nonce_and_balance(&self, address: &Address) -> ProviderResult<Option<(u64, U256)>> {
+        Ok(self.basic_account(address)?.map(|account| (account.nonce, account.balance)))
+    }
 }
 
 /// Account reader
@@ -63,4 +71,23 @@ pub trait ChangeSetReader {
         block_number: BlockNumber,
         address: Address,
     ) -> ProviderResult<Option<AccountBeforeTx>>;
+
+    /// Iterate over account changesets of all blocks in the given range and return the account
+    /// state from before each block, ordered by block number and then by address.
+    ///
+    /// An account state of `None` means that the account did not exist before the block.
+    fn account_changesets_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let mut changesets = Vec::new();
+        for block_number in range {
+            changesets.extend(
+                self.account_block_changeset(block_number)?
+                    .into_iter()
+                    .map(|account_before| (block_number, account_before)),
+            );
+        }
+        Ok(changesets)
+    }
 }
@@ -3,11 +3,12 @@ use crate::{
     ReceiptProviderIdExt, TransactionVariant, TransactionsProvider,
 };
 use alloc::{sync::Arc, vec::Vec};
+use alloy_consensus::{transaction::TxHashRef, BlockHeader, Transaction, TxReceipt};
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
-use alloy_primitives::{BlockNumber, TxNumber, B256};
-use core::ops::RangeInclusive;
-use reth_primitives_traits::{RecoveredBlock, SealedHeader};
-use reth_storage_errors::provider::ProviderResult;
+use alloy_primitives::{BlockNumber, Log, TxHash, TxNumber, B256, U256};
+use core::ops::{Range, RangeInclusive};
+use reth_primitives_traits::{Block, BlockBody, RecoveredBlock, SealedBlock, SealedHeader};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// A helper enum that represents the origin of the requested block.
 ///
@@ -40,6 +41,63 @@ impl BlockSource {
     }
 }
 
+/// The per-block data needed to answer `eth_feeHistory`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FeeHistoryEntry {
+    /// The base fee of the block, `0` for blocks before London.
+    pub base_fee_per_gas: u64,
+    /// The gas used by the block.
+    pub gas_used: u64,
+    /// The gas limit of the block.
+    pub gas_limit: u64,
+    /// The effective priority fees of the block's transactions, sorted in ascending order.
+    pub priority_fees: Vec<u128>,
+}
+
+/// The logs of a block grouped by transaction, see [`BlockReaderIdExt::logs_by_block`].
+///
+/// Each log is paired with its index within the block.
+pub type BlockLogs = Vec<(TxHash, Vec<(u64, Log)>)>;
+
+/// A proof that a block is part of the canonical chain.
+///
+/// The proof is the chain of canonical headers linking the block to a checkpoint, the best block
+/// at the time the proof was created. A verifier that trusts the checkpoint hash can check the
+/// proof by following the parent hashes, see [`CanonicalProof::verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CanonicalProof<H> {
+    /// The header of the proven block.
+    pub header: H,
+    /// The canonical headers descending from the block, in ascending order.
+    ///
+    /// The last header is the checkpoint, this is empty if the block itself is the checkpoint.
+    pub descendants: Vec<H>,
+}
+
+impl<H: reth_primitives_traits::BlockHeader> CanonicalProof<H> {
+    /// Returns the header of the checkpoint the proof links to.
+    pub fn checkpoint(&self) -> &H {
+        self.descendants.last().unwrap_or(&self.header)
+    }
+
+    /// Returns `true` if the proof shows that the block with the given hash is an ancestor of, or
+    /// equal to, the checkpoint with the given hash.
+    pub fn verify(&self, block_hash: B256, checkpoint_hash: B256) -> bool {
+        let mut hash = self.header.hash_slow();
+        if hash != block_hash {
+            return false
+        }
+        for header in &self.descendants {
+            if header.parent_hash() != hash {
+                return false
+            }
+            hash = header.hash_slow();
+        }
+        hash == checkpoint_hash
+    }
+}
+
 /// A helper type alias to access [`BlockReader::Block`].
 pub type ProviderBlock<P> = <P as BlockReader>::Block;
 
@@ -90,6 +148,22 @@ pub trait BlockReader:
         &self,
     ) -> ProviderResult<Option<(RecoveredBlock<Self::Block>, Vec<Self::Receipt>)>>;
 
+    /// Returns the hash of the genesis block.
+    ///
+    /// Returns [`ProviderError::HeaderNotFound`] if the genesis block is not stored yet.
+    fn genesis_hash(&self) -> ProviderResult<B256> {
+        self.block_hash(0)?.ok_or(ProviderError::HeaderNotFound(0.into()))
+    }
+
+    /// Returns the sealed genesis block.
+    ///
+    /// Returns [`ProviderError::HeaderNotFound`] if the genesis block is not stored yet.
+    fn genesis_block(&self) -> ProviderResult<SealedBlock<Self::Block>> {
+        let hash = self.genesis_hash()?;
+        let block = self.block(hash.into())?.ok_or(ProviderError::HeaderNotFound(hash.into()))?;
+        Ok(SealedBlock::new_unchecked(block, hash))
+    }
+
     /// Returns the block with matching hash from the database.
     ///
     /// Returns `None` if block is not found.
@@ -131,6 +205,124 @@ pub trait BlockReader:
     /// Note: returns only available blocks
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>>;
 
+    /// Returns all sealed blocks in the given inclusive range, without the senders of their
+    /// transactions.
+    ///
+    /// Note: returns only available blocks
+    fn sealed_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedBlock<Self::Block>>> {
+        Ok(self.block_range(range)?.into_iter().map(SealedBlock::seal_slow).collect())
+    }
+
+    /// Returns all sealed blocks in the given inclusive range, each paired with the total
+    /// difficulty of the chain up to and including the block.
+    ///
+    /// Total difficulty is no longer stored, so the total difficulty of the parent of the first
+    /// block must be provided by the caller. It is zero if the range starts at genesis.
+    fn sealed_blocks_with_td(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        parent_td: U256,
+    ) -> ProviderResult<Vec<(SealedBlock<Self::Block>, U256)>> {
+        let mut td = parent_td;
+        Ok(self
+            .sealed_block_range(range)?
+            .into_iter()
+            .map(|block| {
+                td += block.header().difficulty();
+                (block, td)
+            })
+            .collect())
+    }
+
+    /// Returns up to `n` most recent sealed blocks, ending at the best block, newest first.
+    ///
+    /// Returns fewer blocks if the chain is shorter than `n`.
+    fn recent_blocks(&self, n: usize) -> ProviderResult<Vec<SealedBlock<Self::Block>>> {
+        if n == 0 {
+            return Ok(Vec::new())
+        }
+
+        let best_number = self.best_block_number()?;
+        let start = best_number.saturating_sub(n as u64 - 1);
+        let mut blocks = self.sealed_block_range(start..=best_number)?;
+        blocks.reverse();
+        Ok(blocks)
+    }
+
+    /// Returns a [`CanonicalProof`] linking the block with the given hash to the best block.
+    ///
+    /// The proof contains a header for every block up to the best block, so its size is bounded
+    /// by `max_descendants`, the maximum number of blocks the best block may be ahead of the
+    /// given block.
+    ///
+    /// Returns `None` if the block is unknown, not canonical or too far below the best block.
+    fn canonical_proof(
+        &self,
+        block_hash: B256,
+        max_descendants: u64,
+    ) -> ProviderResult<Option<CanonicalProof<Self::Header>>> {
+        let Some(number) = self.block_number(block_hash)? else { return Ok(None) };
+        if self.block_hash(number)? != Some(block_hash) {
+            return Ok(None)
+        }
+
+        let best_number = self.best_block_number()?;
+        if best_number.saturating_sub(number) > max_descendants {
+            return Ok(None)
+        }
+        let mut headers = self.sealed_headers_range(number..=best_number)?.into_iter();
+        let Some(header) = headers.next().filter(|header| header.hash() == block_hash) else {
+            // the block was reorged out in the meantime
+            return Ok(None)
+        };
+
+        let mut parent_hash = block_hash;
+        let mut descendants = Vec::with_capacity(headers.len());
+        for header in headers {
+            if header.parent_hash() != parent_hash {
+                // the chain was reorged in the meantime
+                return Ok(None)
+            }
+            parent_hash = header.hash();
+            descendants.push(header.unseal());
+        }
+
+        Ok(Some(CanonicalProof { header: header.unseal(), descendants }))
+    }
+
+    /// Returns the [`FeeHistoryEntry`] of every block in the given range.
+    ///
+    /// Computing reward percentiles from the entries is left to the caller.
+    fn fee_history_data(&self, range: Range<BlockNumber>) -> ProviderResult<Vec<FeeHistoryEntry>> {
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let blocks = self.block_range(range.start..=range.end - 1)?;
+        Ok(blocks
+            .into_iter()
+            .map(|block| {
+                let header = block.header();
+                let base_fee_per_gas = header.base_fee_per_gas().unwrap_or_default();
+                let mut priority_fees = block
+                    .body()
+                    .transactions_iter()
+                    .filter_map(|tx| tx.effective_tip_per_gas(base_fee_per_gas))
+                    .collect::<Vec<_>>();
+                priority_fees.sort_unstable();
+                FeeHistoryEntry {
+                    base_fee_per_gas,
+                    gas_used: header.gas_used(),
+                    gas_limit: header.gas_limit(),
+                    priority_fees,
+                }
+            })
+            .collect())
+    }
+
     /// Returns a range of blocks from the database, along with the senders of each
     /// transaction in the blocks.
     fn block_with_senders_range(
@@ -145,6 +337,31 @@ pub trait BlockReader:
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<RecoveredBlock<Self::Block>>>;
 
+    /// Returns a range of sealed blocks with senders like
+    /// [`BlockReader::recovered_block_range`], but keeps the blocks loaded before the first
+    /// failing block instead of discarding the whole range.
+    ///
+    /// The blocks are returned in ascending order together with the error of the first block that
+    /// failed to load, if any.
+    fn recovered_block_range_partial(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> (Vec<RecoveredBlock<Self::Block>>, Option<ProviderError>) {
+        if let Ok(blocks) = self.recovered_block_range(range.clone()) {
+            return (blocks, None)
+        }
+
+        // load the blocks one by one to find the first failing block
+        let mut blocks = Vec::new();
+        for number in range {
+            match self.recovered_block_range(number..=number) {
+                Ok(block) => blocks.extend(block),
+                Err(err) => return (blocks, Some(err)),
+            }
+        }
+        (blocks, None)
+    }
+
     /// Returns the block number that contains the given transaction.
     fn block_by_transaction_id(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>>;
 }
@@ -193,6 +410,12 @@ impl<T: BlockReader> BlockReader for Arc<T> {
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
         T::block_range(self, range)
     }
+    fn sealed_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedBlock<Self::Block>>> {
+        T::sealed_block_range(self, range)
+    }
     fn block_with_senders_range(
         &self,
         range: RangeInclusive<BlockNumber>,
@@ -254,6 +477,12 @@ impl<T: BlockReader> BlockReader for &T {
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
         T::block_range(self, range)
     }
+    fn sealed_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedBlock<Self::Block>>> {
+        T::sealed_block_range(self, range)
+    }
     fn block_with_senders_range(
         &self,
         range: RangeInclusive<BlockNumber>,
@@ -344,6 +573,82 @@ pub trait BlockReaderIdExt: BlockReader + ReceiptProviderIdExt {
         }
     }
 
+    /// Returns the transaction at the given index of the block with the matching [`BlockId`],
+    /// without loading the other transactions of the block.
+    ///
+    /// Returns `None` if the block is not found or the index is out of range for the block.
+    fn transaction_by_block_and_index(
+        &self,
+        id: BlockId,
+        index: usize,
+    ) -> ProviderResult<Option<Self::Transaction>> {
+        let Some(number) = self.block_number_for_id(id)? else { return Ok(None) };
+        let Some(body) = self.block_body_indices(number)? else { return Ok(None) };
+        if index as u64 >= body.tx_count() {
+            return Ok(None)
+        }
+        self.transaction_by_id(body.first_tx_num() + index as u64)
+    }
+
+    /// Returns the logs of the block with the matching [`BlockId`], grouped by the hash of the
+    /// transaction that emitted them.
+    ///
+    /// Each log is paired with its index within the block, counted across all transactions in
+    /// order. Transactions without logs are included with an empty list.
+    ///
+    /// Returns `None` if the block or its receipts are not found.
+    fn logs_by_block(&self, id: BlockId) -> ProviderResult<Option<BlockLogs>> {
+        let id = match id {
+            BlockId::Hash(hash) => BlockHashOrNumber::Hash(hash.block_hash),
+            BlockId::Number(num) => match self.convert_block_number(num)? {
+                Some(num) => BlockHashOrNumber::Number(num),
+                None => return Ok(None),
+            },
+        };
+        let Some(block) = self.block(id)? else { return Ok(None) };
+        let Some(receipts) = self.receipts_by_block(id)? else { return Ok(None) };
+
+        let mut log_index = 0;
+        let logs = block
+            .body()
+            .transactions_iter()
+            .zip(receipts)
+            .map(|(tx, receipt)| {
+                let logs = receipt
+                    .into_logs()
+                    .into_iter()
+                    .map(|log| {
+                        let indexed = (log_index, log);
+                        log_index += 1;
+                        indexed
+                    })
+                    .collect();
+                (*tx.tx_hash(), logs)
+            })
+            .collect();
+        Ok(Some(logs))
+    }
+
+    /// Returns the number of confirmations of the block with the given hash, i.e. the number of
+    /// canonical blocks from the block up to and including the best block.
+    ///
+    /// Returns `Some(0)` for the pending block and `None` if the block is unknown or no longer
+    /// part of the canonical chain, e.g. because it was reorged out.
+    fn confirmations(&self, block_hash: B256) -> ProviderResult<Option<u64>> {
+        if let Some(number) = self.block_number(block_hash)? &&
+            self.block_hash(number)? == Some(block_hash)
+        {
+            let best_number = self.best_block_number()?;
+            return Ok(Some(best_number.saturating_sub(number) + 1))
+        }
+
+        if self.pending_block()?.is_some_and(|block| block.hash() == block_hash) {
+            return Ok(Some(0))
+        }
+
+        Ok(None)
+    }
+
     /// Returns the header with matching tag from the database
     ///
     /// Returns `None` if header is not found.
@@ -401,3 +706,38 @@ pub trait ChainStateBlockWriter: Send + Sync {
     /// Saves the given safe block number in the DB.
     fn save_safe_block_number(&self, block_number: BlockNumber) -> ProviderResult<()>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::Sealable;
+
+    #[test]
+    fn verify_canonical_proof() {
+        let mut headers = Vec::<Header>::new();
+        let mut parent_hash = B256::ZERO;
+        for number in 0..4 {
+            let header = Header { number, parent_hash, ..Default::default() };
+            parent_hash = header.hash_slow();
+            headers.push(header);
+        }
+        let hashes = headers.iter().map(Sealable::hash_slow).collect::<Vec<_>>();
+
+        let proof =
+            CanonicalProof { header: headers[1].clone(), descendants: headers[2..].to_vec() };
+        assert_eq!(proof.checkpoint(), &headers[3]);
+        assert!(proof.verify(hashes[1], hashes[3]));
+        assert!(!proof.verify(hashes[0], hashes[3]));
+        assert!(!proof.verify(hashes[1], hashes[2]));
+
+        // a gap in the chain breaks the proof
+        let gapped =
+            CanonicalProof { header: headers[0].clone(), descendants: headers[2..].to_vec() };
+        assert!(!gapped.verify(hashes[0], hashes[3]));
+
+        // the checkpoint itself
+        let proof = CanonicalProof { header: headers[3].clone(), descendants: Vec::new() };
+        assert!(proof.verify(hashes[3], hashes[3]));
+    }
+}
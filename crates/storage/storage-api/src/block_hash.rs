@@ -1,6 +1,7 @@
 use alloc::vec::Vec;
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{BlockNumber, B256};
+use core::{num::NonZeroU64, ops::Range};
 use reth_storage_errors::provider::ProviderResult;
 
 /// Client trait for fetching block hashes by number.
@@ -32,4 +33,87 @@ pub trait BlockHashReader: Send + Sync {
         start: BlockNumber,
         end: BlockNumber,
     ) -> ProviderResult<Vec<B256>>;
+
+    /// Get the hashes of the blocks in the given range.
+    ///
+    /// The returned vector is aligned one-to-one with the range: the hash of block `n` is at
+    /// offset `n - range.start`, and is `None` if no block with this number exists.
+    ///
+    /// The default implementation looks up each block individually, implementations backed by a
+    /// cursor should override this with a single range scan.
+    fn block_hashes(&self, range: Range<BlockNumber>) -> ProviderResult<Vec<Option<B256>>> {
+        range.map(|number| self.block_hash(number)).collect()
+    }
+
+    /// Get the numbers and hashes of every `step`-th canonical block in `from..=to`, in ascending
+    /// order.
+    ///
+    /// The `to` block is always included, even if it's not on a step boundary. Blocks that don't
+    /// exist are skipped.
+    fn canonical_checkpoints(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        step: NonZeroU64,
+    ) -> ProviderResult<Vec<(BlockNumber, B256)>> {
+        let mut checkpoints = Vec::new();
+        let mut number = from;
+        while number <= to {
+            if let Some(hash) = self.block_hash(number)? {
+                checkpoints.push((number, hash));
+            }
+            number = match number.checked_add(step.get()) {
+                // make sure the `to` block is included
+                Some(next) if next > to && number < to => to,
+                Some(next) => next,
+                None if number < to => to,
+                None => break,
+            };
+        }
+        Ok(checkpoints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Hashes;
+
+    impl BlockHashReader for Hashes {
+        fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+            Ok((number <= 10).then(|| B256::with_last_byte(number as u8)))
+        }
+
+        fn canonical_hashes_range(
+            &self,
+            _start: BlockNumber,
+            _end: BlockNumber,
+        ) -> ProviderResult<Vec<B256>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn canonical_checkpoints() {
+        let numbers = |from, to, step| {
+            Hashes
+                .canonical_checkpoints(from, to, NonZeroU64::new(step).unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|(number, hash)| {
+                    assert_eq!(hash, B256::with_last_byte(number as u8));
+                    number
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(0, 10, 5), vec![0, 5, 10]);
+        assert_eq!(numbers(1, 8, 3), vec![1, 4, 7, 8]);
+        assert_eq!(numbers(2, 2, 3), vec![2]);
+        assert_eq!(numbers(3, 2, 1), Vec::<u64>::new());
+        // missing blocks are skipped
+        assert_eq!(numbers(8, 12, 3), vec![8]);
+        assert_eq!(numbers(u64::MAX - 1, u64::MAX, 5), Vec::<u64>::new());
+    }
 }
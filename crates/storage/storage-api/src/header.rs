@@ -3,7 +3,7 @@ use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{BlockHash, BlockNumber};
 use core::ops::RangeBounds;
 use reth_primitives_traits::{BlockHeader, SealedHeader};
-use reth_storage_errors::provider::ProviderResult;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// A helper type alias to access [`HeaderProvider::Header`].
 pub type ProviderHeader<P> = <P as HeaderProvider>::Header;
@@ -56,6 +56,15 @@ pub trait HeaderProvider: Send + Sync {
         number: BlockNumber,
     ) -> ProviderResult<Option<SealedHeader<Self::Header>>>;
 
+    /// Returns the sealed header of the best block of the canonical chain.
+    ///
+    /// Returns [`ProviderError::BestBlockNotFound`] if there are no canonical blocks, and
+    /// [`ProviderError::UnsupportedProvider`] by default for providers that don't track the
+    /// canonical tip.
+    fn best_header(&self) -> ProviderResult<SealedHeader<Self::Header>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
     /// Get headers in range of block numbers.
     fn sealed_headers_range(
         &self,
@@ -96,3 +96,8 @@ pub use header_sync_gap::HeaderSyncGapProvider;
 
 mod full;
 pub use full::*;
+
+#[cfg(feature = "std")]
+mod retry;
+#[cfg(feature = "std")]
+pub use retry::*;
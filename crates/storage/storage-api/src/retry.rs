@@ -0,0 +1,253 @@
+use crate::{BlockHashReader, BlockNumReader, HeaderProvider};
+use alloc::vec::Vec;
+use alloy_primitives::{BlockHash, BlockNumber, B256};
+use core::{
+    ops::{Bound, RangeBounds},
+    time::Duration,
+};
+use reth_chainspec::ChainInfo;
+use reth_primitives_traits::SealedHeader;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Configuration of the [`RetryingProvider`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Maximum number of retries after the initial attempt.
+    pub max_retries: usize,
+    /// Delay before each retry.
+    pub backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self { max_retries: 3, backoff: Duration::from_millis(10) }
+    }
+}
+
+impl RetryConfig {
+    /// Sets the maximum number of retries.
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before each retry.
+    pub const fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+/// A provider wrapper that retries reads failing with a transient database error.
+///
+/// Whether an error is [transient](reth_storage_errors::provider::ProviderError::is_transient) is
+/// decided by the database backend. Other errors are returned immediately.
+///
+/// Retries block the calling thread for the configured backoff, so this wrapper is meant for
+/// blocking contexts only. Don't use it from async tasks, e.g. spawn the reads with
+/// `spawn_blocking` instead.
+#[derive(Debug, Clone)]
+pub struct RetryingProvider<P> {
+    inner: P,
+    config: RetryConfig,
+}
+
+impl<P> RetryingProvider<P> {
+    /// Creates a new [`RetryingProvider`] with the default [`RetryConfig`].
+    pub fn new(inner: P) -> Self {
+        Self::with_config(inner, RetryConfig::default())
+    }
+
+    /// Creates a new [`RetryingProvider`] with the given [`RetryConfig`].
+    pub const fn with_config(inner: P, config: RetryConfig) -> Self {
+        Self { inner, config }
+    }
+
+    /// Returns the wrapped provider.
+    pub const fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Returns the retry configuration.
+    pub const fn config(&self) -> &RetryConfig {
+        &self.config
+    }
+
+    /// Consumes the wrapper and returns the wrapped provider.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Runs the given read, retrying it while it fails with a transient error and retries are
+    /// left.
+    pub fn retry<T>(&self, mut f: impl FnMut(&P) -> ProviderResult<T>) -> ProviderResult<T> {
+        let mut retries = 0;
+        loop {
+            match f(&self.inner) {
+                Err(err) if err.is_transient() && retries < self.config.max_retries => {
+                    retries += 1;
+                    std::thread::sleep(self.config.backoff);
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+/// Copies the bounds of the range so it can be passed to multiple attempts.
+fn range_bounds(range: impl RangeBounds<BlockNumber>) -> (Bound<BlockNumber>, Bound<BlockNumber>) {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
+
+impl<P: BlockHashReader> BlockHashReader for RetryingProvider<P> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.retry(|p| p.block_hash(number))
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.retry(|p| p.canonical_hashes_range(start, end))
+    }
+}
+
+impl<P: BlockNumReader> BlockNumReader for RetryingProvider<P> {
+    fn chain_info(&self) -> ProviderResult<ChainInfo> {
+        self.retry(|p| p.chain_info())
+    }
+
+    fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.retry(|p| p.best_block_number())
+    }
+
+    fn last_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.retry(|p| p.last_block_number())
+    }
+
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.retry(|p| p.earliest_block_number())
+    }
+
+    fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
+        self.retry(|p| p.block_number(hash))
+    }
+}
+
+impl<P: HeaderProvider> HeaderProvider for RetryingProvider<P> {
+    type Header = P::Header;
+
+    fn header(&self, block_hash: BlockHash) -> ProviderResult<Option<Self::Header>> {
+        self.retry(|p| p.header(block_hash))
+    }
+
+    fn sealed_header_by_hash(
+        &self,
+        block_hash: BlockHash,
+    ) -> ProviderResult<Option<SealedHeader<Self::Header>>> {
+        self.retry(|p| p.sealed_header_by_hash(block_hash))
+    }
+
+    fn header_by_number(&self, num: u64) -> ProviderResult<Option<Self::Header>> {
+        self.retry(|p| p.header_by_number(num))
+    }
+
+    fn headers_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Self::Header>> {
+        let range = range_bounds(range);
+        self.retry(|p| p.headers_range(range))
+    }
+
+    fn sealed_header(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<SealedHeader<Self::Header>>> {
+        self.retry(|p| p.sealed_header(number))
+    }
+
+    fn best_header(&self) -> ProviderResult<SealedHeader<Self::Header>> {
+        self.retry(|p| p.best_header())
+    }
+
+    fn sealed_headers_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader<Self::Header>>> {
+        let range = range_bounds(range);
+        self.retry(|p| p.sealed_headers_range(range))
+    }
+
+    fn sealed_headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        mut predicate: impl FnMut(&SealedHeader<Self::Header>) -> bool,
+    ) -> ProviderResult<Vec<SealedHeader<Self::Header>>> {
+        let range = range_bounds(range);
+        self.retry(|p| p.sealed_headers_while(range, &mut predicate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_storage_errors::{
+        db::{DatabaseError, DatabaseErrorInfo},
+        provider::ProviderError,
+    };
+    use std::sync::Mutex;
+
+    /// Provider that returns the queued errors before succeeding.
+    struct FlakyProvider {
+        errors: Mutex<Vec<ProviderError>>,
+    }
+
+    impl BlockHashReader for FlakyProvider {
+        fn block_hash(&self, _number: BlockNumber) -> ProviderResult<Option<B256>> {
+            match self.errors.lock().unwrap().pop() {
+                Some(err) => Err(err),
+                None => Ok(Some(B256::with_last_byte(1))),
+            }
+        }
+
+        fn canonical_hashes_range(
+            &self,
+            _start: BlockNumber,
+            _end: BlockNumber,
+        ) -> ProviderResult<Vec<B256>> {
+            unimplemented!()
+        }
+    }
+
+    fn read_error(transient: bool) -> ProviderError {
+        DatabaseError::Read(DatabaseErrorInfo::new("read", -1).with_transient(transient)).into()
+    }
+
+    fn flaky(errors: Vec<ProviderError>, max_retries: usize) -> RetryingProvider<FlakyProvider> {
+        RetryingProvider::with_config(
+            FlakyProvider { errors: Mutex::new(errors) },
+            RetryConfig::default().with_max_retries(max_retries).with_backoff(Duration::ZERO),
+        )
+    }
+
+    #[test]
+    fn retries_transient_errors() {
+        // two transient errors, then success
+        let provider = flaky(vec![read_error(true), read_error(true)], 2);
+        assert_eq!(provider.block_hash(1).unwrap(), Some(B256::with_last_byte(1)));
+        assert!(provider.inner().errors.lock().unwrap().is_empty());
+
+        // out of retries
+        let provider = flaky(vec![read_error(true), read_error(true)], 1);
+        assert!(provider.block_hash(1).unwrap_err().is_transient());
+    }
+
+    #[test]
+    fn propagates_permanent_errors() {
+        let provider = flaky(vec![read_error(false), read_error(true)], 3);
+        assert!(!provider.block_hash(1).unwrap_err().is_transient());
+        assert!(provider.inner().errors.lock().unwrap().is_empty());
+    }
+}
@@ -2,13 +2,13 @@ use super::{
     AccountReader, BlockHashReader, BlockIdReader, StateProofProvider, StateRootProvider,
     StorageRootProvider,
 };
-use alloc::boxed::Box;
-use alloy_consensus::constants::KECCAK_EMPTY;
+use alloc::{boxed::Box, collections::BTreeMap};
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, BlockHash, BlockNumber, StorageKey, StorageValue, B256, U256};
 use auto_impl::auto_impl;
-use reth_execution_types::ExecutionOutcome;
-use reth_primitives_traits::Bytecode;
+use reth_execution_types::{Chain, ExecutionOutcome};
+use reth_primitives_traits::{Bytecode, NodePrimitives};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie_common::HashedPostState;
 use revm_database::BundleState;
@@ -103,6 +103,72 @@ pub trait HashedPostStateProvider: Send + Sync {
     fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState;
 }
 
+/// Extension trait for computing the state root of a [`Chain`] tip.
+pub trait ChainStateRootProvider: StateRootProvider + HashedPostStateProvider {
+    /// Returns the state root produced by the tip of the given chain.
+    ///
+    /// The state of the chain is applied on top of this provider, which must provide the state at
+    /// the fork block of the chain. Only the trie nodes touched by the chain are recomputed.
+    fn chain_state_root<N: NodePrimitives>(&self, chain: &Chain<N>) -> ProviderResult<B256> {
+        self.state_root(self.hashed_post_state(chain.execution_outcome().state()))
+    }
+}
+
+impl<T: StateRootProvider + HashedPostStateProvider + ?Sized> ChainStateRootProvider for T {}
+
+/// Caches the state roots computed with [`ChainStateRootProvider::chain_state_root`], keyed by the
+/// chain tip.
+///
+/// The state root of a tip only depends on the blocks leading up to it, so a cached root never
+/// becomes stale. At most `max_len` roots are kept, the roots of the lowest tips are evicted first.
+#[derive(Debug, Clone)]
+pub struct ChainStateRootCache {
+    roots: BTreeMap<(BlockNumber, BlockHash), B256>,
+    max_len: usize,
+}
+
+impl ChainStateRootCache {
+    /// Creates a new cache holding at most `max_len` roots.
+    pub const fn new(max_len: usize) -> Self {
+        Self { roots: BTreeMap::new(), max_len }
+    }
+
+    /// Returns the number of cached roots.
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Returns `true` if no roots are cached.
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Returns the state root produced by the tip of the given chain, computing it with
+    /// [`ChainStateRootProvider::chain_state_root`] if it's not cached yet.
+    ///
+    /// On a cache miss, the provider must provide the state at the fork block of the chain.
+    pub fn state_root<N: NodePrimitives>(
+        &mut self,
+        provider: &(impl ChainStateRootProvider + ?Sized),
+        chain: &Chain<N>,
+    ) -> ProviderResult<B256> {
+        let tip = chain.tip();
+        let key = (tip.number(), tip.hash());
+        if let Some(root) = self.roots.get(&key) {
+            return Ok(*root)
+        }
+
+        let root = provider.chain_state_root(chain)?;
+        if self.max_len > 0 {
+            if self.roots.len() >= self.max_len {
+                self.roots.pop_first();
+            }
+            self.roots.insert(key, root);
+        }
+        Ok(root)
+    }
+}
+
 /// Trait for reading bytecode associated with a given code hash.
 #[auto_impl(&, Arc, Box)]
 pub trait BytecodeReader: Send + Sync {
@@ -200,3 +266,107 @@ pub trait StateProviderFactory: BlockIdReader + Send + Sync {
     /// This will return `None` if there's no pending state.
     fn maybe_pending(&self) -> ProviderResult<Option<StateProviderBox>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{address, keccak256};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use reth_ethereum_primitives::{Block, EthPrimitives};
+    use reth_primitives_traits::{Account, RecoveredBlock};
+    use reth_trie_common::{updates::TrieUpdates, TrieInput};
+
+    /// Derives the state root from the touched accounts and counts the computations.
+    #[derive(Default)]
+    struct MockStateRootProvider {
+        computations: AtomicUsize,
+    }
+
+    impl HashedPostStateProvider for MockStateRootProvider {
+        fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+            let mut hashed_state = HashedPostState::default();
+            for (address, account) in &bundle_state.state {
+                hashed_state
+                    .accounts
+                    .insert(keccak256(address), account.info.is_some().then(Account::default));
+            }
+            hashed_state
+        }
+    }
+
+    impl StateRootProvider for MockStateRootProvider {
+        fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
+            self.computations.fetch_add(1, Ordering::Relaxed);
+            let mut accounts = hashed_state.accounts.into_keys().collect::<Vec<_>>();
+            accounts.sort_unstable();
+            Ok(keccak256(accounts.concat()))
+        }
+
+        fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
+            self.state_root(input.state)
+        }
+
+        fn state_root_with_updates(
+            &self,
+            hashed_state: HashedPostState,
+        ) -> ProviderResult<(B256, TrieUpdates)> {
+            Ok((self.state_root(hashed_state)?, TrieUpdates::default()))
+        }
+
+        fn state_root_from_nodes_with_updates(
+            &self,
+            input: TrieInput,
+        ) -> ProviderResult<(B256, TrieUpdates)> {
+            self.state_root_with_updates(input.state)
+        }
+    }
+
+    fn block(number: BlockNumber, parent_hash: BlockHash) -> RecoveredBlock<Block> {
+        let header = Header { number, parent_hash, ..Default::default() };
+        RecoveredBlock::new_unhashed(Block { header, body: Default::default() }, Vec::new())
+    }
+
+    fn outcome(number: BlockNumber, address: Address) -> ExecutionOutcome {
+        let bundle = BundleState::builder(number..=number)
+            .state_present_account_info(address, Default::default())
+            .build();
+        ExecutionOutcome::new(bundle, vec![Vec::new()], number, Vec::new())
+    }
+
+    #[test]
+    fn cached_chain_state_root() {
+        let (first, second) = (
+            address!("0x0000000000000000000000000000000000000001"),
+            address!("0x0000000000000000000000000000000000000002"),
+        );
+        let block1 = block(1, B256::ZERO);
+        let block2 = block(2, block1.hash());
+        let mut execution_outcome = outcome(1, first);
+        execution_outcome.extend(outcome(2, second));
+        let chain = Chain::<EthPrimitives>::new([block1, block2], execution_outcome, None);
+
+        let provider = MockStateRootProvider::default();
+        let merged = BundleState::builder(1..=2)
+            .state_present_account_info(first, Default::default())
+            .state_present_account_info(second, Default::default())
+            .build();
+        let expected = provider.state_root(provider.hashed_post_state(&merged)).unwrap();
+        assert_eq!(provider.chain_state_root(&chain).unwrap(), expected);
+
+        let mut cache = ChainStateRootCache::new(1);
+        let computations = provider.computations.load(Ordering::Relaxed);
+        assert_eq!(cache.state_root(&provider, &chain).unwrap(), expected);
+        assert_eq!(cache.state_root(&provider, &chain).unwrap(), expected);
+        assert_eq!(provider.computations.load(Ordering::Relaxed), computations + 1);
+        assert_eq!(cache.len(), 1);
+
+        // another tip evicts the lowest one
+        let parent =
+            Chain::<EthPrimitives>::from_block(block(1, B256::ZERO), outcome(1, first), None);
+        cache.state_root(&provider, &parent).unwrap();
+        assert_eq!(cache.len(), 1);
+        cache.state_root(&provider, &chain).unwrap();
+        assert_eq!(provider.computations.load(Ordering::Relaxed), computations + 3);
+    }
+}
@@ -41,4 +41,17 @@ pub trait StorageChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>>;
+
+    /// Iterate over storage changesets of all blocks in the given range and return the storage
+    /// state from before each block, ordered by block number, address and storage key.
+    fn storage_changesets_range(
+        &self,
+        range: core::ops::Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>> {
+        let mut changesets = Vec::new();
+        for block_number in range {
+            changesets.extend(self.storage_changeset(block_number)?);
+        }
+        Ok(changesets)
+    }
 }
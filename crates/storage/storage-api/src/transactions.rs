@@ -2,7 +2,7 @@ use crate::{BlockNumReader, BlockReader};
 use alloc::vec::Vec;
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::{Address, BlockNumber, TxHash, TxNumber};
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber};
 use core::ops::{Range, RangeBounds, RangeInclusive};
 use reth_primitives_traits::SignedTransaction;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -106,6 +106,29 @@ pub trait TransactionsProviderExt: BlockReader {
         Ok(from..=to)
     }
 
+    /// Get the number and hash of the block the transaction was mined in, together with the index
+    /// of the transaction in that block.
+    ///
+    /// Returns `None` if the transaction is not found.
+    fn transaction_block_by_hash(
+        &self,
+        tx_hash: TxHash,
+    ) -> ProviderResult<Option<(BlockNumber, BlockHash, usize)>> {
+        let Some(tx_id) = self.transaction_id(tx_hash)? else { return Ok(None) };
+        let Some(block_number) = self.transaction_block(tx_id)? else { return Ok(None) };
+
+        let block_hash = self
+            .block_hash(block_number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+        let body = self
+            .block_body_indices(block_number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+
+        // `tx_id` is always `>=` the block's first transaction number
+        let index = (tx_id - body.first_tx_num()) as usize;
+        Ok(Some((block_number, block_hash, index)))
+    }
+
     /// Get transaction hashes from a transaction range.
     fn transaction_hashes_by_range(
         &self,